@@ -0,0 +1,56 @@
+use anyhow::Result;
+use alloy::primitives::address;
+use rustyarb::{
+    collectors::replay::ReplayCollector,
+    engine::Engine,
+    executors::null::NullExecutor,
+    strategies::hype_usdc_cross_arbitrage::{HypeUsdcCrossArbitrage, Event, Action},
+};
+use tracing::{info, Level};
+use tracing_subscriber::{filter, prelude::*};
+
+// Usage: cargo run --example backtest -- <recording.jsonl> [min_profit_bps]
+#[tokio::main]
+async fn main() -> Result<()> {
+    let filter = filter::Targets::new().with_target("rustyarb", Level::WARN).with_target("backtest", Level::INFO);
+    tracing_subscriber::registry()
+        .with(tracing_subscriber::fmt::layer())
+        .with(filter)
+        .init();
+
+    let mut args = std::env::args().skip(1);
+    let path = args.next().expect("usage: backtest <recording.jsonl> [min_profit_bps]");
+    let min_profit_bps: f64 = args.next().map(|s| s.parse()).transpose()?.unwrap_or(10.0);
+
+    let usdc = address!("0xb88339cb7199b77e23db6e890353e22632ba630f");
+    let hype = address!("0x5555555555555555555555555555555555555555");
+
+    let strategy = HypeUsdcCrossArbitrage::new(
+        20.0,    // order_size_usd
+        2.0,     // hl_maker_fee_bps
+        0.0001,  // dex_gas_fee_usd
+        min_profit_bps,
+        usdc,
+        hype,
+        3000,
+    );
+
+    let executor = NullExecutor::<Action>::new();
+    let recorded = executor.recorded();
+
+    let mut engine: Engine<Event, Action> = Engine::new()
+        .with_event_channel_capacity(100_000)
+        .with_action_channel_capacity(100_000);
+    engine.add_collector(Box::new(ReplayCollector::<Event>::new(&path)));
+    engine.add_strategy(Box::new(strategy));
+    engine.add_executor(Box::new(executor));
+
+    if let Ok(mut set) = engine.run().await {
+        while set.join_next().await.is_some() {}
+    }
+
+    let actions = recorded.lock().unwrap();
+    info!("📊 {} signals at {} bps threshold", actions.len(), min_profit_bps);
+
+    Ok(())
+}
@@ -11,9 +11,9 @@ use rustyarb::{
     executors::{
         arbitrage::{ArbitrageExecutor, ArbitrageAction},
         univ3::{UniV3Executor, UniV3SwapAction},
-        hyperliquid::{HyperliquidExecutor, HyperliquidOrderAction},
+        hyperliquid::{HyperliquidExecutor, HyperliquidOrderAction, OrderMode, TimeInForce},
     },
-    types::Executor,
+    types::{Direction, Executor},
 };
 use tracing::{info, Level};
 use tracing_subscriber::{filter, prelude::*};
@@ -48,7 +48,7 @@ async fn main() -> Result<()> {
     let arb_executor = ArbitrageExecutor::new(
         UniV3Executor::new(provider.clone(), &private_key, router_address)?,
         HyperliquidExecutor::new(private_key.clone())?,
-        exec_manager,
+        exec_manager.clone(),
         15,  // cooldown_secs
     );
 
@@ -66,8 +66,16 @@ async fn main() -> Result<()> {
                 is_buy: false,
                 size: 0.3,
                 limit_px: 20.0,
+                tif: TimeInForce::Ioc,
+                mode: OrderMode::Limit,
             },
-            direction: "Buy DEX → Sell HL".to_string(),
+            direction: Direction::BuyDexSellCex,
+            expected_dex_out: U256::ZERO,
+            decision_hl_px: 20.0,
+            net_profit_bps: 0.0,
+            expected_gross_profit_usd: 0.0,
+            native_px_usd: 20.0,
+            observed_at: None,
         },
         ArbitrageAction {
             dex_swap: UniV3SwapAction {
@@ -82,14 +90,26 @@ async fn main() -> Result<()> {
                 is_buy: true,
                 size: 0.3,
                 limit_px: 40.0,
+                tif: TimeInForce::Ioc,
+                mode: OrderMode::Limit,
             },
-            direction: "Buy HL → Sell DEX".to_string(),
+            direction: Direction::BuyCexSellDex,
+            expected_dex_out: U256::ZERO,
+            decision_hl_px: 40.0,
+            net_profit_bps: 0.0,
+            expected_gross_profit_usd: 0.0,
+            native_px_usd: 40.0,
+            observed_at: None,
         },
     ];
 
     info!("🧪 Testing both directions | $11 orders | 15s cooldown");
 
     for (i, test_action) in test_scenarios.into_iter().enumerate() {
+        // Cooldown no longer blocks inside execute; wait it out between tests
+        if let Some(remaining) = exec_manager.cooldown_remaining() {
+            tokio::time::sleep(remaining).await;
+        }
         info!("Test {}: {}", i + 1, test_action.direction);
         
         let start = std::time::Instant::now();
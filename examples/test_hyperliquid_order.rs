@@ -1,5 +1,5 @@
 use anyhow::Result;
-use rustyarb::executors::hyperliquid::{HyperliquidExecutor, HyperliquidOrderAction};
+use rustyarb::executors::hyperliquid::{HyperliquidExecutor, HyperliquidOrderAction, OrderMode, TimeInForce};
 use rustyarb::types::Executor;
 use tracing::{info, Level};
 use tracing_subscriber::{filter, prelude::*};
@@ -23,6 +23,8 @@ async fn main() -> Result<()> {
         is_buy: false,
         size: 1.0,
         limit_px: 32.0, // ~$40 + 20% = $48 (within 95% tolerance)
+        tif: TimeInForce::Ioc,
+        mode: OrderMode::Limit,
     };
 
     info!("Placing test order: BUY {} {} @ ${:.2}", test_action.size, test_action.coin, test_action.limit_px);
@@ -0,0 +1,421 @@
+use std::sync::{atomic::Ordering, Arc};
+use std::time::Duration;
+
+use alloy::{primitives::Address, providers::Provider, signers::local::PrivateKeySigner};
+use anyhow::Result;
+use tracing::info;
+
+use crate::collectors::{
+    balance::BalanceCollector,
+    binance::BinanceCollector,
+    cex::CexVenue,
+    hyperliquid::{HyperliquidCollector, HyperliquidFundingCollector},
+    uniswapv3::UniV3Collector,
+};
+use crate::config::{Config, StrategyConfig};
+use crate::engine::StrategyBundle;
+use crate::execution::{CircuitBreaker, EdgeGauge, ExecutionManager, PositionTracker, SlippageMonitor};
+use crate::executors::{
+    arbitrage::{ArbitrageExecutor, TwapConfig},
+    binance::BinanceExecutor,
+    hyperliquid::{AssetPrecision, HyperliquidExecutor, RepricePolicy, RestingOrders},
+    univ3::{UniV3Executor, WHYPE},
+};
+use crate::strategies::hype_usdc_cross_arbitrage::{Action, Event, HypeUsdcCrossArbitrage};
+use crate::types::{Collector, CollectorMap};
+use crate::utilities::{
+    alerts::Notifier,
+    health::HealthState,
+    price_oracle::NativePriceOracle,
+    reconcile::Reconciler,
+    retry::RetryPolicy,
+    tokens::TokenRegistry,
+};
+
+/// Wires the collectors, strategy and executor for one cross-exchange strategy config.
+/// Services shared across strategies (alerts, breaker, price oracle, health) are passed in.
+pub struct StrategyBuilder<'a, P> {
+    config: &'a Config,
+    strategy_config: &'a StrategyConfig,
+    provider: Arc<P>,
+    private_key: String,
+    notifier: Option<Arc<dyn Notifier>>,
+    circuit_breaker: Option<Arc<CircuitBreaker>>,
+    native_price: Option<Arc<NativePriceOracle>>,
+    health: Option<Arc<HealthState>>,
+    http_fallback: Option<Arc<P>>,
+    poll_pools: bool,
+    tokens: Option<Arc<TokenRegistry<P>>>,
+}
+
+impl<'a, P: Provider + 'static> StrategyBuilder<'a, P> {
+    pub fn new(config: &'a Config, strategy_config: &'a StrategyConfig, provider: Arc<P>, private_key: String) -> Self {
+        Self {
+            config,
+            strategy_config,
+            provider,
+            private_key,
+            notifier: None,
+            circuit_breaker: None,
+            native_price: None,
+            health: None,
+            http_fallback: None,
+            poll_pools: false,
+            tokens: None,
+        }
+    }
+
+    pub fn with_notifier(mut self, notifier: Option<Arc<dyn Notifier>>) -> Self {
+        self.notifier = notifier;
+        self
+    }
+
+    pub fn with_circuit_breaker(mut self, breaker: Option<Arc<CircuitBreaker>>) -> Self {
+        self.circuit_breaker = breaker;
+        self
+    }
+
+    pub fn with_native_price(mut self, oracle: Option<Arc<NativePriceOracle>>) -> Self {
+        self.native_price = oracle;
+        self
+    }
+
+    /// Register the executor's halt conditions as readiness checks on `health`
+    pub fn with_health(mut self, health: Arc<HealthState>) -> Self {
+        self.health = Some(health);
+        self
+    }
+
+    /// HTTP provider the pool collector polls through if its subscription keeps failing
+    pub fn with_http_fallback(mut self, provider: Option<Arc<P>>) -> Self {
+        self.http_fallback = provider;
+        self
+    }
+
+    /// Poll pool state through the main provider instead of subscribing (it is HTTP-only)
+    pub fn with_pool_polling(mut self, poll: bool) -> Self {
+        self.poll_pools = poll;
+        self
+    }
+
+    /// Token metadata cache shared across strategies
+    pub fn with_token_registry(mut self, tokens: Arc<TokenRegistry<P>>) -> Self {
+        self.tokens = Some(tokens);
+        self
+    }
+
+    fn register_halt_check(&self, name: &str, is_halted: impl Fn() -> bool + Send + Sync + 'static) {
+        if let Some(health) = &self.health {
+            health.register_halt_check(name, is_halted);
+        }
+    }
+
+    fn collectors(&self, owner: Address) -> Result<Vec<Box<dyn Collector<Event>>>> {
+        let strategy_config = self.strategy_config;
+        let mut collectors: Vec<Box<dyn Collector<Event>>> = Vec::new();
+
+        // DEX collector (UniswapV3) watching every configured pool for the pair
+        let mut pools = vec![strategy_config.pool_address.parse()?];
+        for pool in &strategy_config.pool_addresses {
+            pools.push(pool.parse()?);
+        }
+        let poll_interval = Duration::from_millis(self.config.http_poll_ms);
+        let mut univ3_collector = UniV3Collector::with_pools(self.provider.clone(), pools);
+        if self.poll_pools {
+            univ3_collector = univ3_collector.with_polling(poll_interval);
+        } else if let Some(http) = &self.http_fallback {
+            univ3_collector = univ3_collector.with_http_fallback(http.clone(), poll_interval);
+        }
+        collectors.push(Box::new(CollectorMap::new(
+            Box::new(univ3_collector),
+            |pool_state| Event::PoolUpdate(pool_state),
+        )));
+
+        // CEX collector for the hedge venue
+        match strategy_config.cex_venue {
+            CexVenue::Hyperliquid => {
+                let mut hl_collector = HyperliquidCollector::new(strategy_config.hyperliquid_coin.clone());
+                if let Some(secs) = strategy_config.hl_stall_timeout_secs {
+                    hl_collector = hl_collector.with_stall_timeout(Duration::from_secs(secs));
+                }
+                collectors.push(Box::new(CollectorMap::new(
+                    Box::new(hl_collector),
+                    |bbo| Event::HyperliquidBbo(bbo),
+                )));
+            }
+            CexVenue::Binance => {
+                let binance_collector = Box::new(BinanceCollector::new(
+                    strategy_config.hedge_coin().to_string()
+                ));
+                collectors.push(Box::new(CollectorMap::new(
+                    binance_collector,
+                    |bbo| Event::CexBbo(bbo),
+                )));
+            }
+        }
+
+        // Funding collector for perp hedges
+        if strategy_config.funding_aware {
+            let funding_collector = Box::new(HyperliquidFundingCollector::new(
+                strategy_config.hyperliquid_coin.clone()
+            ));
+            collectors.push(Box::new(CollectorMap::new(
+                funding_collector,
+                |funding| Event::FundingRate(funding),
+            )));
+        }
+
+        // Wallet balance collector for the low-gas halt
+        if strategy_config.min_gas_balance.is_some() {
+            let balance_collector = Box::new(BalanceCollector::new(
+                self.provider.clone(),
+                owner,
+                vec![strategy_config.token_a_address.parse()?, strategy_config.token_b_address.parse()?],
+            ).with_poll_interval(Duration::from_secs(strategy_config.balance_poll_secs)));
+            collectors.push(Box::new(CollectorMap::new(
+                balance_collector,
+                |balances| Event::BalanceUpdate(balances),
+            )));
+        }
+
+        Ok(collectors)
+    }
+
+    /// Build the engine components, plus a handle to any HL orders the executor leaves
+    /// resting so the caller can cancel them on shutdown
+    pub fn build(self) -> Result<(StrategyBundle<Event, Action>, Arc<RestingOrders>)> {
+        let config = self.config;
+        let strategy_config = self.strategy_config;
+        let private_key = &self.private_key;
+        let owner = private_key.parse::<PrivateKeySigner>()?.address();
+        let router_address: Address = strategy_config.router_address.parse()?;
+
+        let collectors = self.collectors(owner)?;
+
+        let mut strategy = HypeUsdcCrossArbitrage::from_config(strategy_config)?;
+        if config.heartbeat_secs > 0 {
+            strategy = strategy.with_status_interval(Duration::from_secs(config.heartbeat_secs));
+        }
+        if config.startup_grace_secs > 0 {
+            strategy = strategy.with_startup_grace(Duration::from_secs(config.startup_grace_secs));
+        }
+        if let (Some(gas_native), Some(oracle)) = (strategy_config.dex_gas_fee_native, &self.native_price) {
+            strategy = strategy.with_native_gas(gas_native, oracle.clone());
+        }
+        let edge_gauge = Arc::new(EdgeGauge::new());
+        let twap_enabled = strategy_config.twap_slices > 1 || strategy_config.max_clip_usd.is_some();
+        if twap_enabled || strategy_config.recheck_edge {
+            strategy = strategy.with_edge_gauge(edge_gauge.clone());
+        }
+        let position_tracker = Arc::new(PositionTracker::new(Duration::from_secs(3600)));
+        if let Some(max_notional) = strategy_config.max_notional_per_hour_usd {
+            strategy = strategy.with_notional_cap(max_notional, position_tracker.clone());
+        }
+
+        // Per-strategy execution manager (1 execution at a time per strategy)
+        let exec_manager = Arc::new(ExecutionManager::new(1));
+
+        let mut hl_executor = match std::env::var("HL_API_PRIVATE_KEY") {
+            Ok(api_key) => {
+                let account = match &strategy_config.hl_account_address {
+                    Some(account) => account.parse()?,
+                    None => owner,
+                };
+                info!("    HL orders signed by API wallet for {}", account);
+                HyperliquidExecutor::new(api_key)?.with_account_address(account)
+            }
+            Err(_) => HyperliquidExecutor::new(private_key.clone())?,
+        };
+        if let Some(vault) = &strategy_config.hl_vault_address {
+            let vault_address: Address = vault.parse()?;
+            info!("    HL orders routed via vault {}", vault_address);
+            hl_executor = hl_executor.with_vault_address(vault_address);
+        }
+        if let Some(sz_decimals) = strategy_config.hl_fallback_sz_decimals {
+            hl_executor = hl_executor.with_fallback_precision(
+                AssetPrecision::for_coin(&strategy_config.hyperliquid_coin, sz_decimals)
+            );
+        }
+        if strategy_config.hl_max_reprices > 0 {
+            hl_executor = hl_executor.with_repricing(RepricePolicy {
+                max_reprices: strategy_config.hl_max_reprices,
+                interval: Duration::from_millis(strategy_config.hl_reprice_interval_ms),
+                deadline: Duration::from_millis(strategy_config.hl_reprice_deadline_ms),
+            });
+        }
+        let resting_orders = hl_executor.resting_orders();
+        let hl_user = hl_executor.user_address();
+
+        let mut dex_executor = UniV3Executor::new(self.provider.clone(), private_key, router_address)?
+            .with_swap_deadline(Duration::from_secs(strategy_config.swap_deadline_secs));
+        if strategy_config.simulate_first {
+            dex_executor = dex_executor.with_simulation(strategy_config.slippage_bps);
+        }
+        if strategy_config.auto_wrap {
+            dex_executor = dex_executor.with_auto_wrap(WHYPE);
+        }
+        if strategy_config.manual_nonce {
+            dex_executor = dex_executor.with_manual_nonce();
+        }
+        if let Some(recipient) = &strategy_config.swap_recipient {
+            dex_executor = dex_executor.with_recipient(recipient.parse()?);
+        }
+        if let Some(cap_gwei) = strategy_config.max_gas_price_gwei {
+            dex_executor = dex_executor.with_max_gas_price(cap_gwei);
+        }
+
+        let mut arb_executor = ArbitrageExecutor::new(
+            dex_executor,
+            hl_executor,
+            exec_manager.clone(),
+            config.cooldown_secs,
+        )
+        .with_hl_fee_bps(strategy_config.hedge_fee_bps());
+        if strategy_config.cex_venue == CexVenue::Binance {
+            info!("    Hedging on Binance {}", strategy_config.hedge_coin());
+            arb_executor = arb_executor.with_binance_hedge(BinanceExecutor::from_env()?);
+        }
+        if let Some(secs) = strategy_config.direction_cooldown_secs {
+            arb_executor = arb_executor.with_direction_cooldown(secs);
+        }
+        if let Some(notifier) = &self.notifier {
+            arb_executor = arb_executor.with_notifier(notifier.clone());
+        }
+        if let Some(breaker) = &self.circuit_breaker {
+            arb_executor = arb_executor.with_circuit_breaker(breaker.clone());
+        }
+        if strategy_config.dex_max_retries > 0 || strategy_config.hl_max_retries > 0 {
+            let retry = |max_retries| RetryPolicy {
+                max_retries,
+                initial_backoff: Duration::from_millis(strategy_config.retry_backoff_ms),
+                ..RetryPolicy::default()
+            };
+            arb_executor = arb_executor.with_retries(
+                retry(strategy_config.dex_max_retries),
+                retry(strategy_config.hl_max_retries),
+            );
+        }
+        if strategy_config.auto_unwind {
+            arb_executor = arb_executor.with_auto_unwind(strategy_config.unwind_max_loss_bps);
+            let halted = arb_executor.unwind_halt_flag();
+            self.register_halt_check(
+                &format!("{} unwind", strategy_config.name),
+                move || halted.load(Ordering::SeqCst),
+            );
+        }
+        if let Some(max_slippage_bps) = strategy_config.max_realized_slippage_bps {
+            let monitor = Arc::new(SlippageMonitor::new(
+                max_slippage_bps,
+                strategy_config.max_slippage_breaches,
+            ));
+            let halted = monitor.clone();
+            self.register_halt_check(&strategy_config.name, move || halted.is_halted());
+            arb_executor = arb_executor.with_slippage_monitor(monitor);
+        }
+        if strategy_config.max_notional_per_hour_usd.is_some() {
+            arb_executor = arb_executor.with_position_tracker(position_tracker);
+        }
+        arb_executor = arb_executor.with_submission_mode(strategy_config.submission_mode);
+        if let Some(secs) = strategy_config.reconcile_interval_secs {
+            let coin = strategy_config.hyperliquid_coin.split('/').next().unwrap_or_default().to_string();
+            let mut reconciler = Reconciler::new(
+                self.provider.clone(),
+                owner,
+                hl_user,
+                strategy_config.token_b_address.parse()?,
+                coin,
+            )
+            .with_interval(Duration::from_secs(secs))
+            .with_thresholds(strategy_config.reconcile_alert_drift, strategy_config.reconcile_halt_drift)
+            .with_halt_samples(strategy_config.reconcile_halt_samples)
+            .with_execution_manager(exec_manager.clone());
+            if strategy_config.auto_wrap {
+                reconciler = reconciler.with_native();
+            }
+            if let Some(notifier) = &self.notifier {
+                reconciler = reconciler.with_notifier(notifier.clone());
+            }
+            if let Some(tokens) = &self.tokens {
+                reconciler = reconciler.with_token_registry(tokens.clone());
+            }
+            let drift = reconciler.drift();
+            reconciler.spawn();
+            arb_executor = arb_executor.with_inventory_drift(drift.clone());
+            self.register_halt_check(
+                &format!("{} inventory drift", strategy_config.name),
+                move || drift.is_halted(),
+            );
+        }
+        if strategy_config.recheck_edge {
+            arb_executor = arb_executor.with_edge_recheck(edge_gauge.clone(), strategy_config.min_profit_bps);
+        }
+        if twap_enabled {
+            arb_executor = arb_executor.with_twap(TwapConfig {
+                slices: strategy_config.twap_slices,
+                max_clip_usd: strategy_config.max_clip_usd,
+                interval: Duration::from_millis(strategy_config.twap_interval_ms),
+                min_notional_usd: strategy_config.twap_min_notional_usd,
+                min_edge_bps: strategy_config.min_profit_bps,
+                edge: edge_gauge,
+            });
+        }
+
+        let bundle = StrategyBundle {
+            collectors,
+            strategy: Box::new(strategy),
+            executors: vec![Box::new(arb_executor)],
+        };
+        Ok((bundle, resting_orders))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::providers::ProviderBuilder;
+
+    use super::*;
+
+    // Well-known dev key; nothing is sent
+    const DEV_KEY: &str = "0x59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d";
+
+    fn config(extra: &str) -> Config {
+        toml::from_str(&format!(r#"
+            max_concurrent = 1
+            cooldown_secs = 15
+
+            [[strategies]]
+            name = "hype-usdc"
+            enabled = true
+            pool_address = "0xe712d505572b3f84c1b4deb99e1beab9dd0e23c9"
+            router_address = "0x6D99e7f6747AF2cDbB5164b6DD50e40D4fDe1e77"
+            fee = 3000
+            token_a_address = "0xb88339cb7199b77e23db6e890353e22632ba630f"
+            token_b_address = "0x5555555555555555555555555555555555555555"
+            hyperliquid_coin = "HYPE/USDC"
+            order_size_usd = 100.0
+            hl_maker_fee_bps = 1.5
+            dex_gas_fee_usd = 0.05
+            min_profit_bps = 10.0
+            slippage_bps = 50.0
+            {}
+        "#, extra)).unwrap()
+    }
+
+    fn collector_names(config: &Config) -> Vec<String> {
+        let provider = Arc::new(ProviderBuilder::new().connect_http("http://127.0.0.1:8545".parse().unwrap()));
+        let (bundle, resting) = StrategyBuilder::new(config, &config.strategies[0], provider, DEV_KEY.to_string())
+            .build()
+            .unwrap();
+        assert_eq!(bundle.executors.len(), 1);
+        assert!(resting.is_empty());
+        bundle.collectors.iter().map(|collector| collector.name().to_string()).collect()
+    }
+
+    #[test]
+    fn bundle_holds_the_collectors_the_config_asks_for() {
+        assert_eq!(collector_names(&config("")), ["univ3", "hyperliquid"]);
+
+        let config = config("funding_aware = true\nmin_gas_balance = 0.1");
+        assert_eq!(collector_names(&config), ["univ3", "hyperliquid", "hl-funding", "balance"]);
+    }
+}
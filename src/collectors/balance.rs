@@ -0,0 +1,112 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use alloy::{
+    primitives::{Address, U256},
+    providers::Provider,
+    sol,
+};
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc::unbounded_channel;
+use tokio_stream::wrappers::UnboundedReceiverStream;
+use tracing::warn;
+
+use crate::types::{Collector, CollectorStream};
+
+sol! {
+    #[sol(rpc)]
+    interface IERC20Balance {
+        function balanceOf(address owner) external view returns (uint256);
+        function decimals() external view returns (uint8);
+        function symbol() external view returns (string);
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenBalance {
+    pub token: Address,
+    pub balance: U256,
+}
+
+/// Wallet balances at one point in time
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BalanceUpdate {
+    pub owner: Address,
+    /// Native gas token balance in wei
+    pub native: U256,
+    pub tokens: Vec<TokenBalance>,
+}
+
+impl BalanceUpdate {
+    pub fn token(&self, token: Address) -> Option<U256> {
+        self.tokens.iter().find(|t| t.token == token).map(|t| t.balance)
+    }
+}
+
+/// Polls the native balance and each listed ERC20 balance of `owner`
+pub struct BalanceCollector<P> {
+    provider: Arc<P>,
+    owner: Address,
+    tokens: Vec<Address>,
+    poll_interval: Duration,
+}
+
+impl<P> BalanceCollector<P> {
+    pub fn new(provider: Arc<P>, owner: Address, tokens: Vec<Address>) -> Self {
+        Self { provider, owner, tokens, poll_interval: Duration::from_secs(30) }
+    }
+
+    pub fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+}
+
+async fn fetch_balances<P: Provider>(provider: &P, owner: Address, tokens: &[Address]) -> Result<BalanceUpdate> {
+    let native = provider.get_balance(owner).await?;
+    let mut balances = Vec::with_capacity(tokens.len());
+    for &token in tokens {
+        let balance = IERC20Balance::new(token, provider).balanceOf(owner).call().await?;
+        balances.push(TokenBalance { token, balance });
+    }
+    Ok(BalanceUpdate { owner, native, tokens: balances })
+}
+
+#[async_trait]
+impl<P> Collector<BalanceUpdate> for BalanceCollector<P>
+where
+    P: Provider + 'static,
+{
+    async fn get_event_stream(&self) -> Result<CollectorStream<'_, BalanceUpdate>> {
+        let (sender, receiver) = unbounded_channel();
+        let provider = self.provider.clone();
+        let owner = self.owner;
+        let tokens = self.tokens.clone();
+        let poll_interval = self.poll_interval;
+
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(poll_interval);
+            loop {
+                interval.tick().await;
+                let update = match fetch_balances(&*provider, owner, &tokens).await {
+                    Ok(update) => update,
+                    Err(e) => {
+                        warn!("Balance poll failed: {}", e);
+                        continue;
+                    }
+                };
+                if sender.send(update).is_err() {
+                    break;
+                }
+            }
+        });
+
+        Ok(Box::pin(UnboundedReceiverStream::new(receiver)))
+    }
+
+    fn name(&self) -> &str {
+        "balance"
+    }
+}
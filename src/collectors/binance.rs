@@ -0,0 +1,146 @@
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use anyhow::Result;
+use async_trait::async_trait;
+use futures_util::{SinkExt, StreamExt};
+use serde::Deserialize;
+use tokio::sync::mpsc::{unbounded_channel, UnboundedSender};
+use tokio_stream::wrappers::UnboundedReceiverStream;
+use tokio_tungstenite::{connect_async, tungstenite::Message};
+use tracing::{info, warn};
+
+use crate::collectors::cex::{CexBbo, CexVenue};
+use crate::types::{Collector, CollectorStream};
+
+const BINANCE_WS_URL: &str = "wss://stream.binance.com:9443/ws";
+const RECONNECT_DELAY: Duration = Duration::from_secs(1);
+
+/// `<symbol>@bookTicker` push: best bid/ask price and quantity
+#[derive(Deserialize)]
+struct BookTicker {
+    #[serde(rename = "s")]
+    symbol: String,
+    #[serde(rename = "b")]
+    bid_px: String,
+    #[serde(rename = "a")]
+    ask_px: String,
+}
+
+impl BookTicker {
+    fn into_bbo(self) -> Option<CexBbo> {
+        // Book tickers carry no event time; stamp with local time
+        let time = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0);
+        Some(CexBbo {
+            venue: CexVenue::Binance,
+            coin: self.symbol,
+            bid: self.bid_px.parse().ok()?,
+            ask: self.ask_px.parse().ok()?,
+            time,
+            received_at: Some(Instant::now()),
+        })
+    }
+}
+
+/// Parse one raw book-ticker message
+pub fn parse_book_ticker(text: &str) -> Option<CexBbo> {
+    serde_json::from_str::<BookTicker>(text).ok()?.into_bbo()
+}
+
+/// Streams the book ticker for one Binance spot symbol (e.g. "HYPEUSDT"),
+/// reconnecting whenever the connection drops (Binance cycles them every 24h)
+pub struct BinanceCollector {
+    symbol: String,
+    url: String,
+}
+
+impl BinanceCollector {
+    pub fn new(symbol: String) -> Self {
+        Self { symbol, url: BINANCE_WS_URL.to_string() }
+    }
+
+    /// Override the WebSocket base (e.g. the testnet host)
+    pub fn with_url(mut self, url: String) -> Self {
+        self.url = url;
+        self
+    }
+}
+
+/// Run one connection until it drops; returns Ok only once the consumer is gone
+async fn stream_session(url: &str, symbol: &str, sender: &UnboundedSender<CexBbo>) -> Result<()> {
+    let stream_url = format!("{}/{}@bookTicker", url, symbol.to_lowercase());
+    let (ws, _) = connect_async(stream_url.as_str()).await?;
+    let (mut write, mut read) = ws.split();
+    info!("Binance: subscribed to {} bookTicker", symbol);
+
+    while let Some(msg) = read.next().await {
+        let text = match msg? {
+            Message::Text(text) => text,
+            Message::Ping(payload) => {
+                write.send(Message::Pong(payload)).await?;
+                continue;
+            }
+            Message::Close(frame) => anyhow::bail!("closed by Binance: {:?}", frame),
+            _ => continue,
+        };
+
+        if let Some(bbo) = parse_book_ticker(&text) {
+            if sender.send(bbo).is_err() {
+                return Ok(());
+            }
+        }
+    }
+    anyhow::bail!("connection closed by Binance")
+}
+
+#[async_trait]
+impl Collector<CexBbo> for BinanceCollector {
+    async fn get_event_stream(&self) -> Result<CollectorStream<'_, CexBbo>> {
+        let (sender, receiver) = unbounded_channel();
+        let url = self.url.clone();
+        let symbol = self.symbol.clone();
+
+        tokio::spawn(async move {
+            loop {
+                match stream_session(&url, &symbol, &sender).await {
+                    Ok(()) => break,
+                    Err(e) => warn!("Binance: {} - reconnecting", e),
+                }
+                tokio::time::sleep(RECONNECT_DELAY).await;
+            }
+        });
+
+        Ok(Box::pin(UnboundedReceiverStream::new(receiver)))
+    }
+
+    fn name(&self) -> &str {
+        "binance"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_a_book_ticker_message() {
+        // Sample from the Binance spot WebSocket docs
+        let text = r#"{"u":400900217,"s":"BNBUSDT","b":"25.35190000","B":"31.21000000","a":"25.36520000","A":"40.66000000"}"#;
+        let bbo = parse_book_ticker(text).unwrap();
+        assert_eq!(bbo.venue, CexVenue::Binance);
+        assert_eq!(bbo.coin, "BNBUSDT");
+        assert_eq!(bbo.bid, 25.3519);
+        assert_eq!(bbo.ask, 25.3652);
+        assert!(bbo.time > 0);
+        assert!(bbo.received_at.is_some());
+    }
+
+    #[test]
+    fn ignores_other_messages() {
+        // Subscription acks and malformed prices carry no quote
+        assert!(parse_book_ticker(r#"{"result":null,"id":1}"#).is_none());
+        assert!(parse_book_ticker(r#"{"u":1,"s":"BNBUSDT","b":"n/a","B":"1","a":"25.3652","A":"1"}"#).is_none());
+    }
+}
@@ -0,0 +1,87 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use alloy::providers::Provider;
+use anyhow::Result;
+use async_trait::async_trait;
+use futures_util::StreamExt;
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc::{unbounded_channel, UnboundedSender};
+use tokio_stream::wrappers::UnboundedReceiverStream;
+use tracing::{info, warn};
+
+use crate::types::{Collector, CollectorStream};
+
+const RESUBSCRIBE_DELAY: Duration = Duration::from_secs(1);
+
+/// New chain head, for logic keyed to chain time rather than the wall clock
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlockTick {
+    pub number: u64,
+    /// Block timestamp (seconds since epoch)
+    pub timestamp: u64,
+    /// EIP-1559 base fee in wei; None on chains without one
+    pub base_fee: Option<u64>,
+}
+
+/// Streams `newHeads` from a pubsub provider. A re-announced height (e.g. after a
+/// reorg or a resubscribe) is dropped, so ticks are strictly increasing in `number`.
+pub struct BlockCollector<P> {
+    provider: Arc<P>,
+}
+
+impl<P> BlockCollector<P> {
+    pub fn new(provider: Arc<P>) -> Self {
+        Self { provider }
+    }
+}
+
+/// Forward heads until the subscription ends; returns Ok only once the consumer is gone
+async fn stream_session<P: Provider>(provider: &P, last_number: &mut u64, sender: &UnboundedSender<BlockTick>) -> Result<()> {
+    let mut heads = provider.subscribe_blocks().await?.into_stream();
+    info!("Blocks: subscribed to new heads");
+
+    while let Some(header) = heads.next().await {
+        if header.number <= *last_number {
+            continue;
+        }
+        *last_number = header.number;
+        let tick = BlockTick {
+            number: header.number,
+            timestamp: header.timestamp,
+            base_fee: header.base_fee_per_gas,
+        };
+        if sender.send(tick).is_err() {
+            return Ok(());
+        }
+    }
+    anyhow::bail!("newHeads subscription ended")
+}
+
+#[async_trait]
+impl<P> Collector<BlockTick> for BlockCollector<P>
+where
+    P: Provider + 'static,
+{
+    async fn get_event_stream(&self) -> Result<CollectorStream<'_, BlockTick>> {
+        let (sender, receiver) = unbounded_channel();
+        let provider = self.provider.clone();
+
+        tokio::spawn(async move {
+            let mut last_number = 0;
+            loop {
+                match stream_session(&*provider, &mut last_number, &sender).await {
+                    Ok(()) => break,
+                    Err(e) => warn!("Blocks: {} - resubscribing", e),
+                }
+                tokio::time::sleep(RESUBSCRIBE_DELAY).await;
+            }
+        });
+
+        Ok(Box::pin(UnboundedReceiverStream::new(receiver)))
+    }
+
+    fn name(&self) -> &str {
+        "blocks"
+    }
+}
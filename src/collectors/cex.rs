@@ -0,0 +1,52 @@
+use std::time::Instant;
+
+use serde::{Deserialize, Serialize};
+
+use crate::collectors::hyperliquid::HyperliquidBbo;
+
+/// Centralized venue a strategy hedges on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CexVenue {
+    #[default]
+    Hyperliquid,
+    Binance,
+}
+
+impl CexVenue {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CexVenue::Hyperliquid => "Hyperliquid",
+            CexVenue::Binance => "Binance",
+        }
+    }
+}
+
+/// Venue-neutral top of book, so strategies price the hedge the same way on any CEX
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CexBbo {
+    pub venue: CexVenue,
+    pub coin: String,
+    pub bid: f64,
+    pub ask: f64,
+    /// Exchange timestamp (ms since epoch)
+    pub time: u64,
+    /// When the collector received this update, for latency measurement
+    #[serde(skip)]
+    pub received_at: Option<Instant>,
+}
+
+impl CexBbo {
+    /// None unless both sides of the book are present and parse
+    pub fn from_hyperliquid(bbo: &HyperliquidBbo) -> Option<Self> {
+        let px = |i: usize| bbo.levels.get(i)?.as_ref()?.px.parse::<f64>().ok();
+        Some(Self {
+            venue: CexVenue::Hyperliquid,
+            coin: bbo.coin.clone(),
+            bid: px(0)?,
+            ask: px(1)?,
+            time: bbo.time,
+            received_at: bbo.received_at,
+        })
+    }
+}
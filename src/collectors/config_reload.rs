@@ -0,0 +1,67 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use tokio::signal::unix::{signal, SignalKind};
+use tokio::sync::mpsc::unbounded_channel;
+use tokio_stream::wrappers::UnboundedReceiverStream;
+use tracing::{info, warn};
+
+use crate::config::{Config, StrategyParams};
+use crate::types::{Collector, CollectorStream};
+
+/// Freshly loaded tunables for every configured strategy
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConfigReload {
+    pub strategies: Vec<StrategyParams>,
+}
+
+/// Re-reads the config file on every SIGHUP and emits the strategies' scalar
+/// parameters. A file that fails to load or validate is logged and ignored.
+pub struct ConfigReloadCollector {
+    path: String,
+}
+
+impl ConfigReloadCollector {
+    pub fn new(path: String) -> Self {
+        Self { path }
+    }
+}
+
+#[async_trait]
+impl Collector<ConfigReload> for ConfigReloadCollector {
+    async fn get_event_stream(&self) -> Result<CollectorStream<'_, ConfigReload>> {
+        let (sender, receiver) = unbounded_channel();
+        let mut hangups = signal(SignalKind::hangup())?;
+        let path = self.path.clone();
+
+        tokio::spawn(async move {
+            while hangups.recv().await.is_some() {
+                let config = match Config::load(&path) {
+                    Ok(config) => config,
+                    Err(e) => {
+                        warn!("Config reload from {} failed, keeping current parameters: {:#}", path, e);
+                        continue;
+                    }
+                };
+                info!("🔄 Reloaded {} ({} strategies)", path, config.strategies.len());
+                let reload = ConfigReload {
+                    strategies: config.strategies.iter().map(|s| s.params()).collect(),
+                };
+                if sender.send(reload).is_err() {
+                    break;
+                }
+            }
+        });
+
+        Ok(Box::pin(UnboundedReceiverStream::new(receiver)))
+    }
+
+    fn name(&self) -> &str {
+        "config-reload"
+    }
+
+    /// Emits only on SIGHUP
+    fn expects_steady_events(&self) -> bool {
+        false
+    }
+}
@@ -0,0 +1,46 @@
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use tokio_stream::{wrappers::IntervalStream, StreamExt};
+
+use crate::types::{Collector, CollectorStream};
+
+/// Timer tick, so strategies can report state even when markets are quiet
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Heartbeat {
+    /// Local time (ms since epoch)
+    pub time: u64,
+}
+
+/// Emits a [Heartbeat] every `interval`, independent of market data
+pub struct HeartbeatCollector {
+    interval: Duration,
+}
+
+impl HeartbeatCollector {
+    pub fn new(interval: Duration) -> Self {
+        Self { interval }
+    }
+}
+
+#[async_trait]
+impl Collector<Heartbeat> for HeartbeatCollector {
+    async fn get_event_stream(&self) -> Result<CollectorStream<'_, Heartbeat>> {
+        let mut interval = tokio::time::interval(self.interval);
+        // The first tick completes immediately; skip it so beats start one interval in
+        interval.reset();
+        let stream = IntervalStream::new(interval).map(|_| Heartbeat {
+            time: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_millis() as u64)
+                .unwrap_or(0),
+        });
+        Ok(Box::pin(stream))
+    }
+
+    fn name(&self) -> &str {
+        "heartbeat"
+    }
+}
@@ -1,60 +1,285 @@
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
 use anyhow::Result;
 use async_trait::async_trait;
 use hyperliquid_rust_sdk::{BaseUrl, InfoClient, Message, Subscription};
-use tokio::sync::mpsc::unbounded_channel;
-use tokio_stream::{wrappers::UnboundedReceiverStream, StreamExt};
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender};
+use tokio_stream::wrappers::UnboundedReceiverStream;
+use tracing::warn;
 
 use crate::types::{Collector, CollectorStream};
 
-#[derive(Debug, Clone)]
+/// One side of the book, mirrored from the SDK so it can be serialized for recording
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BookLevel {
+    pub px: String,
+    pub sz: String,
+    pub n: u64,
+}
+
+impl From<hyperliquid_rust_sdk::BookLevel> for BookLevel {
+    fn from(level: hyperliquid_rust_sdk::BookLevel) -> Self {
+        Self { px: level.px, sz: level.sz, n: level.n }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HyperliquidBbo {
     pub coin: String,
-    pub levels: Vec<Option<hyperliquid_rust_sdk::BookLevel>>,
+    pub levels: Vec<Option<BookLevel>>,
     pub time: u64,
+    /// When the collector received this update, for latency measurement
+    #[serde(skip)]
+    pub received_at: Option<Instant>,
 }
 
+/// Re-subscribe if no BBO arrives for this long
+const DEFAULT_STALL_TIMEOUT: Duration = Duration::from_secs(30);
+
 pub struct HyperliquidCollector {
     coin: String,
+    stall_timeout: Duration,
 }
 
 impl HyperliquidCollector {
     pub fn new(coin: String) -> Self {
-        Self { coin }
+        Self { coin, stall_timeout: DEFAULT_STALL_TIMEOUT }
+    }
+
+    /// Treat the feed as stalled after this long without a message
+    pub fn with_stall_timeout(mut self, stall_timeout: Duration) -> Self {
+        self.stall_timeout = stall_timeout;
+        self
+    }
+}
+
+/// Subscribe once and forward BBOs until the feed stalls or errors.
+/// Returns Ok only once the consumer is gone.
+async fn stream_bbo(coin: String, stall_timeout: Duration, sender: UnboundedSender<HyperliquidBbo>) -> Result<()> {
+    let mut info_client = InfoClient::new(None, Some(BaseUrl::Mainnet))
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to create InfoClient: {:?}", e))?;
+
+    let (msg_sender, mut msg_receiver) = unbounded_channel();
+    let _subscription_id = info_client
+        .subscribe(Subscription::Bbo { coin }, msg_sender)
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to subscribe to BBO: {:?}", e))?;
+
+    // The InfoClient owns the socket; it lives until this function returns
+    forward_until_stall(&mut msg_receiver, stall_timeout, &sender, |msg| match msg {
+        Message::Bbo(bbo) => Some(HyperliquidBbo {
+            coin: bbo.data.coin,
+            levels: bbo.data.bbo.into_iter().map(|l| l.map(BookLevel::from)).collect(),
+            time: bbo.data.time,
+            received_at: Some(Instant::now()),
+        }),
+        _ => None,
+    })
+    .await
+}
+
+/// Forward the BBOs `convert` finds in `feed` until it closes or goes `stall_timeout`
+/// without a message. Returns Ok only once the consumer is gone.
+async fn forward_until_stall<M>(
+    feed: &mut UnboundedReceiver<M>,
+    stall_timeout: Duration,
+    sender: &UnboundedSender<HyperliquidBbo>,
+    convert: impl Fn(M) -> Option<HyperliquidBbo>,
+) -> Result<()> {
+    loop {
+        let msg = match tokio::time::timeout(stall_timeout, feed.recv()).await {
+            Ok(Some(msg)) => msg,
+            Ok(None) => anyhow::bail!("HL subscription closed"),
+            Err(_) => anyhow::bail!("no HL BBO for {:.1}s", stall_timeout.as_secs_f64()),
+        };
+        if let Some(bbo) = convert(msg) {
+            if sender.send(bbo).is_err() {
+                return Ok(());
+            }
+        }
+    }
+}
+
+/// Watchdog: run `session` again `retry_delay` after each stall or error, until one
+/// returns Ok because the consumer is gone
+async fn resubscribe_on_stall<F, Fut>(sender: UnboundedSender<HyperliquidBbo>, retry_delay: Duration, mut session: F)
+where
+    F: FnMut(UnboundedSender<HyperliquidBbo>) -> Fut,
+    Fut: std::future::Future<Output = Result<()>>,
+{
+    loop {
+        match session(sender.clone()).await {
+            Ok(()) => break,
+            Err(e) => warn!("⚠️  HL feed stalled ({}) - re-subscribing", e),
+        }
+        tokio::time::sleep(retry_delay).await;
     }
 }
 
 #[async_trait]
 impl Collector<HyperliquidBbo> for HyperliquidCollector {
     async fn get_event_stream(&self) -> Result<CollectorStream<'_, HyperliquidBbo>> {
-        let mut info_client = InfoClient::new(None, Some(BaseUrl::Mainnet))
+        let (sender, receiver) = unbounded_channel();
+        let coin = self.coin.clone();
+        let stall_timeout = self.stall_timeout;
+
+        // A stalled or dropped feed is torn down and re-subscribed so the strategy
+        // never keeps trading against a frozen HL price
+        tokio::spawn(resubscribe_on_stall(sender, Duration::from_secs(1), move |sender| {
+            stream_bbo(coin.clone(), stall_timeout, sender)
+        }));
+
+        Ok(Box::pin(UnboundedReceiverStream::new(receiver)))
+    }
+
+    fn name(&self) -> &str {
+        "hyperliquid"
+    }
+}
+
+/// Hyperliquid settles perp funding once an hour
+const FUNDING_INTERVAL_MS: u64 = 3_600_000;
+
+/// Latest hourly funding rate for a perp; positive means longs pay shorts
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FundingRate {
+    pub coin: String,
+    /// Fraction of notional paid per funding interval
+    pub rate: f64,
+    /// Expected time of the next settlement (ms since epoch)
+    pub next_time: u64,
+}
+
+/// Polls the funding history for one perp and emits each new settlement
+pub struct HyperliquidFundingCollector {
+    coin: String,
+    poll_interval: Duration,
+}
+
+impl HyperliquidFundingCollector {
+    pub fn new(coin: String) -> Self {
+        Self { coin, poll_interval: Duration::from_secs(60) }
+    }
+
+    pub fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+}
+
+#[async_trait]
+impl Collector<FundingRate> for HyperliquidFundingCollector {
+    async fn get_event_stream(&self) -> Result<CollectorStream<'_, FundingRate>> {
+        let info_client = InfoClient::new(None, Some(BaseUrl::Mainnet))
             .await
             .map_err(|e| anyhow::anyhow!("Failed to create InfoClient: {:?}", e))?;
 
         let (sender, receiver) = unbounded_channel();
-        
-        let _subscription_id = info_client
-            .subscribe(Subscription::Bbo { coin: self.coin.clone() }, sender)
-            .await
-            .map_err(|e| anyhow::anyhow!("Failed to subscribe to BBO: {:?}", e))?;
+        let coin = self.coin.clone();
+        let poll_interval = self.poll_interval;
 
         tokio::spawn(async move {
-            let _client = info_client;
-            std::future::pending::<()>().await;
-        });
+            let mut interval = tokio::time::interval(poll_interval);
+            let mut last_time = 0;
+            loop {
+                interval.tick().await;
+                let now_ms = SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map(|d| d.as_millis() as u64)
+                    .unwrap_or(0);
+                // Two intervals back always covers the most recent settlement
+                let start = now_ms.saturating_sub(2 * FUNDING_INTERVAL_MS);
+
+                let history = match info_client.funding_history(coin.clone(), start, None).await {
+                    Ok(history) => history,
+                    Err(e) => {
+                        warn!("HL funding poll failed: {:?}", e);
+                        continue;
+                    }
+                };
 
-        let stream = UnboundedReceiverStream::new(receiver).filter_map(|msg| {
-            match msg {
-                Message::Bbo(bbo) => {
-                    Some(HyperliquidBbo {
-                        coin: bbo.data.coin,
-                        levels: bbo.data.bbo,
-                        time: bbo.data.time,
-                    })
+                let Some(latest) = history.into_iter().max_by_key(|f| f.time) else {
+                    continue;
+                };
+                if latest.time <= last_time {
+                    continue;
+                }
+                let Ok(rate) = latest.funding_rate.parse::<f64>() else {
+                    continue;
+                };
+                last_time = latest.time;
+
+                let event = FundingRate {
+                    coin: latest.coin,
+                    rate,
+                    next_time: latest.time + FUNDING_INTERVAL_MS,
+                };
+                if sender.send(event).is_err() {
+                    break;
                 }
-                _ => None,
             }
         });
 
-        Ok(Box::pin(stream))
+        Ok(Box::pin(UnboundedReceiverStream::new(receiver)))
+    }
+
+    fn name(&self) -> &str {
+        "hl-funding"
     }
-}
\ No newline at end of file
+
+    /// Emits once per hourly settlement
+    fn expects_steady_events(&self) -> bool {
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{atomic::{AtomicU32, Ordering}, Arc};
+
+    use super::*;
+
+    fn bbo(time: u64) -> HyperliquidBbo {
+        HyperliquidBbo { coin: "HYPE".to_string(), levels: vec![None, None], time, received_at: None }
+    }
+
+    #[tokio::test]
+    async fn silent_feed_is_re_subscribed() {
+        let subscriptions = Arc::new(AtomicU32::new(0));
+        let counter = subscriptions.clone();
+        let (sender, mut receiver) = unbounded_channel();
+
+        // Each subscription delivers one BBO and then goes quiet without closing
+        let watchdog = tokio::spawn(resubscribe_on_stall(sender, Duration::from_millis(10), move |sender| {
+            let n = counter.fetch_add(1, Ordering::SeqCst) + 1;
+            async move {
+                let (feed, mut messages) = unbounded_channel();
+                feed.send(bbo(n as u64)).unwrap();
+                let result = forward_until_stall(&mut messages, Duration::from_millis(50), &sender, Some).await;
+                drop(feed);
+                result
+            }
+        }));
+
+        assert_eq!(receiver.recv().await.unwrap().time, 1);
+        // The gap outlasts the timeout: torn down and subscribed again
+        assert_eq!(receiver.recv().await.unwrap().time, 2);
+        assert_eq!(subscriptions.load(Ordering::SeqCst), 2);
+
+        // Once the consumer is gone the watchdog stops
+        drop(receiver);
+        tokio::time::timeout(Duration::from_secs(1), watchdog).await.unwrap().unwrap();
+        assert_eq!(subscriptions.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn closed_feed_is_reported_as_an_error() {
+        let (sender, _receiver) = unbounded_channel();
+        let (feed, mut messages) = unbounded_channel::<HyperliquidBbo>();
+        drop(feed);
+        let err = forward_until_stall(&mut messages, Duration::from_secs(1), &sender, Some).await.unwrap_err();
+        assert_eq!(err.to_string(), "HL subscription closed");
+    }
+}
@@ -1,2 +1,11 @@
 pub mod uniswapv3;
-pub mod hyperliquid;
\ No newline at end of file
+pub mod hyperliquid;
+pub mod okx;
+pub mod binance;
+pub mod cex;
+pub mod heartbeat;
+pub mod config_reload;
+pub mod balance;
+pub mod block;
+pub mod recorder;
+pub mod replay;
@@ -0,0 +1,162 @@
+use std::time::Duration;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use futures_util::{SinkExt, StreamExt};
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc::{unbounded_channel, UnboundedSender};
+use tokio_stream::wrappers::UnboundedReceiverStream;
+use tokio_tungstenite::{connect_async, tungstenite::Message};
+use tracing::{info, warn};
+
+use crate::types::{Collector, CollectorStream};
+
+const OKX_PUBLIC_WS_URL: &str = "wss://ws.okx.com:8443/ws/v5/public";
+/// OKX drops connections idle for 30s; ping a little before that. A ping still unanswered
+/// after another interval means the connection is dead.
+const PING_INTERVAL: Duration = Duration::from_secs(25);
+const RECONNECT_DELAY: Duration = Duration::from_secs(1);
+
+/// Top of book for one OKX instrument
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OkxBbo {
+    pub inst_id: String,
+    pub bid_px: f64,
+    pub bid_sz: f64,
+    pub ask_px: f64,
+    pub ask_sz: f64,
+    /// Exchange timestamp (ms since epoch)
+    pub ts: u64,
+}
+
+#[derive(Deserialize)]
+struct PushData {
+    data: Vec<BookSnapshot>,
+}
+
+/// Levels are `[px, sz, deprecated, order_count]`
+#[derive(Deserialize)]
+struct BookSnapshot {
+    asks: Vec<Vec<String>>,
+    bids: Vec<Vec<String>>,
+    ts: String,
+}
+
+impl BookSnapshot {
+    fn into_bbo(self, inst_id: &str) -> Option<OkxBbo> {
+        let level = |side: &[Vec<String>]| -> Option<(f64, f64)> {
+            let best = side.first()?;
+            Some((best.first()?.parse().ok()?, best.get(1)?.parse().ok()?))
+        };
+        let (bid_px, bid_sz) = level(&self.bids)?;
+        let (ask_px, ask_sz) = level(&self.asks)?;
+        Some(OkxBbo {
+            inst_id: inst_id.to_string(),
+            bid_px,
+            bid_sz,
+            ask_px,
+            ask_sz,
+            ts: self.ts.parse().ok()?,
+        })
+    }
+}
+
+/// Streams the `bbo-tbt` channel for one instrument (e.g. "HYPE-USDT") from
+/// OKX's public WebSocket, reconnecting whenever OKX resets the connection
+pub struct OkxCollector {
+    inst_id: String,
+    url: String,
+}
+
+impl OkxCollector {
+    pub fn new(inst_id: String) -> Self {
+        Self { inst_id, url: OKX_PUBLIC_WS_URL.to_string() }
+    }
+
+    /// Override the WebSocket endpoint (e.g. the AWS or demo-trading host)
+    pub fn with_url(mut self, url: String) -> Self {
+        self.url = url;
+        self
+    }
+}
+
+/// Run one connection until it drops; returns Ok only once the consumer is gone
+async fn stream_session(url: &str, inst_id: &str, sender: &UnboundedSender<OkxBbo>) -> Result<()> {
+    let (ws, _) = connect_async(url).await?;
+    let (mut write, mut read) = ws.split();
+
+    let subscribe = serde_json::json!({
+        "op": "subscribe",
+        "args": [{ "channel": "bbo-tbt", "instId": inst_id }],
+    });
+    write.send(Message::Text(subscribe.to_string().into())).await?;
+    info!("OKX: subscribed to bbo-tbt {}", inst_id);
+
+    let mut awaiting_pong = false;
+    loop {
+        let msg = match tokio::time::timeout(PING_INTERVAL, read.next()).await {
+            Ok(Some(msg)) => msg?,
+            Ok(None) => anyhow::bail!("connection closed by OKX"),
+            Err(_) if awaiting_pong => anyhow::bail!("no pong within {}s", PING_INTERVAL.as_secs()),
+            Err(_) => {
+                write.send(Message::Text("ping".into())).await?;
+                awaiting_pong = true;
+                continue;
+            }
+        };
+
+        let text = match msg {
+            Message::Text(text) => text,
+            Message::Ping(payload) => {
+                write.send(Message::Pong(payload)).await?;
+                continue;
+            }
+            Message::Close(frame) => anyhow::bail!("closed by OKX: {:?}", frame),
+            _ => continue,
+        };
+        if text.as_str() == "pong" {
+            awaiting_pong = false;
+            continue;
+        }
+
+        // Subscribe acks and error events carry no `data` and are skipped here
+        let Ok(push) = serde_json::from_str::<PushData>(&text) else {
+            if text.contains("\"event\":\"error\"") {
+                anyhow::bail!("OKX error: {}", text);
+            }
+            continue;
+        };
+        for snapshot in push.data {
+            if let Some(bbo) = snapshot.into_bbo(inst_id) {
+                if sender.send(bbo).is_err() {
+                    return Ok(());
+                }
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl Collector<OkxBbo> for OkxCollector {
+    async fn get_event_stream(&self) -> Result<CollectorStream<'_, OkxBbo>> {
+        let (sender, receiver) = unbounded_channel();
+        let url = self.url.clone();
+        let inst_id = self.inst_id.clone();
+
+        tokio::spawn(async move {
+            loop {
+                match stream_session(&url, &inst_id, &sender).await {
+                    Ok(()) => break,
+                    Err(e) => warn!("OKX: {} - reconnecting", e),
+                }
+                tokio::time::sleep(RECONNECT_DELAY).await;
+            }
+        });
+
+        Ok(Box::pin(UnboundedReceiverStream::new(receiver)))
+    }
+
+    fn name(&self) -> &str {
+        "okx"
+    }
+}
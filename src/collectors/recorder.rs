@@ -0,0 +1,114 @@
+use std::marker::PhantomData;
+use std::path::Path;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::Serialize;
+use tokio::io::{AsyncWriteExt, BufWriter};
+use tokio::sync::mpsc::{unbounded_channel, UnboundedSender};
+use tokio_stream::StreamExt;
+use tracing::{error, info};
+
+use crate::collectors::replay::RecordedEvent;
+use crate::types::{Collector, CollectorStream};
+
+/// Appends events to a JSONL file in the format read by
+/// [ReplayCollector](crate::collectors::replay::ReplayCollector).
+/// Writes happen on a background task so recording never blocks the caller.
+pub struct EventRecorder<E> {
+    sender: UnboundedSender<String>,
+    _event: PhantomData<fn(E)>,
+}
+
+// Manual impl: clones share one writer task and don't need `E: Clone`
+impl<E> Clone for EventRecorder<E> {
+    fn clone(&self) -> Self {
+        Self { sender: self.sender.clone(), _event: PhantomData }
+    }
+}
+
+impl<E: Serialize> EventRecorder<E> {
+    /// Open (or create) the file in append mode and start the writer task
+    pub async fn spawn(path: impl AsRef<Path>) -> Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .await?;
+        info!("⏺️  Recording events to {}", path.display());
+
+        let (sender, mut receiver) = unbounded_channel::<String>();
+        tokio::spawn(async move {
+            let mut writer = BufWriter::new(file);
+            while let Some(line) = receiver.recv().await {
+                if let Err(e) = writer.write_all(line.as_bytes()).await {
+                    error!("Recorder write failed: {}", e);
+                    continue;
+                }
+                // Flush once the backlog is drained rather than per line
+                if receiver.is_empty() {
+                    if let Err(e) = writer.flush().await {
+                        error!("Recorder flush failed: {}", e);
+                    }
+                }
+            }
+            let _ = writer.flush().await;
+        });
+
+        Ok(Self { sender, _event: PhantomData })
+    }
+
+    /// Timestamp and queue one event for writing
+    pub fn record(&self, event: &E) {
+        let ts_ms = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0);
+
+        match serde_json::to_string(&RecordedEvent { ts_ms, event }) {
+            Ok(mut line) => {
+                line.push('\n');
+                let _ = self.sender.send(line);
+            }
+            Err(e) => error!("Recorder serialize failed: {}", e),
+        }
+    }
+}
+
+/// RecordingCollector wraps a [Collector](Collector) and tees every event it emits
+/// to an [EventRecorder], forwarding the event unchanged.
+pub struct RecordingCollector<E> {
+    collector: Box<dyn Collector<E>>,
+    recorder: EventRecorder<E>,
+}
+
+impl<E> RecordingCollector<E> {
+    pub fn new(collector: Box<dyn Collector<E>>, recorder: EventRecorder<E>) -> Self {
+        Self { collector, recorder }
+    }
+}
+
+#[async_trait]
+impl<E> Collector<E> for RecordingCollector<E>
+where
+    E: Serialize + Send + Sync + 'static,
+{
+    async fn get_event_stream(&self) -> Result<CollectorStream<'_, E>> {
+        let stream = self.collector.get_event_stream().await?;
+        let recorder = &self.recorder;
+        let stream = stream.map(move |event| {
+            recorder.record(&event);
+            event
+        });
+        Ok(Box::pin(stream))
+    }
+
+    fn name(&self) -> &str {
+        self.collector.name()
+    }
+
+    fn expects_steady_events(&self) -> bool {
+        self.collector.expects_steady_events()
+    }
+}
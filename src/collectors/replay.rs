@@ -0,0 +1,92 @@
+use std::marker::PhantomData;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use tokio_stream::StreamExt;
+use tracing::info;
+
+use crate::types::{Collector, CollectorStream};
+
+/// One line of a recording: `{"ts_ms": <unix millis>, "event": <E as JSON>}`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordedEvent<E> {
+    pub ts_ms: u64,
+    pub event: E,
+}
+
+/// Replays a newline-delimited JSON recording (as written by
+/// [EventRecorder](crate::collectors::recorder::EventRecorder)) as an event stream.
+pub struct ReplayCollector<E> {
+    path: PathBuf,
+    /// Sleep between events to reproduce the recorded timing
+    realtime: bool,
+    _event: PhantomData<fn() -> E>,
+}
+
+impl<E> ReplayCollector<E> {
+    /// Replay as fast as possible. Size the engine's event channel for the recording,
+    /// otherwise slow strategies can lag behind and drop events.
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            realtime: false,
+            _event: PhantomData,
+        }
+    }
+
+    pub fn with_realtime(mut self, realtime: bool) -> Self {
+        self.realtime = realtime;
+        self
+    }
+}
+
+#[async_trait]
+impl<E> Collector<E> for ReplayCollector<E>
+where
+    E: DeserializeOwned + Send + Sync + 'static,
+{
+    async fn get_event_stream(&self) -> Result<CollectorStream<'_, E>> {
+        let content = tokio::fs::read_to_string(&self.path).await?;
+
+        let mut records = Vec::new();
+        for (i, line) in content.lines().enumerate() {
+            if line.trim().is_empty() {
+                continue;
+            }
+            let record: RecordedEvent<E> = serde_json::from_str(line)
+                .map_err(|e| anyhow::anyhow!("Bad record at {}:{}: {}", self.path.display(), i + 1, e))?;
+            records.push(record);
+        }
+
+        info!("⏪ Replaying {} events from {}", records.len(), self.path.display());
+
+        // Pair each event with the gap since the previous one
+        let mut last_ts = records.first().map(|r| r.ts_ms).unwrap_or(0);
+        let realtime = self.realtime;
+        let timed: Vec<(u64, E)> = records.into_iter()
+            .map(|r| {
+                let delay = if realtime { r.ts_ms.saturating_sub(last_ts) } else { 0 };
+                last_ts = r.ts_ms;
+                (delay, r.event)
+            })
+            .collect();
+
+        let stream = tokio_stream::iter(timed).then(|(delay_ms, event)| async move {
+            if delay_ms > 0 {
+                tokio::time::sleep(Duration::from_millis(delay_ms)).await;
+            } else {
+                tokio::task::yield_now().await;
+            }
+            event
+        });
+
+        Ok(Box::pin(stream))
+    }
+
+    fn name(&self) -> &str {
+        "replay"
+    }
+}
@@ -1,8 +1,10 @@
-use std::sync::Arc;
+use std::sync::{atomic::Ordering, Arc};
+use std::time::{Duration, Instant};
 
 use alloy::{
     primitives::{Address, U256},
     providers::Provider,
+    sol,
 };
 
 use amms::{
@@ -11,39 +13,223 @@ use amms::{
 };
 use anyhow::Result;
 use async_trait::async_trait;
-use tokio_stream::StreamExt;
+use serde::{Deserialize, Serialize};
+use futures_util::StreamExt;
+use tokio::sync::mpsc::{unbounded_channel, UnboundedSender};
+use tokio_stream::wrappers::UnboundedReceiverStream;
+use tracing::{info, warn};
 
 use crate::types::{Collector, CollectorStream};
 
-#[derive(Debug, Clone)]
+const RESYNC_INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+const RESYNC_MAX_BACKOFF: Duration = Duration::from_secs(30);
+/// Failed subscription sessions in a row before switching to the HTTP fallback
+const WS_FAILURES_BEFORE_FALLBACK: u32 = 3;
+
+sol! {
+    #[sol(rpc)]
+    interface IUniswapV3PoolState {
+        function slot0() external view returns (uint160 sqrtPriceX96, int24 tick, uint16 observationIndex, uint16 observationCardinality, uint16 observationCardinalityNext, uint8 feeProtocol, bool unlocked);
+        function liquidity() external view returns (uint128);
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UniV3PoolState {
+    pub address: Address,
+    /// Block the state was synced at, used to drop stale or reorg-duplicated updates
+    pub block_number: u64,
+    /// Pool tokens in Uniswap order (lower address first); `sqrt_price` is token1 per token0.
+    /// Zero when unknown, e.g. in recordings made before these fields existed.
+    #[serde(default)]
+    pub token0: Address,
+    #[serde(default)]
+    pub token1: Address,
     pub sqrt_price: U256,
+    /// In-range liquidity and current tick; zero in recordings made before these existed
+    #[serde(default)]
+    pub liquidity: u128,
+    #[serde(default)]
+    pub tick: i32,
     pub fee: u32,
     pub token_a_decimals: u8,
     pub token_b_decimals: u8,
+    /// Full tick state for simulating sized swaps. Live only: not recorded, so replayed
+    /// and what-if states quote from `sqrt_price` alone.
+    #[serde(skip)]
+    pub pool: Option<Arc<UniswapV3Pool>>,
+    /// When the collector produced this state, for latency measurement
+    #[serde(skip)]
+    pub received_at: Option<Instant>,
 }
 
 pub struct UniV3Collector<P> {
     provider: Arc<P>,
-    pool_address: Address,
+    pool_addresses: Vec<Address>,
+    // Poll `provider` on this interval instead of subscribing (e.g. it is HTTP-only)
+    poll_interval: Option<Duration>,
+    // HTTP provider to poll through once the subscription keeps failing
+    http_fallback: Option<(Arc<P>, Duration)>,
 }
 
 impl<P> UniV3Collector<P> {
     pub fn new(provider: Arc<P>, pool_address: Address) -> Self {
+        Self::with_pools(provider, vec![pool_address])
+    }
+
+    /// Watch several pools (e.g. every fee tier of a pair) through one state space
+    pub fn with_pools(provider: Arc<P>, pool_addresses: Vec<Address>) -> Self {
         Self {
             provider,
-            pool_address,
+            pool_addresses,
+            poll_interval: None,
+            http_fallback: None,
         }
     }
 
-    fn extract_pool_state(pool: &UniswapV3Pool, _address: Address) -> UniV3PoolState {
+    /// Poll slot0 and liquidity every `interval` instead of subscribing to new blocks
+    pub fn with_polling(mut self, interval: Duration) -> Self {
+        self.poll_interval = Some(interval);
+        self
+    }
+
+    /// Switch to polling through `provider` after repeated subscription failures
+    pub fn with_http_fallback(mut self, provider: Arc<P>, interval: Duration) -> Self {
+        self.http_fallback = Some((provider, interval));
+        self
+    }
+
+    fn extract_pool_state(pool: &UniswapV3Pool, address: Address, block_number: u64) -> UniV3PoolState {
         UniV3PoolState {
+            address,
+            block_number,
+            token0: pool.token_a.address,
+            token1: pool.token_b.address,
             sqrt_price: pool.sqrt_price,
+            liquidity: pool.liquidity,
+            tick: pool.tick,
             fee: pool.fee,
             token_a_decimals: pool.token_a.decimals,
             token_b_decimals: pool.token_b.decimals,
+            pool: Some(Arc::new(pool.clone())),
+            received_at: Some(Instant::now()),
+        }
+    }
+}
+
+/// Sync the pools, emit their current state, then forward updates until the
+/// subscription ends; returns Ok only once the consumer is gone
+async fn stream_session<P: Provider + 'static>(
+    provider: Arc<P>,
+    pool_addresses: &[Address],
+    sender: &UnboundedSender<UniV3PoolState>,
+) -> Result<()> {
+    let pools: Vec<AMM> = pool_addresses.iter()
+        .map(|&address| UniswapV3Pool::new(address).into())
+        .collect();
+
+    let state_space_manager = StateSpaceBuilder::new(provider)
+        .with_amms(pools)
+        .sync()
+        .await?;
+
+    let state = state_space_manager.state.clone();
+    let latest_block = state_space_manager.latest_block.clone();
+    let snapshot = |addresses: Vec<Address>| {
+        let state = state.clone();
+        let latest_block = latest_block.clone();
+        async move {
+            let block_number = latest_block.load(Ordering::Relaxed);
+            let state_guard = state.read().await;
+            addresses.into_iter()
+                .filter_map(|address| match state_guard.get(&address) {
+                    Some(AMM::UniswapV3Pool(pool)) => Some(UniV3Collector::<P>::extract_pool_state(pool, address, block_number)),
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+        }
+    };
+
+    // Fresh state first, so strategies never quote from a pre-resync pool
+    for pool_state in snapshot(pool_addresses.to_vec()).await {
+        if sender.send(pool_state).is_err() {
+            return Ok(());
         }
     }
+    info!("UniV3: synced {} pools", pool_addresses.len());
+
+    let mut updates = Box::pin(state_space_manager.subscribe().await?);
+    // One update can touch several watched pools; emit a state for each
+    while let Some(result) = updates.next().await {
+        let addresses = match result {
+            Ok(addresses) => addresses,
+            Err(e) => {
+                warn!("UniV3: update failed: {}", e);
+                continue;
+            }
+        };
+        for pool_state in snapshot(addresses).await {
+            if sender.send(pool_state).is_err() {
+                return Ok(());
+            }
+        }
+    }
+    anyhow::bail!("pool subscription ended")
+}
+
+/// Sync the pools once for tokens, decimals and ticks, then refresh slot0 and liquidity
+/// on every new block seen at `poll_interval`. Ticks are not re-synced, so sized swap
+/// simulation drifts as liquidity moves; resyncs (on any error) bring them current.
+async fn poll_session<P: Provider + 'static>(
+    provider: Arc<P>,
+    pool_addresses: &[Address],
+    poll_interval: Duration,
+    sender: &UnboundedSender<UniV3PoolState>,
+) -> Result<()> {
+    let amms: Vec<AMM> = pool_addresses.iter()
+        .map(|&address| UniswapV3Pool::new(address).into())
+        .collect();
+    let state_space_manager = StateSpaceBuilder::new(provider.clone())
+        .with_amms(amms)
+        .sync()
+        .await?;
+    let mut pools: Vec<UniswapV3Pool> = {
+        let state_guard = state_space_manager.state.read().await;
+        pool_addresses.iter()
+            .filter_map(|address| match state_guard.get(address) {
+                Some(AMM::UniswapV3Pool(pool)) => Some(pool.clone()),
+                _ => None,
+            })
+            .collect()
+    };
+    info!("UniV3: polling {} pools every {}ms", pools.len(), poll_interval.as_millis());
+
+    let mut interval = tokio::time::interval(poll_interval);
+    let mut last_block = 0;
+    loop {
+        interval.tick().await;
+        let block_number = provider.get_block_number().await?;
+        if block_number <= last_block {
+            continue;
+        }
+        for pool in &mut pools {
+            let pool_state = refresh_pool(&*provider, pool, block_number).await?;
+            if sender.send(pool_state).is_err() {
+                return Ok(());
+            }
+        }
+        last_block = block_number;
+    }
+}
+
+/// Read a pool's slot0 and liquidity at `block_number` into `pool` and its state
+async fn refresh_pool<P: Provider>(provider: &P, pool: &mut UniswapV3Pool, block_number: u64) -> Result<UniV3PoolState> {
+    let contract = IUniswapV3PoolState::new(pool.address, provider);
+    let slot0 = contract.slot0().block(block_number.into()).call().await?;
+    pool.sqrt_price = U256::from(slot0.sqrtPriceX96);
+    pool.tick = slot0.tick.as_i32();
+    pool.liquidity = contract.liquidity().block(block_number.into()).call().await?;
+    Ok(UniV3Collector::<P>::extract_pool_state(pool, pool.address, block_number))
 }
 
 #[async_trait]
@@ -52,56 +238,100 @@ where
     P: Provider + 'static,
 {
     async fn get_event_stream(&self) -> Result<CollectorStream<'_, UniV3PoolState>> {
-        let pool: AMM = UniswapV3Pool::new(self.pool_address).into();
-        
-        let state_space_manager = StateSpaceBuilder::new(self.provider.clone())
-            .with_amms(vec![pool])
-            .sync()
-            .await?;
-
-        let state = state_space_manager.state.clone();
-        
-        let initial_state = {
-            let state_guard = state.read().await;
-            state_guard.get(&self.pool_address)
-                .and_then(|amm| {
-                    if let AMM::UniswapV3Pool(pool) = amm {
-                        Some(Self::extract_pool_state(pool, self.pool_address))
-                    } else {
-                        None
-                    }
-                })
-        };
-
-        let stream = state_space_manager.subscribe().await?;
+        let (sender, receiver) = unbounded_channel();
+        let provider = self.provider.clone();
+        let pool_addresses = self.pool_addresses.clone();
+        let mut polling = self.poll_interval.map(|interval| (provider.clone(), interval));
+        let http_fallback = self.http_fallback.clone();
 
-        let updates_stream = stream.then(move |result| {
-            let state = state.clone();
-            async move {
-                match result {
-                    Ok(addresses) => {
-                        if addresses.is_empty() {
-                            return None;
+        // Resync from scratch whenever the sync or subscription fails, backing off
+        // between attempts so a node outage doesn't turn into a tight loop
+        tokio::spawn(async move {
+            let mut backoff = RESYNC_INITIAL_BACKOFF;
+            let mut failures = 0;
+            loop {
+                let started = Instant::now();
+                let session = match &polling {
+                    Some((provider, interval)) => poll_session(provider.clone(), &pool_addresses, *interval, &sender).await,
+                    None => stream_session(provider.clone(), &pool_addresses, &sender).await,
+                };
+                match session {
+                    Ok(()) => break,
+                    Err(e) => {
+                        // A session that stayed up a while was healthy; start backoff over
+                        if started.elapsed() > RESYNC_MAX_BACKOFF {
+                            backoff = RESYNC_INITIAL_BACKOFF;
+                            failures = 0;
                         }
-                        
-                        let address = addresses[0];
-                        let state_guard = state.read().await;
-                        state_guard.get(&address)
-                            .and_then(|amm| {
-                                if let AMM::UniswapV3Pool(pool) = amm {
-                                    Some(Self::extract_pool_state(pool, address))
-                                } else {
-                                    None
-                                }
-                            })
+                        failures += 1;
+                        warn!("UniV3: {:#} - resyncing in {}s", e, backoff.as_secs());
                     }
-                    Err(_) => None,
                 }
+                if polling.is_none() && failures >= WS_FAILURES_BEFORE_FALLBACK {
+                    if let Some(fallback) = &http_fallback {
+                        warn!("UniV3: subscription failed {} times in a row, falling back to HTTP polling", failures);
+                        polling = Some(fallback.clone());
+                    }
+                }
+                tokio::time::sleep(backoff).await;
+                backoff = (backoff * 2).min(RESYNC_MAX_BACKOFF);
             }
-        }).filter_map(|x| x);
-        
-        let combined_stream = tokio_stream::iter(initial_state).chain(updates_stream);
+        });
+
+        Ok(Box::pin(UnboundedReceiverStream::new(receiver)))
+    }
+
+    fn name(&self) -> &str {
+        "univ3"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::{
+        primitives::{aliases::{I24, U160}, Bytes},
+        providers::ProviderBuilder,
+        sol_types::SolCall,
+        transports::mock::Asserter,
+    };
+
+    use super::*;
+
+    #[tokio::test]
+    async fn polled_slot0_and_liquidity_become_the_pool_state() {
+        let asserter = Asserter::new();
+        let slot0 = IUniswapV3PoolState::slot0Call::abi_encode_returns(&IUniswapV3PoolState::slot0Return {
+            sqrtPriceX96: U160::from(501_082_896_750_095_862_372_827u128),
+            tick: I24::try_from(-240_000).unwrap(),
+            observationIndex: 0,
+            observationCardinality: 1,
+            observationCardinalityNext: 1,
+            feeProtocol: 0,
+            unlocked: true,
+        });
+        asserter.push_success(&Bytes::from(slot0));
+        asserter.push_success(&Bytes::from(IUniswapV3PoolState::liquidityCall::abi_encode_returns(&7_000_000u128)));
+        let provider = ProviderBuilder::new().connect_mocked_client(asserter);
+
+        let mut pool = UniswapV3Pool::new(Address::repeat_byte(0xaa));
+        pool.token_a.decimals = 18;
+        pool.token_b.decimals = 6;
+        let state = refresh_pool(&provider, &mut pool, 1234).await.unwrap();
+
+        assert_eq!(state.address, Address::repeat_byte(0xaa));
+        assert_eq!(state.block_number, 1234);
+        assert_eq!(state.sqrt_price, U256::from(501_082_896_750_095_862_372_827u128));
+        assert_eq!((state.tick, state.liquidity), (-240_000, 7_000_000));
+        assert_eq!((state.token_a_decimals, state.token_b_decimals), (18, 6));
+        // The pool itself is kept current for the next block's simulation
+        assert_eq!(pool.liquidity, 7_000_000);
+    }
 
-        Ok(Box::pin(combined_stream))
+    #[tokio::test]
+    async fn failed_poll_ends_the_session() {
+        // No responses queued: the slot0 read fails and the session resyncs
+        let provider = ProviderBuilder::new().connect_mocked_client(Asserter::new());
+        let mut pool = UniswapV3Pool::new(Address::repeat_byte(0xaa));
+        assert!(refresh_pool(&provider, &mut pool, 1234).await.is_err());
     }
 }
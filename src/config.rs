@@ -1,12 +1,67 @@
+use alloy::primitives::Address;
 use anyhow::Result;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::fs;
 
+use crate::collectors::cex::CexVenue;
+use crate::executors::arbitrage::SubmissionMode;
+use crate::executors::hyperliquid::{OrderMode, TimeInForce};
+use crate::strategies::hype_usdc_cross_arbitrage::BothProfitable;
+use crate::utilities::logging::LogFormat;
+
 #[derive(Debug, Deserialize, Clone)]
 pub struct Config {
+    /// May be left empty when `rpc_url_http` is set, to run on HTTP polling alone
+    #[serde(default)]
     pub rpc_url_ws: String,
+    /// HTTP endpoint used when the WebSocket can't connect or its subscription keeps failing
+    #[serde(default)]
+    pub rpc_url_http: Option<String>,
+    /// Pool state polling interval while on the HTTP fallback
+    #[serde(default = "default_http_poll_ms")]
+    pub http_poll_ms: u64,
     pub max_concurrent: usize,
     pub cooldown_secs: u64,
+    /// Append every live event to this JSONL file for later replay (disabled when unset)
+    #[serde(default)]
+    pub record_path: Option<String>,
+    /// Bind address for the what-if `/evaluate` endpoint (disabled when unset)
+    #[serde(default)]
+    pub evaluate_addr: Option<String>,
+    /// Halt every strategy after this many consecutive execution failures (disabled when unset)
+    #[serde(default)]
+    pub circuit_breaker_failures: Option<u32>,
+    #[serde(default = "default_circuit_breaker_cooldown_secs")]
+    pub circuit_breaker_cooldown_secs: u64,
+    /// Bind address for `/healthz` and `/readyz` probes (disabled when unset)
+    #[serde(default)]
+    pub health_addr: Option<String>,
+    /// `/readyz` fails once any feed has been silent this long
+    #[serde(default = "default_health_stale_secs")]
+    pub health_stale_secs: u64,
+    /// POST alerts (fills, leg failures, halts) as JSON here; usually set through
+    /// `RUSTYARB_ALERT_WEBHOOK_URL` since the URL is often a secret
+    #[serde(default)]
+    pub alert_webhook_url: Option<String>,
+    /// CoinGecko id of the native gas token (e.g. "hyperliquid"); enables the price oracle
+    #[serde(default)]
+    pub native_price_coingecko_id: Option<String>,
+    #[serde(default = "default_native_price_poll_secs")]
+    pub native_price_poll_secs: u64,
+    /// Oracle prices older than this are ignored in favour of `native_price_default_usd`
+    #[serde(default = "default_native_price_ttl_secs")]
+    pub native_price_ttl_secs: u64,
+    #[serde(default)]
+    pub native_price_default_usd: Option<f64>,
+    /// Log each strategy's spreads and feed status this often; 0 disables
+    #[serde(default = "default_heartbeat_secs")]
+    pub heartbeat_secs: u64,
+    /// Warn if a strategy's DEX or hedge feed is still silent this long after startup; 0 disables
+    #[serde(default = "default_startup_grace_secs")]
+    pub startup_grace_secs: u64,
+    /// "pretty" (default) or "json"; `LOG_FORMAT` overrides
+    #[serde(default)]
+    pub log_format: LogFormat,
     pub strategies: Vec<StrategyConfig>,
 }
 
@@ -16,20 +71,421 @@ pub struct StrategyConfig {
     pub enabled: bool,
     // DEX
     pub pool_address: String,
+    /// Other pools for the same pair (e.g. other fee tiers); each trade routes through
+    /// whichever watched pool quotes best
+    #[serde(default)]
+    pub pool_addresses: Vec<String>,
     pub router_address: String,
     pub fee: u32,
     pub token_a_address: String,
     pub token_b_address: String,
     // CEX
     pub hyperliquid_coin: String,
+    /// Venue the hedge is priced and placed on (default hyperliquid)
+    #[serde(default)]
+    pub cex_venue: CexVenue,
+    /// Spot symbol (e.g. "HYPEUSDT") when `cex_venue = "binance"`
+    #[serde(default)]
+    pub binance_symbol: Option<String>,
+    /// Binance spot fee applied to the hedge side of the spread
+    #[serde(default = "default_binance_fee_bps")]
+    pub binance_fee_bps: f64,
+    #[serde(default)]
+    pub hl_tif: TimeInForce,
+    /// `{ type = "MarketIoc", max_slippage_bps = 30.0 }` crosses the live BBO instead
+    /// of using the decision-time limit
+    #[serde(default)]
+    pub hl_order_mode: OrderMode,
+    /// "sequential" (default) sends the hedge after the swap; "concurrent" sends both at
+    /// once and "hedge_first" sizes the swap to the hedge fill, both accepting that a
+    /// failed swap can no longer cancel the hedge
+    #[serde(default)]
+    pub submission_mode: SubmissionMode,
+    /// Re-subscribe to the HL BBO feed after this long without a message (default 30s)
+    #[serde(default)]
+    pub hl_stall_timeout_secs: Option<u64>,
+    /// Optional vault to route this strategy's HL orders through
+    #[serde(default)]
+    pub hl_vault_address: Option<String>,
+    /// Account an HL API wallet (`HL_API_PRIVATE_KEY`) trades for; defaults to the
+    /// `PRIVATE_KEY` address
+    #[serde(default)]
+    pub hl_account_address: Option<String>,
+    /// Size decimals to assume if HL metadata is unavailable; orders fail without it
+    #[serde(default)]
+    pub hl_fallback_sz_decimals: Option<u32>,
+    /// With `hl_tif = "Alo"`: re-peg a resting hedge up to this many times before
+    /// crossing; 0 posts once and leaves the order alone
+    #[serde(default)]
+    pub hl_max_reprices: u32,
+    #[serde(default = "default_hl_reprice_interval_ms")]
+    pub hl_reprice_interval_ms: u64,
+    #[serde(default = "default_hl_reprice_deadline_ms")]
+    pub hl_reprice_deadline_ms: u64,
+    /// Perp hedges only: include expected funding over `funding_holding_hours`
+    /// in the net-profit check
+    #[serde(default)]
+    pub funding_aware: bool,
+    #[serde(default = "default_funding_holding_hours")]
+    pub funding_holding_hours: f64,
     // Strategy params
     pub order_size_usd: f64,
+    /// Dynamic sizing: trade `min_notional_usd` at `min_profit_bps`, scaling linearly up
+    /// to `max_notional_usd` once the edge clears it by `size_ramp_bps`. Both bounds
+    /// enable it; edges are still quoted at `order_size_usd`.
+    #[serde(default)]
+    pub min_notional_usd: Option<f64>,
+    #[serde(default)]
+    pub max_notional_usd: Option<f64>,
+    #[serde(default = "default_size_ramp_bps")]
+    pub size_ramp_bps: f64,
     pub hl_maker_fee_bps: f64,
     pub dex_gas_fee_usd: f64,
+    /// Gas per swap in native token; with the price oracle this replaces `dex_gas_fee_usd`
+    #[serde(default)]
+    pub dex_gas_fee_native: Option<f64>,
     pub min_profit_bps: f64,
     pub slippage_bps: f64,
+    /// Quote the DEX leg from a simulated swap of `order_size_usd` (price impact
+    /// included) rather than mid price ± fee. States without tick data (replays) use
+    /// a single-tick-range estimate from in-range liquidity.
+    #[serde(default)]
+    pub simulate_price_impact: bool,
+    /// Reject signals where DEX and HL mids differ by more than this (likely a bad feed)
+    #[serde(default)]
+    pub max_divergence_bps: Option<f64>,
+    /// Skip trades whose DEX leg would move the pool price more than this, estimated
+    /// from in-range liquidity
+    #[serde(default)]
+    pub max_price_impact_bps: Option<f64>,
+    /// Plausible range for the pair's price (USDC per HYPE); quotes outside it are
+    /// treated as bad data, e.g. a decimals misconfiguration
+    #[serde(default)]
+    pub sanity_min_price: Option<f64>,
+    #[serde(default)]
+    pub sanity_max_price: Option<f64>,
+    /// Both directions clearing `min_profit_bps` means crossed quotes: skip (default)
+    /// or take the better one
+    #[serde(default)]
+    pub on_both_profitable: BothProfitable,
+    /// Hysteresis: open only above `min_profit_bps + enter_buffer_bps`; after a trade,
+    /// that direction waits until the spread drops below `min_profit_bps - exit_buffer_bps`
+    /// or `reentry_cooldown_secs` pass
+    #[serde(default)]
+    pub enter_buffer_bps: f64,
+    #[serde(default)]
+    pub exit_buffer_bps: f64,
+    #[serde(default)]
+    pub reentry_cooldown_secs: Option<u64>,
+    /// Don't re-emit an action with the same direction and size within this many ms
+    #[serde(default)]
+    pub dedup_window_ms: Option<u64>,
+    /// Gate trades on an EMA of each direction's net edge over about this many
+    /// evaluations instead of the raw value; 0 (default) disables smoothing
+    #[serde(default)]
+    pub smoothing_window: u32,
+    /// After a trade, only that direction waits this long, instead of `cooldown_secs`
+    /// blocking both
+    #[serde(default)]
+    pub direction_cooldown_secs: Option<u64>,
+    /// eth_call the DEX swap before sending; abort if it reverts or under-delivers
+    /// the expected output by more than `slippage_bps`
+    #[serde(default)]
+    pub simulate_first: bool,
+    /// Retries for DEX sends / HL orders that never reached the venue (connect errors,
+    /// 429), with exponential backoff starting at `retry_backoff_ms`. HL orders are only
+    /// retried under `submission_mode = "hedge_first"`, before the swap is sent.
+    #[serde(default)]
+    pub dex_max_retries: u32,
+    #[serde(default)]
+    pub hl_max_retries: u32,
+    #[serde(default = "default_retry_backoff_ms")]
+    pub retry_backoff_ms: u64,
+    /// If the hedge fails, retry it as a reduce-only IOC and then swap the DEX leg back, losing at
+    /// most `unwind_max_loss_bps`; a failed unwind halts the strategy
+    #[serde(default)]
+    pub auto_unwind: bool,
+    #[serde(default = "default_unwind_max_loss_bps")]
+    pub unwind_max_loss_bps: f64,
+    /// Trade native HYPE on the DEX leg: wrap inputs and unwrap WHYPE outputs in the
+    /// same router multicall
+    #[serde(default)]
+    pub auto_wrap: bool,
+    /// Assign DEX transaction nonces locally so back-to-back swaps don't collide
+    #[serde(default)]
+    pub manual_nonce: bool,
+    /// Swaps not mined within this many seconds revert rather than land late
+    #[serde(default = "default_swap_deadline_secs")]
+    pub swap_deadline_secs: u64,
+    /// Skip trades while the suggested gas price is above this, or while a swap's
+    /// estimated gas would exceed the trade's gross profit; disabled when unset
+    #[serde(default)]
+    pub max_gas_price_gwei: Option<f64>,
+    /// Pay DEX swap output here instead of the signer (e.g. a hedging vault); the
+    /// signer still funds and approves the inputs
+    #[serde(default)]
+    pub swap_recipient: Option<String>,
+    /// Halt after `max_slippage_breaches` consecutive round trips whose realized
+    /// slippage (decision price vs fills) exceeds this; disabled when unset
+    #[serde(default)]
+    pub max_realized_slippage_bps: Option<f64>,
+    #[serde(default = "default_max_slippage_breaches")]
+    pub max_slippage_breaches: u32,
+    /// Split round trips of at least `twap_min_notional_usd` into this many slices,
+    /// `twap_interval_ms` apart; 1 executes immediately
+    #[serde(default = "default_twap_slices")]
+    pub twap_slices: u32,
+    /// Re-check the live edge against `min_profit_bps` right before executing
+    #[serde(default)]
+    pub recheck_edge: bool,
+    #[serde(default = "default_twap_interval_ms")]
+    pub twap_interval_ms: u64,
+    #[serde(default)]
+    pub twap_min_notional_usd: f64,
+    /// Break round trips larger than this notional into clips of at most this size,
+    /// `twap_interval_ms` apart, each re-checking the edge first
+    #[serde(default)]
+    pub max_clip_usd: Option<f64>,
+    /// Stop emitting trades once executed notional over the last 60 minutes reaches this
+    #[serde(default)]
+    pub max_notional_per_hour_usd: Option<f64>,
+    /// Poll wallet balances and pause while native balance (in HYPE) is below this
+    #[serde(default)]
+    pub min_gas_balance: Option<f64>,
+    #[serde(default = "default_balance_poll_secs")]
+    pub balance_poll_secs: u64,
+    /// Compare wallet + HL holdings of the traded token against their startup level this
+    /// often; disabled when unset
+    #[serde(default)]
+    pub reconcile_interval_secs: Option<u64>,
+    /// Alert when holdings drift more than this many tokens from the startup level
+    #[serde(default)]
+    pub reconcile_alert_drift: f64,
+    /// Halt new trades once drift reaches this many tokens
+    #[serde(default)]
+    pub reconcile_halt_drift: Option<f64>,
+    /// Consecutive samples at `reconcile_halt_drift` needed to halt
+    #[serde(default = "default_reconcile_halt_samples")]
+    pub reconcile_halt_samples: u32,
+}
+
+fn default_heartbeat_secs() -> u64 {
+    30
+}
+
+fn default_http_poll_ms() -> u64 {
+    1000
+}
+
+fn default_startup_grace_secs() -> u64 {
+    60
+}
+
+fn default_native_price_poll_secs() -> u64 {
+    60
+}
+
+fn default_native_price_ttl_secs() -> u64 {
+    300
+}
+
+fn default_retry_backoff_ms() -> u64 {
+    100
+}
+
+fn default_unwind_max_loss_bps() -> f64 {
+    100.0
+}
+
+fn default_binance_fee_bps() -> f64 {
+    10.0
+}
+
+fn default_balance_poll_secs() -> u64 {
+    30
+}
+
+fn default_circuit_breaker_cooldown_secs() -> u64 {
+    300
+}
+
+fn default_health_stale_secs() -> u64 {
+    60
+}
+
+fn default_max_slippage_breaches() -> u32 {
+    3
+}
+
+fn default_size_ramp_bps() -> f64 {
+    10.0
+}
+
+fn default_swap_deadline_secs() -> u64 {
+    30
+}
+
+fn default_reconcile_halt_samples() -> u32 {
+    2
+}
+
+fn default_twap_slices() -> u32 {
+    1
 }
 
+fn default_twap_interval_ms() -> u64 {
+    2_000
+}
+
+fn default_funding_holding_hours() -> f64 {
+    1.0
+}
+
+fn default_hl_reprice_interval_ms() -> u64 {
+    1_000
+}
+
+fn default_hl_reprice_deadline_ms() -> u64 {
+    10_000
+}
+
+/// Fee tiers (hundredths of a bip) Uniswap V3 pools are deployed with
+const UNIV3_FEE_TIERS: &[u32] = &[100, 500, 3000, 10000];
+
+/// Scalar strategy parameters that can change without a restart (see SIGHUP reload).
+/// Addresses, venues and enabling/disabling a strategy still need one.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StrategyParams {
+    /// Strategy the parameters belong to
+    pub name: String,
+    pub min_profit_bps: f64,
+    pub slippage_bps: f64,
+    pub order_size_usd: f64,
+    pub hedge_fee_bps: f64,
+    pub dex_gas_fee_usd: f64,
+    pub max_divergence_bps: Option<f64>,
+}
+
+impl StrategyConfig {
+    pub fn params(&self) -> StrategyParams {
+        StrategyParams {
+            name: self.name.clone(),
+            min_profit_bps: self.min_profit_bps,
+            slippage_bps: self.slippage_bps,
+            order_size_usd: self.order_size_usd,
+            hedge_fee_bps: self.hedge_fee_bps(),
+            dex_gas_fee_usd: self.dex_gas_fee_usd,
+            max_divergence_bps: self.max_divergence_bps,
+        }
+    }
+
+    /// Market name on the configured hedge venue
+    pub fn hedge_coin(&self) -> &str {
+        match (self.cex_venue, &self.binance_symbol) {
+            (CexVenue::Binance, Some(symbol)) => symbol,
+            _ => &self.hyperliquid_coin,
+        }
+    }
+
+    /// Trading fee on the configured hedge venue
+    pub fn hedge_fee_bps(&self) -> f64 {
+        match self.cex_venue {
+            CexVenue::Hyperliquid => self.hl_maker_fee_bps,
+            CexVenue::Binance => self.binance_fee_bps,
+        }
+    }
+
+    fn validate(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        
+        let addresses = [
+            ("pool_address", Some(&self.pool_address)),
+            ("router_address", Some(&self.router_address)),
+            ("token_a_address", Some(&self.token_a_address)),
+            ("token_b_address", Some(&self.token_b_address)),
+            ("hl_vault_address", self.hl_vault_address.as_ref()),
+            ("hl_account_address", self.hl_account_address.as_ref()),
+            ("swap_recipient", self.swap_recipient.as_ref()),
+        ];
+        let extra_pools = self.pool_addresses.iter().map(|pool| ("pool_addresses", Some(pool)));
+        for (field, value) in addresses.into_iter().chain(extra_pools) {
+            if let Some(value) = value {
+                if value.parse::<Address>().is_err() {
+                    problems.push(format!("{} '{}' is not a valid address", field, value));
+                }
+            }
+        }
+        
+        if !UNIV3_FEE_TIERS.contains(&self.fee) {
+            problems.push(format!("fee {} is not a Uniswap V3 tier {:?}", self.fee, UNIV3_FEE_TIERS));
+        }
+        if self.cex_venue == CexVenue::Binance && self.binance_symbol.is_none() {
+            problems.push("cex_venue = \"binance\" requires binance_symbol".to_string());
+        }
+        if self.enabled && self.order_size_usd <= 0.0 {
+            problems.push("order_size_usd must be > 0".to_string());
+        }
+        match (self.min_notional_usd, self.max_notional_usd) {
+            (Some(min), Some(max)) if min <= 0.0 || min > max => {
+                problems.push(format!("min_notional_usd {} must be > 0 and at most max_notional_usd {}", min, max));
+            }
+            (Some(_), None) | (None, Some(_)) => {
+                problems.push("min_notional_usd and max_notional_usd must be set together".to_string());
+            }
+            _ => {}
+        }
+        if let (Some(min), Some(max)) = (self.sanity_min_price, self.sanity_max_price) {
+            if min >= max {
+                problems.push(format!("sanity_min_price {} must be below sanity_max_price {}", min, max));
+            }
+        }
+        if self.swap_deadline_secs == 0 {
+            problems.push("swap_deadline_secs must be > 0".to_string());
+        }
+        // Both read the signer's balance of the swap output, which would be elsewhere
+        if self.swap_recipient.is_some() && self.auto_unwind {
+            problems.push("auto_unwind cannot swap back output paid to swap_recipient".to_string());
+        }
+        if self.swap_recipient.is_some() && self.reconcile_interval_secs.is_some() {
+            problems.push("reconcile_interval_secs does not track output paid to swap_recipient".to_string());
+        }
+        // Native balance moves with gas outside swaps (approvals, unwinds), so it cannot gate a halt
+        if self.auto_wrap && self.reconcile_halt_drift.is_some() {
+            problems.push("reconcile_halt_drift cannot be used with auto_wrap".to_string());
+        }
+        if self.max_gas_price_gwei.is_some_and(|cap| cap <= 0.0) {
+            problems.push("max_gas_price_gwei must be > 0".to_string());
+        }
+        if self.max_clip_usd.is_some_and(|clip| clip <= 0.0) {
+            problems.push("max_clip_usd must be > 0".to_string());
+        }
+        if !(0.0..10_000.0).contains(&self.slippage_bps) {
+            problems.push(format!("slippage_bps {} must be in [0, 10000)", self.slippage_bps));
+        }
+        if !(0.0..10_000.0).contains(&self.min_profit_bps) {
+            problems.push(format!("min_profit_bps {} must be in [0, 10000)", self.min_profit_bps));
+        }
+        
+        problems
+    }
+}
+
+const ENV_PREFIX: &str = "RUSTYARB_";
+const ENV_STRATEGY_PREFIX: &str = "RUSTYARB_STRATEGY_";
+const ENV_REQUIRED_TOP: &[&str] = &["max_concurrent", "cooldown_secs"];
+const ENV_REQUIRED_STRATEGY: &[&str] = &[
+    "pool_address", "router_address", "fee", "token_a_address", "token_b_address",
+    "hyperliquid_coin", "order_size_usd", "hl_maker_fee_bps", "dex_gas_fee_usd",
+    "min_profit_bps", "slippage_bps",
+];
+/// Fields that must stay strings even if they look numeric
+const ENV_STRING_FIELDS: &[&str] = &[
+    "rpc_url_ws", "rpc_url_http", "record_path", "evaluate_addr", "health_addr", "log_format", "native_price_coingecko_id", "alert_webhook_url", "name", "pool_address",
+    "router_address", "token_a_address", "token_b_address", "hyperliquid_coin",
+    "hl_tif", "submission_mode", "hl_vault_address", "hl_account_address", "cex_venue", "binance_symbol",
+    "swap_recipient", "on_both_profitable",
+];
+
 impl Config {
     pub fn load(path: &str) -> Result<Self> {
         let content = fs::read_to_string(path)?;
@@ -38,18 +494,93 @@ impl Config {
         let content = Self::substitute_env_vars(&content)?;
         
         let config: Config = toml::from_str(&content)?;
+        config.validate()
+    }
+    
+    /// Build a single-strategy config from environment variables, for deploys without
+    /// a config file. Every field maps to an upper-cased variable: top-level fields to
+    /// `RUSTYARB_<FIELD>` (e.g. `RUSTYARB_RPC_URL_WS`) and strategy fields to
+    /// `RUSTYARB_STRATEGY_<FIELD>` (e.g. `RUSTYARB_STRATEGY_POOL_ADDRESS`). Optional
+    /// fields keep their file defaults; all missing required variables are reported at once.
+    pub fn from_env() -> Result<Self> {
+        Self::from_vars(std::env::vars())
+    }
+    
+    fn from_vars(vars: impl Iterator<Item = (String, String)>) -> Result<Self> {
+        let mut top = toml::Table::new();
+        let mut strategy = toml::Table::new();
+        for (key, value) in vars {
+            if let Some(field) = key.strip_prefix(ENV_STRATEGY_PREFIX) {
+                let field = field.to_ascii_lowercase();
+                strategy.insert(field.clone(), Self::env_value(&field, value));
+            } else if let Some(field) = key.strip_prefix(ENV_PREFIX) {
+                let field = field.to_ascii_lowercase();
+                top.insert(field.clone(), Self::env_value(&field, value));
+            }
+        }
         
-        if config.max_concurrent == 0 {
-            anyhow::bail!("max_concurrent must be > 0");
+        strategy.entry("name").or_insert(toml::Value::String("env".to_string()));
+        strategy.entry("enabled").or_insert(toml::Value::Boolean(true));
+        
+        let missing: Vec<String> = ENV_REQUIRED_TOP.iter()
+            .filter(|field| !top.contains_key(**field))
+            .map(|field| format!("{}{}", ENV_PREFIX, field.to_ascii_uppercase()))
+            .chain(ENV_REQUIRED_STRATEGY.iter()
+                .filter(|field| !strategy.contains_key(**field))
+                .map(|field| format!("{}{}", ENV_STRATEGY_PREFIX, field.to_ascii_uppercase())))
+            .collect();
+        if !missing.is_empty() {
+            anyhow::bail!("Missing required environment variables: {}", missing.join(", "));
+        }
+        
+        top.insert("strategies".to_string(), toml::Value::Array(vec![toml::Value::Table(strategy)]));
+        let config: Config = toml::Value::Table(top).try_into()?;
+        config.validate()
+    }
+    
+    /// Type an env value for deserialization: known string fields stay strings, tables
+    /// (e.g. `hl_order_mode`) may be given as inline TOML, everything else is inferred
+    fn env_value(field: &str, value: String) -> toml::Value {
+        if ENV_STRING_FIELDS.contains(&field) {
+            return toml::Value::String(value);
+        }
+        if value.starts_with('{') {
+            if let Some(table) = format!("v = {}", value).parse::<toml::Table>().ok().and_then(|mut t| t.remove("v")) {
+                return table;
+            }
+        }
+        if let Ok(b) = value.parse::<bool>() {
+            return toml::Value::Boolean(b);
+        }
+        if let Ok(i) = value.parse::<i64>() {
+            return toml::Value::Integer(i);
+        }
+        if let Ok(f) = value.parse::<f64>() {
+            return toml::Value::Float(f);
+        }
+        toml::Value::String(value)
+    }
+    
+    /// Check every strategy up front and report all problems at once, naming the strategy
+    fn validate(self) -> Result<Self> {
+        let mut errors = Vec::new();
+        if self.max_concurrent == 0 {
+            errors.push("max_concurrent must be > 0".to_string());
+        }
+        if self.rpc_url_ws.is_empty() && self.rpc_url_http.is_none() {
+            errors.push("rpc_url_ws or rpc_url_http must be set".to_string());
         }
         
-        for strategy in &config.strategies {
-            if strategy.enabled && strategy.order_size_usd <= 0.0 {
-                anyhow::bail!("order_size_usd must be > 0 in strategy '{}'", strategy.name);
+        for strategy in &self.strategies {
+            for problem in strategy.validate() {
+                errors.push(format!("strategy '{}': {}", strategy.name, problem));
             }
         }
         
-        Ok(config)
+        if !errors.is_empty() {
+            anyhow::bail!("Invalid config:\n  {}", errors.join("\n  "));
+        }
+        Ok(self)
     }
     
     fn substitute_env_vars(content: &str) -> Result<String> {
@@ -67,3 +598,113 @@ impl Config {
         Ok(result)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vars(overrides: &[(&str, &str)]) -> Vec<(String, String)> {
+        let mut vars: Vec<(String, String)> = [
+            ("RUSTYARB_RPC_URL_WS", "wss://rpc.example"),
+            ("RUSTYARB_MAX_CONCURRENT", "1"),
+            ("RUSTYARB_COOLDOWN_SECS", "15"),
+            ("RUSTYARB_STRATEGY_POOL_ADDRESS", "0xe712d505572b3f84c1b4deb99e1beab9dd0e23c9"),
+            ("RUSTYARB_STRATEGY_ROUTER_ADDRESS", "0x6D99e7f6747AF2cDbB5164b6DD50e40D4fDe1e77"),
+            ("RUSTYARB_STRATEGY_FEE", "3000"),
+            ("RUSTYARB_STRATEGY_TOKEN_A_ADDRESS", "0xb88339cb7199b77e23db6e890353e22632ba630f"),
+            ("RUSTYARB_STRATEGY_TOKEN_B_ADDRESS", "0x5555555555555555555555555555555555555555"),
+            ("RUSTYARB_STRATEGY_HYPERLIQUID_COIN", "HYPE"),
+            ("RUSTYARB_STRATEGY_ORDER_SIZE_USD", "100.0"),
+            ("RUSTYARB_STRATEGY_HL_MAKER_FEE_BPS", "1.5"),
+            ("RUSTYARB_STRATEGY_DEX_GAS_FEE_USD", "0.05"),
+            ("RUSTYARB_STRATEGY_MIN_PROFIT_BPS", "10.0"),
+            ("RUSTYARB_STRATEGY_SLIPPAGE_BPS", "50.0"),
+        ]
+        .iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+        for (key, value) in overrides {
+            vars.retain(|(k, _)| k != key);
+            vars.push((key.to_string(), value.to_string()));
+        }
+        vars
+    }
+
+    fn invalid(overrides: &[(&str, &str)]) -> String {
+        Config::from_vars(vars(overrides).into_iter()).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn valid_config_passes() {
+        assert!(Config::from_vars(vars(&[]).into_iter()).is_ok());
+    }
+
+    #[test]
+    fn bad_address_is_rejected() {
+        let err = invalid(&[("RUSTYARB_STRATEGY_POOL_ADDRESS", "0x1234")]);
+        assert!(err.contains("strategy 'env': pool_address '0x1234' is not a valid address"), "{}", err);
+    }
+
+    #[test]
+    fn bad_fee_tier_is_rejected() {
+        let err = invalid(&[("RUSTYARB_STRATEGY_FEE", "2500")]);
+        assert!(err.contains("fee 2500 is not a Uniswap V3 tier"), "{}", err);
+    }
+
+    #[test]
+    fn negative_bps_are_rejected() {
+        let err = invalid(&[("RUSTYARB_STRATEGY_SLIPPAGE_BPS", "-5.0"), ("RUSTYARB_STRATEGY_MIN_PROFIT_BPS", "-1.0")]);
+        assert!(err.contains("slippage_bps -5 must be in [0, 10000)"), "{}", err);
+        assert!(err.contains("min_profit_bps -1 must be in [0, 10000)"), "{}", err);
+    }
+
+    #[test]
+    fn every_problem_is_reported_at_once() {
+        let err = invalid(&[("RUSTYARB_STRATEGY_ROUTER_ADDRESS", "router"), ("RUSTYARB_STRATEGY_FEE", "1")]);
+        assert!(err.contains("router_address 'router'") && err.contains("fee 1 is not"), "{}", err);
+    }
+
+    #[test]
+    fn missing_variables_are_listed_together() {
+        let vars = vars(&[]).into_iter()
+            .filter(|(k, _)| k != "RUSTYARB_COOLDOWN_SECS" && k != "RUSTYARB_STRATEGY_FEE");
+        let err = Config::from_vars(vars).unwrap_err().to_string();
+        assert!(err.contains("RUSTYARB_COOLDOWN_SECS, RUSTYARB_STRATEGY_FEE"), "{}", err);
+    }
+
+    #[test]
+    fn env_config_matches_the_equivalent_toml() {
+        // No other test reads the process environment
+        for (key, value) in vars(&[]) {
+            unsafe { std::env::set_var(key, value) };
+        }
+        let from_env = Config::from_env().unwrap();
+
+        let path = std::env::temp_dir().join(format!("rustyarb-config-{}.toml", std::process::id()));
+        fs::write(&path, r#"
+            rpc_url_ws = "wss://rpc.example"
+            max_concurrent = 1
+            cooldown_secs = 15
+
+            [[strategies]]
+            name = "env"
+            enabled = true
+            pool_address = "0xe712d505572b3f84c1b4deb99e1beab9dd0e23c9"
+            router_address = "0x6D99e7f6747AF2cDbB5164b6DD50e40D4fDe1e77"
+            fee = 3000
+            token_a_address = "0xb88339cb7199b77e23db6e890353e22632ba630f"
+            token_b_address = "0x5555555555555555555555555555555555555555"
+            hyperliquid_coin = "HYPE"
+            order_size_usd = 100.0
+            hl_maker_fee_bps = 1.5
+            dex_gas_fee_usd = 0.05
+            min_profit_bps = 10.0
+            slippage_bps = 50.0
+        "#).unwrap();
+        let from_file = Config::load(path.to_str().unwrap()).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        // Config has no PartialEq; Debug covers every field
+        assert_eq!(format!("{:?}", from_env), format!("{:?}", from_file));
+    }
+}
@@ -1,9 +1,25 @@
-use tokio::sync::broadcast::{self, Sender};
+use std::future::Future;
+use std::sync::Arc;
+
+use tokio::sync::broadcast::{self, error::RecvError, Sender};
+use tokio::sync::Mutex;
 use tokio::task::JoinSet;
 use tokio_stream::StreamExt;
 use tracing::{error, info};
 
+use crate::collectors::recorder::{EventRecorder, RecordingCollector};
 use crate::types::{Collector, Executor, Strategy};
+use crate::utilities::health::HealthState;
+
+/// Applied to every collector when the engine starts (e.g. to tee events to disk)
+type CollectorWrapper<E> = Box<dyn Fn(Box<dyn Collector<E>>) -> Box<dyn Collector<E>> + Send>;
+
+/// The collectors, strategy and executors that make up one strategy, registered together
+pub struct StrategyBundle<E, A> {
+    pub collectors: Vec<Box<dyn Collector<E>>>,
+    pub strategy: Box<dyn Strategy<E, A>>,
+    pub executors: Vec<Box<dyn Executor<A>>>,
+}
 
 /// The main engine of Artemis. This struct is responsible for orchestrating the
 /// data flow between collectors, strategies, and executors.
@@ -22,6 +38,15 @@ pub struct Engine<E, A> {
 
     /// The capacity of the action channel.
     action_channel_capacity: usize,
+
+    /// How many times a panicked collector or strategy task is respawned.
+    max_task_restarts: u32,
+
+    /// Optional wrapper applied to each collector in `run`.
+    collector_wrapper: Option<CollectorWrapper<E>>,
+
+    /// Optional liveness state updated by the collector loops.
+    health: Option<Arc<HealthState>>,
 }
 
 impl<E, A> Engine<E, A> {
@@ -32,6 +57,9 @@ impl<E, A> Engine<E, A> {
             executors: vec![],
             event_channel_capacity: 512,
             action_channel_capacity: 512,
+            max_task_restarts: 3,
+            collector_wrapper: None,
+            health: None,
         }
     }
 
@@ -44,6 +72,32 @@ impl<E, A> Engine<E, A> {
         self.action_channel_capacity = capacity;
         self
     }
+
+    /// Report collector liveness and last-event times to `health`
+    pub fn with_health(mut self, health: Arc<HealthState>) -> Self {
+        self.health = Some(health);
+        self
+    }
+
+    pub fn with_max_task_restarts(mut self, max_restarts: u32) -> Self {
+        self.max_task_restarts = max_restarts;
+        self
+    }
+}
+
+impl<E, A> Engine<E, A>
+where
+    E: serde::Serialize + Send + Sync + 'static,
+{
+    /// Append every collected event to `recorder` as a timestamped JSON line before it
+    /// is broadcast. Lines are flushed whenever the writer catches up, so an abrupt
+    /// shutdown loses at most the events still in flight.
+    pub fn with_event_recorder(mut self, recorder: EventRecorder<E>) -> Self {
+        self.collector_wrapper = Some(Box::new(move |collector| {
+            Box::new(RecordingCollector::new(collector, recorder.clone()))
+        }));
+        self
+    }
 }
 
 impl<E, A> Default for Engine<E, A> {
@@ -72,6 +126,13 @@ where
         self.executors.push(executor);
     }
 
+    /// Adds every component of one strategy.
+    pub fn add_strategy_bundle(&mut self, bundle: StrategyBundle<E, A>) {
+        self.collectors.extend(bundle.collectors);
+        self.strategies.push(bundle.strategy);
+        self.executors.extend(bundle.executors);
+    }
+
     /// The core run loop of the engine. This function will spawn a thread for
     /// each collector, strategy, and executor. It will then orchestrate the
     /// data flow between them.
@@ -111,57 +172,281 @@ where
                                 }
                             });
                         }
+                        Err(RecvError::Closed) => break,
                         Err(e) => error!("error receiving action: {}", e),
                     }
                 }
             });
         }
 
-        // Spawn strategies in separate threads.
+        // Spawn strategies in separate threads. The strategy and its receiver live
+        // behind a mutex so a respawn after a panic resumes with the same state.
         for mut strategy in self.strategies {
-            let mut event_receiver = event_sender.subscribe();
+            let event_receiver = event_sender.subscribe();
             let action_sender_clone = _action_sender.clone();
             strategy.sync_state().await?;
+            let shared = Arc::new(Mutex::new((strategy, event_receiver)));
 
-            set.spawn(async move {
-                info!("starting strategy... ");
-                loop {
-                    match event_receiver.recv().await {
-                        Ok(event) => {
-                            for action in strategy.process_event(event).await {
-                                match action_sender_clone.send(action) {
-                                    Ok(_) => {}
-                                    Err(e) => error!("error sending action: {}", e),
+            set.spawn(supervise("strategy", self.max_task_restarts, move || {
+                let shared = shared.clone();
+                let action_sender_clone = action_sender_clone.clone();
+                async move {
+                    info!("starting strategy... ");
+                    let mut guard = shared.lock().await;
+                    let (strategy, event_receiver) = &mut *guard;
+                    let mut ticker = strategy.tick_interval().map(|period| {
+                        let mut ticker = tokio::time::interval(period);
+                        // The first tick completes immediately; start one period in
+                        ticker.reset();
+                        ticker
+                    });
+                    loop {
+                        let actions = tokio::select! {
+                            received = event_receiver.recv() => match received {
+                                Ok(event) if strategy.interested_in(&event) => strategy.process_event(event).await,
+                                Ok(_) => continue,
+                                // All collectors finished (e.g. a replay ran out)
+                                Err(RecvError::Closed) => break,
+                                Err(e) => {
+                                    error!("error receiving event: {}", e);
+                                    continue;
+                                }
+                            },
+                            _ = async {
+                                match ticker.as_mut() {
+                                    Some(ticker) => { ticker.tick().await; }
+                                    None => std::future::pending::<()>().await,
                                 }
+                            } => strategy.on_tick().await,
+                        };
+                        for action in actions {
+                            match action_sender_clone.send(action) {
+                                Ok(_) => {}
+                                Err(e) => error!("error sending action: {}", e),
                             }
                         }
-                        Err(e) => error!("error receiving event: {}", e),
                     }
                 }
-            });
+            }));
         }
 
         // Spawn collectors in separate threads.
-        for collector in self.collectors {
+        for (i, collector) in self.collectors.into_iter().enumerate() {
             let event_sender = event_sender.clone();
-            set.spawn(async move {
-                info!("starting collector... ");
-                match collector.get_event_stream().await {
-                    Ok(mut event_stream) => {
-                        while let Some(event) = event_stream.next().await {
-                            match event_sender.send(event) {
-                                Ok(_) => {}
-                                Err(e) => error!("error sending event: {}", e),
+            let collector = match &self.collector_wrapper {
+                Some(wrap) => wrap(collector),
+                None => collector,
+            };
+            let collector = Arc::new(collector);
+            let probe = self.health.as_ref()
+                .map(|h| h.register_collector(format!("{}-{}", collector.name(), i), collector.expects_steady_events()));
+            set.spawn(supervise("collector", self.max_task_restarts, move || {
+                let collector = collector.clone();
+                let event_sender = event_sender.clone();
+                let probe = probe.clone();
+                async move {
+                    info!("starting collector... ");
+                    let _alive = probe.as_ref().map(|p| p.alive_guard());
+                    match collector.get_event_stream().await {
+                        Ok(mut event_stream) => {
+                            while let Some(event) = event_stream.next().await {
+                                if let Some(probe) = &probe {
+                                    probe.touch();
+                                }
+                                match event_sender.send(event) {
+                                    Ok(_) => {}
+                                    Err(e) => error!("error sending event: {}", e),
+                                }
                             }
                         }
-                    }
-                    Err(e) => {
-                        error!("failed to get event stream from collector: {}", e);
+                        Err(e) => {
+                            error!("failed to get event stream from collector: {}", e);
+                        }
                     }
                 }
-            });
+            }));
         }
 
         Ok(set)
     }
-}
\ No newline at end of file
+}
+/// Run the task built by `spawn_task`, respawning it if it panics, up to `max_restarts`
+/// times. A task that returns normally (e.g. a finished replay) is not restarted.
+async fn supervise<F, Fut>(name: &'static str, max_restarts: u32, spawn_task: F)
+where
+    F: Fn() -> Fut,
+    Fut: Future<Output = ()> + Send + 'static,
+{
+    let mut restarts = 0;
+    loop {
+        match tokio::spawn(spawn_task()).await {
+            Ok(()) => break,
+            Err(e) if e.is_panic() && restarts < max_restarts => {
+                restarts += 1;
+                error!("{} task panicked, restarting ({}/{})", name, restarts, max_restarts);
+            }
+            Err(e) => {
+                error!("{} task stopped: {}", name, e);
+                break;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collectors::replay::RecordedEvent;
+    use crate::types::CollectorStream;
+    use anyhow::Result;
+    use async_trait::async_trait;
+    use std::sync::atomic::{AtomicU32, Ordering};
+    use std::time::Duration;
+
+    /// Panics on its first `get_event_stream`, then streams its events
+    struct FlakyCollector {
+        attempts: AtomicU32,
+        events: Vec<u32>,
+    }
+
+    #[async_trait]
+    impl Collector<u32> for FlakyCollector {
+        async fn get_event_stream(&self) -> Result<CollectorStream<'_, u32>> {
+            if self.attempts.fetch_add(1, Ordering::SeqCst) == 0 {
+                panic!("first connect blew up");
+            }
+            Ok(Box::pin(tokio_stream::iter(self.events.clone())))
+        }
+    }
+
+    struct Recorder(Arc<std::sync::Mutex<Vec<u32>>>);
+
+    #[async_trait]
+    impl Strategy<u32, u32> for Recorder {
+        async fn sync_state(&mut self) -> Result<()> {
+            Ok(())
+        }
+
+        async fn process_event(&mut self, event: u32) -> Vec<u32> {
+            self.0.lock().unwrap().push(event);
+            vec![]
+        }
+    }
+
+    #[tokio::test]
+    async fn panicked_collector_is_respawned_and_its_events_arrive() {
+        let seen = Arc::new(std::sync::Mutex::new(vec![]));
+        let mut engine: Engine<u32, u32> = Engine::new().with_max_task_restarts(1);
+        engine.add_collector(Box::new(FlakyCollector { attempts: AtomicU32::new(0), events: vec![1, 2, 3] }));
+        engine.add_strategy(Box::new(Recorder(seen.clone())));
+
+        let mut set = engine.run().await.unwrap();
+        // Every task ends once the respawned collector's stream runs out
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while set.join_next().await.is_some() {}
+        })
+        .await
+        .expect("engine did not wind down");
+
+        assert_eq!(*seen.lock().unwrap(), vec![1, 2, 3]);
+    }
+
+    #[tokio::test]
+    async fn supervise_gives_up_after_max_restarts() {
+        let runs = Arc::new(AtomicU32::new(0));
+        let counter = runs.clone();
+        supervise("test", 2, move || {
+            let counter = counter.clone();
+            async move {
+                counter.fetch_add(1, Ordering::SeqCst);
+                panic!("always");
+            }
+        })
+        .await;
+        assert_eq!(runs.load(Ordering::SeqCst), 3);
+    }
+
+    /// Never emits an event
+    struct SilentCollector;
+
+    #[async_trait]
+    impl Collector<u32> for SilentCollector {
+        async fn get_event_stream(&self) -> Result<CollectorStream<'_, u32>> {
+            Ok(Box::pin(tokio_stream::pending()))
+        }
+    }
+
+    struct Ticker(Arc<AtomicU32>);
+
+    #[async_trait]
+    impl Strategy<u32, u32> for Ticker {
+        async fn sync_state(&mut self) -> Result<()> {
+            Ok(())
+        }
+
+        async fn process_event(&mut self, _event: u32) -> Vec<u32> {
+            vec![]
+        }
+
+        fn tick_interval(&self) -> Option<Duration> {
+            Some(Duration::from_millis(20))
+        }
+
+        async fn on_tick(&mut self) -> Vec<u32> {
+            self.0.fetch_add(1, Ordering::SeqCst);
+            vec![]
+        }
+    }
+
+    #[tokio::test]
+    async fn heartbeat_ticks_without_any_events() {
+        let ticks = Arc::new(AtomicU32::new(0));
+        let mut engine: Engine<u32, u32> = Engine::new();
+        engine.add_collector(Box::new(SilentCollector));
+        engine.add_strategy(Box::new(Ticker(ticks.clone())));
+
+        let mut set = engine.run().await.unwrap();
+        // Nothing fires before the first interval is up
+        assert_eq!(ticks.load(Ordering::SeqCst), 0);
+        tokio::time::sleep(Duration::from_millis(70)).await;
+        assert!(ticks.load(Ordering::SeqCst) >= 2, "{}", ticks.load(Ordering::SeqCst));
+        set.abort_all();
+    }
+
+    #[tokio::test]
+    async fn recorder_writes_every_event_the_strategy_sees() {
+        let path = std::env::temp_dir().join(format!("rustyarb-engine-{}.jsonl", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let seen = Arc::new(std::sync::Mutex::new(vec![]));
+        let mut engine: Engine<u32, u32> = Engine::new().with_event_recorder(EventRecorder::spawn(&path).await.unwrap());
+        // Past its first attempt, so it streams without panicking
+        engine.add_collector(Box::new(FlakyCollector { attempts: AtomicU32::new(1), events: vec![7, 8] }));
+        engine.add_strategy(Box::new(Recorder(seen.clone())));
+
+        let mut set = engine.run().await.unwrap();
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while set.join_next().await.is_some() {}
+        })
+        .await
+        .expect("engine did not wind down");
+
+        // Writes happen in the background; wait for both lines to land
+        let content = tokio::time::timeout(Duration::from_secs(5), async {
+            loop {
+                let content = tokio::fs::read_to_string(&path).await.unwrap_or_default();
+                if content.lines().count() >= 2 {
+                    return content;
+                }
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("recording was not flushed");
+        std::fs::remove_file(&path).unwrap();
+
+        let records: Vec<RecordedEvent<u32>> = content.lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+        assert_eq!(records.iter().map(|r| r.event).collect::<Vec<_>>(), *seen.lock().unwrap());
+        assert_eq!(*seen.lock().unwrap(), vec![7, 8]);
+        assert!(records.iter().all(|r| r.ts_ms > 0));
+    }
+}
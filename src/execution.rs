@@ -1,10 +1,20 @@
-use std::sync::Arc;
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use tokio::sync::{Semaphore, OwnedSemaphorePermit};
 
+use crate::types::Direction;
+
 /// Manages execution concurrency across all arbitrage strategies
 #[derive(Clone)]
 pub struct ExecutionManager {
     semaphore: Arc<Semaphore>,
+    max_concurrent: usize,
+    // Cooldown gate: ms since `epoch` before which no new execution may start
+    epoch: Instant,
+    cooldown_until_ms: Arc<AtomicU64>,
 }
 
 impl ExecutionManager {
@@ -12,15 +22,38 @@ impl ExecutionManager {
     pub fn new(max_concurrent: usize) -> Self {
         Self {
             semaphore: Arc::new(Semaphore::new(max_concurrent)),
+            max_concurrent,
+            epoch: Instant::now(),
+            cooldown_until_ms: Arc::new(AtomicU64::new(0)),
         }
     }
 
-    /// Try to start execution (non-blocking)
+    /// Try to start execution (non-blocking); None while busy or cooling down
     pub fn try_start(&self) -> Option<ExecutionPermit> {
+        if self.cooldown_remaining().is_some() {
+            return None;
+        }
         self.semaphore.clone().try_acquire_owned().ok().map(|permit| {
             ExecutionPermit { _permit: permit }
         })
     }
+
+    /// Block new executions for `cooldown` from now without holding a permit
+    pub fn start_cooldown(&self, cooldown: Duration) {
+        let until = (self.epoch.elapsed() + cooldown).as_millis() as u64;
+        self.cooldown_until_ms.fetch_max(until, Ordering::SeqCst);
+    }
+
+    /// True while any execution holds a permit
+    pub fn is_busy(&self) -> bool {
+        self.semaphore.available_permits() < self.max_concurrent
+    }
+
+    pub fn cooldown_remaining(&self) -> Option<Duration> {
+        let until = self.cooldown_until_ms.load(Ordering::SeqCst);
+        let now = self.epoch.elapsed().as_millis() as u64;
+        (until > now).then(|| Duration::from_millis(until - now))
+    }
 }
 
 /// RAII permit - auto-releases on drop
@@ -28,3 +61,278 @@ pub struct ExecutionPermit {
     _permit: OwnedSemaphorePermit,
 }
 
+/// Tracks realized slippage (decision price vs fills) and halts trading once it
+/// breaches the limit on too many consecutive round trips, a sign we are being picked off
+pub struct SlippageMonitor {
+    max_slippage_bps: f64,
+    max_breaches: u32,
+    consecutive_breaches: AtomicU32,
+    halted: AtomicBool,
+}
+
+impl SlippageMonitor {
+    pub fn new(max_slippage_bps: f64, max_breaches: u32) -> Self {
+        Self {
+            max_slippage_bps,
+            max_breaches: max_breaches.max(1),
+            consecutive_breaches: AtomicU32::new(0),
+            halted: AtomicBool::new(false),
+        }
+    }
+
+    /// Record one round trip's total slippage; returns true if this trips the halt
+    pub fn record(&self, slippage_bps: f64) -> bool {
+        if slippage_bps <= self.max_slippage_bps {
+            self.consecutive_breaches.store(0, Ordering::SeqCst);
+            return false;
+        }
+
+        let breaches = self.consecutive_breaches.fetch_add(1, Ordering::SeqCst) + 1;
+        if breaches >= self.max_breaches && !self.halted.swap(true, Ordering::SeqCst) {
+            return true;
+        }
+        false
+    }
+
+    pub fn is_halted(&self) -> bool {
+        self.halted.load(Ordering::SeqCst)
+    }
+
+    /// Manually resume trading after investigating
+    pub fn reset(&self) {
+        self.consecutive_breaches.store(0, Ordering::SeqCst);
+        self.halted.store(false, Ordering::SeqCst);
+    }
+}
+
+/// Latest net edge per direction as seen by the strategy, shared with executors so
+/// sliced executions can stop once the opportunity is gone
+#[derive(Debug)]
+pub struct EdgeGauge {
+    // f64 bits; NaN until the first quote
+    buy_dex_bps: AtomicU64,
+    buy_hl_bps: AtomicU64,
+}
+
+impl EdgeGauge {
+    pub fn new() -> Self {
+        Self {
+            buy_dex_bps: AtomicU64::new(f64::NAN.to_bits()),
+            buy_hl_bps: AtomicU64::new(f64::NAN.to_bits()),
+        }
+    }
+
+    pub fn update(&self, buy_dex_bps: f64, buy_hl_bps: f64) {
+        self.buy_dex_bps.store(buy_dex_bps.to_bits(), Ordering::SeqCst);
+        self.buy_hl_bps.store(buy_hl_bps.to_bits(), Ordering::SeqCst);
+    }
+
+    /// Current net edge for a direction, if one has been quoted
+    pub fn edge_bps(&self, direction: Direction) -> Option<f64> {
+        let bits = match direction {
+            Direction::BuyDexSellCex => &self.buy_dex_bps,
+            Direction::BuyCexSellDex => &self.buy_hl_bps,
+        };
+        Some(f64::from_bits(bits.load(Ordering::SeqCst))).filter(|bps| !bps.is_nan())
+    }
+}
+
+impl Default for EdgeGauge {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Executed notional over a sliding window, recorded by executors after each round
+/// trip and read by strategies to cap how much they trade
+#[derive(Debug)]
+pub struct PositionTracker {
+    window: Duration,
+    fills: Mutex<VecDeque<(Instant, f64)>>,
+}
+
+impl PositionTracker {
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            fills: Mutex::new(VecDeque::new()),
+        }
+    }
+
+    pub fn record_fill(&self, notional_usd: f64) {
+        self.fills.lock().unwrap().push_back((Instant::now(), notional_usd));
+    }
+
+    /// Notional executed within the window, evicting entries that rolled off
+    pub fn notional_in_window(&self) -> f64 {
+        let mut fills = self.fills.lock().unwrap();
+        while fills.front().is_some_and(|(at, _)| at.elapsed() > self.window) {
+            fills.pop_front();
+        }
+        fills.iter().map(|(_, notional)| notional).sum()
+    }
+}
+
+#[derive(Debug)]
+pub enum CircuitBreakerError {
+    /// Too many consecutive failures; executions resume after `remaining`
+    Tripped { remaining: Duration },
+}
+
+impl fmt::Display for CircuitBreakerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CircuitBreakerError::Tripped { remaining } => {
+                write!(f, "Circuit breaker open ({:.0}s until retry)", remaining.as_secs_f64())
+            }
+        }
+    }
+}
+
+impl std::error::Error for CircuitBreakerError {}
+
+/// Counts consecutive execution failures across every executor sharing it and, past
+/// the threshold, rejects executions until the cooldown passes or it is reset
+#[derive(Debug)]
+pub struct CircuitBreaker {
+    max_failures: u32,
+    cooldown: Duration,
+    consecutive_failures: AtomicU32,
+    tripped_at: Mutex<Option<Instant>>,
+}
+
+impl CircuitBreaker {
+    pub fn new(max_failures: u32, cooldown: Duration) -> Self {
+        Self {
+            max_failures: max_failures.max(1),
+            cooldown,
+            consecutive_failures: AtomicU32::new(0),
+            tripped_at: Mutex::new(None),
+        }
+    }
+
+    /// Err while open; closes again (with a clean count) once the cooldown has elapsed
+    pub fn check(&self) -> Result<(), CircuitBreakerError> {
+        let mut tripped_at = self.tripped_at.lock().unwrap();
+        if let Some(at) = *tripped_at {
+            match self.cooldown.checked_sub(at.elapsed()).filter(|d| !d.is_zero()) {
+                Some(remaining) => return Err(CircuitBreakerError::Tripped { remaining }),
+                None => {
+                    *tripped_at = None;
+                    self.consecutive_failures.store(0, Ordering::SeqCst);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn record_success(&self) {
+        self.consecutive_failures.store(0, Ordering::SeqCst);
+    }
+
+    /// Count a failure; returns true if this one trips the breaker
+    pub fn record_failure(&self) -> bool {
+        let failures = self.consecutive_failures.fetch_add(1, Ordering::SeqCst) + 1;
+        if failures < self.max_failures {
+            return false;
+        }
+        let mut tripped_at = self.tripped_at.lock().unwrap();
+        if tripped_at.is_some() {
+            return false;
+        }
+        *tripped_at = Some(Instant::now());
+        true
+    }
+
+    pub fn is_open(&self) -> bool {
+        self.check().is_err()
+    }
+
+    /// Manually close the breaker after investigating
+    pub fn reset(&self) {
+        *self.tripped_at.lock().unwrap() = None;
+        self.consecutive_failures.store(0, Ordering::SeqCst);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cooldown_blocks_new_executions_without_holding_a_permit() {
+        let manager = ExecutionManager::new(1);
+        manager.start_cooldown(Duration::from_secs(5));
+        assert!(!manager.is_busy());
+        assert!(manager.cooldown_remaining().is_some());
+        assert!(manager.try_start().is_none());
+
+        let manager = ExecutionManager::new(1);
+        let permit = manager.try_start().unwrap();
+        assert!(manager.is_busy());
+        assert!(manager.try_start().is_none());
+        drop(permit);
+        assert!(!manager.is_busy());
+        assert!(manager.try_start().is_some());
+    }
+
+    #[test]
+    fn breaker_trips_at_the_threshold_and_rejects_during_cooldown() {
+        let breaker = CircuitBreaker::new(3, Duration::from_secs(60));
+        assert!(!breaker.record_failure());
+        assert!(!breaker.record_failure());
+        assert!(breaker.check().is_ok());
+        assert!(breaker.record_failure());
+        assert!(breaker.is_open());
+        match breaker.check() {
+            Err(CircuitBreakerError::Tripped { remaining }) => assert!(remaining > Duration::from_secs(59)),
+            Ok(()) => panic!("breaker should be open"),
+        }
+        // Further failures while open don't re-trip it
+        assert!(!breaker.record_failure());
+    }
+
+    #[test]
+    fn breaker_closes_after_the_cooldown() {
+        let breaker = CircuitBreaker::new(1, Duration::from_millis(20));
+        assert!(breaker.record_failure());
+        assert!(breaker.is_open());
+        std::thread::sleep(Duration::from_millis(30));
+        assert!(breaker.check().is_ok());
+        // Closing cleared the count, so one more failure is needed to trip again
+        assert!(breaker.record_failure());
+    }
+
+    #[test]
+    fn success_resets_the_failure_count() {
+        let breaker = CircuitBreaker::new(2, Duration::from_secs(60));
+        assert!(!breaker.record_failure());
+        breaker.record_success();
+        assert!(!breaker.record_failure());
+        assert!(!breaker.is_open());
+        assert!(breaker.record_failure());
+
+        breaker.reset();
+        assert!(!breaker.is_open());
+    }
+
+    #[test]
+    fn consecutive_high_slippage_halts_and_a_good_fill_resets_the_count() {
+        let monitor = SlippageMonitor::new(20.0, 3);
+        assert!(!monitor.record(25.0));
+        assert!(!monitor.record(40.0));
+        // A fill within the limit breaks the streak
+        assert!(!monitor.record(5.0));
+        assert!(!monitor.record(25.0));
+        assert!(!monitor.record(25.0));
+        assert!(!monitor.is_halted());
+
+        assert!(monitor.record(30.0));
+        assert!(monitor.is_halted());
+        // Only the breach that trips it reports true
+        assert!(!monitor.record(30.0));
+
+        monitor.reset();
+        assert!(!monitor.is_halted());
+    }
+}
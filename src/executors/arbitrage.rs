@@ -1,95 +1,1323 @@
-use std::sync::Arc;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 use anyhow::Result;
 use async_trait::async_trait;
-use alloy::providers::Provider;
-use tracing::{error, info};
+use alloy::{
+    primitives::{TxHash, U256},
+    providers::Provider,
+};
+use serde::{Deserialize, Serialize};
+use tracing::{error, info, warn};
 
-use crate::execution::ExecutionManager;
+use crate::execution::{CircuitBreaker, EdgeGauge, ExecutionManager, PositionTracker, SlippageMonitor};
 use crate::executors::{
-    univ3::{UniV3Executor, UniV3SwapAction},
-    hyperliquid::{HyperliquidExecutor, HyperliquidOrderAction},
+    binance::{BinanceExecutor, BinanceOrderAction},
+    univ3::{DexFill, GasGuardError, UniV3ExactOutputAction, UniV3Executor, UniV3SwapAction},
+    hyperliquid::{
+        HedgeVenue, HlFill, HyperliquidExecutorError, HyperliquidOrderAction, TimeInForce,
+        HL_MIN_ORDER_VALUE_USD,
+    },
 };
-use crate::types::Executor;
+use crate::strategies::common;
+use crate::types::{Direction, Executor};
+use crate::utilities::alerts::Notifier;
+use crate::utilities::reconcile::InventoryDrift;
+use crate::utilities::retry::{with_retry, with_retry_guarded, RetryPolicy};
 
 /// Action for executing complete arbitrage (both legs)
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ArbitrageAction {
     pub dex_swap: UniV3SwapAction,
     pub hl_order: HyperliquidOrderAction,
-    pub direction: String,
+    pub direction: Direction,
+    /// DEX output (raw units) expected at the decision price
+    pub expected_dex_out: U256,
+    /// HL price the decision was made at, before the slippage buffer in `limit_px`
+    pub decision_hl_px: f64,
+    /// Expected net profit at decision time, after fees and gas
+    pub net_profit_bps: f64,
+    /// Expected profit in USD before DEX gas, the most gas the trade can pay
+    pub expected_gross_profit_usd: f64,
+    /// USD price of the chain's gas token, to value gas paid on the DEX leg
+    pub native_px_usd: f64,
+    /// DEX decimals of the hedged token, to express a hedge fill in raw DEX units
+    pub hype_decimals: u8,
+    /// Price tolerance the hedge was placed with; bounds the DEX input when the swap is
+    /// sized to the fill
+    pub slippage_bps: f64,
+    /// When the market event behind this decision was received, for latency logging
+    #[serde(skip)]
+    pub observed_at: Option<Instant>,
+}
+
+impl ArbitrageAction {
+    /// Split into `slices` equal child actions; the last one absorbs rounding remainders
+    pub fn split(&self, slices: u32) -> Vec<ArbitrageAction> {
+        let n = slices.max(1);
+        let part = |total: U256, i: u32| {
+            let each = total / U256::from(n);
+            if i + 1 == n { total - each * U256::from(n - 1) } else { each }
+        };
+
+        (0..n)
+            .map(|i| {
+                let mut slice = self.clone();
+                slice.dex_swap.amount_in = part(self.dex_swap.amount_in, i);
+                slice.dex_swap.amount_out_min = part(self.dex_swap.amount_out_min, i);
+                slice.expected_dex_out = part(self.expected_dex_out, i);
+                slice.hl_order.size = self.hl_order.size / n as f64;
+                slice.expected_gross_profit_usd = self.expected_gross_profit_usd / n as f64;
+                slice
+            })
+            .collect()
+    }
+
+    /// Copy sized to `fraction` (at most 1) of this round trip, e.g. to what the hedge filled
+    pub fn scaled(&self, fraction: f64) -> ArbitrageAction {
+        const PRECISION: f64 = 1e12;
+        let ratio = U256::from((fraction.clamp(0.0, 1.0) * PRECISION).round() as u64);
+        let scale = |amount: U256| amount * ratio / U256::from(PRECISION as u64);
+
+        let mut scaled = self.clone();
+        scaled.dex_swap.amount_in = scale(self.dex_swap.amount_in);
+        scaled.dex_swap.amount_out_min = scale(self.dex_swap.amount_out_min);
+        scaled.expected_dex_out = scale(self.expected_dex_out);
+        scaled.hl_order.size = self.hl_order.size * fraction;
+        scaled.expected_gross_profit_usd = self.expected_gross_profit_usd * fraction;
+        scaled
+    }
+
+    /// Round trip for a hedge that filled `filled_sz`: the DEX leg moves exactly that
+    /// much HYPE (output when buying it, input when selling) and other amounts scale with it
+    pub fn sized_to_fill(&self, filled_sz: f64) -> ArbitrageAction {
+        let filled_sz = filled_sz.min(self.hl_order.size);
+        let filled_raw = common::to_raw(filled_sz, self.hype_decimals);
+        let mut matched = self.scaled(filled_sz / self.hl_order.size);
+        matched.hl_order.size = filled_sz;
+        if matched.direction.buys_dex() {
+            matched.expected_dex_out = filled_raw;
+        } else {
+            matched.dex_swap.amount_in = filled_raw;
+        }
+        matched
+    }
+
+    /// Buy `expected_dex_out` exactly, spending at most `slippage_bps` over `amount_in`
+    pub fn exact_output(&self) -> UniV3ExactOutputAction {
+        UniV3ExactOutputAction::from_quote(
+            self.dex_swap.token_in,
+            self.dex_swap.token_out,
+            self.dex_swap.fee,
+            self.expected_dex_out,
+            self.dex_swap.amount_in,
+            self.slippage_bps.max(0.0).round() as u32,
+        )
+    }
+
+    fn notional_usd(&self) -> f64 {
+        self.hl_order.size * self.hl_order.limit_px
+    }
+}
+
+/// Time from the market event behind a trade to each leg's submission
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct LegLatency {
+    pub dex_submit_ms: f64,
+    /// DEX submission to HL submission; negative when a concurrent hedge landed first
+    pub hl_submit_ms: f64,
+    /// Until both legs were out
+    pub total_ms: f64,
+}
+
+impl LegLatency {
+    pub fn measure(observed_at: Instant, dex_submitted: Instant, hl_submitted: Instant) -> Self {
+        let dex_submit_ms = dex_submitted.duration_since(observed_at).as_secs_f64() * 1000.0;
+        let hl_total_ms = hl_submitted.duration_since(observed_at).as_secs_f64() * 1000.0;
+        Self {
+            dex_submit_ms,
+            hl_submit_ms: hl_total_ms - dex_submit_ms,
+            total_ms: dex_submit_ms.max(hl_total_ms),
+        }
+    }
+}
+
+/// Slice large round trips over time instead of sending them at once
+#[derive(Debug, Clone)]
+pub struct TwapConfig {
+    pub slices: u32,
+    /// Cut orders into clips of at most this notional, overriding `slices` when it needs more
+    pub max_clip_usd: Option<f64>,
+    /// Pause between slices
+    pub interval: Duration,
+    /// Orders below this notional still execute immediately
+    pub min_notional_usd: f64,
+    /// Remaining slices are abandoned once the live edge drops below this
+    pub min_edge_bps: f64,
+    pub edge: Arc<EdgeGauge>,
 }
 
-/// Composite executor that handles both DEX and HL legs sequentially
+impl TwapConfig {
+    /// Slice count for an order of `notional_usd`
+    pub fn slices_for(&self, notional_usd: f64) -> u32 {
+        let clips = match self.max_clip_usd {
+            Some(clip) if clip > 0.0 => (notional_usd / clip).ceil() as u32,
+            _ => 1,
+        };
+        self.slices.max(clips)
+    }
+}
+
+/// How a round trip's two legs are submitted
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SubmissionMode {
+    /// DEX first, hedge once the swap is sent: a failed DEX send never opens a hedge
+    #[default]
+    Sequential,
+    /// Both legs at once, shrinking the window between them; a failed DEX send can
+    /// no longer stop the hedge, which is then left open
+    Concurrent,
+    /// Hedge first, then size the DEX swap to what actually filled, so a partial IOC
+    /// leaves no residual; a failed swap leaves the filled hedge open
+    #[serde(rename = "hedge_first")]
+    HedgeFirst,
+}
+
+/// A direction reserved for one execution. `settle` restamps it with the submission
+/// time; if that never happens (skipped or failed), dropping the claim restores the stamp
+/// from before, so an attempt that didn't trade doesn't start a cooldown.
+struct DirectionClaim<'a> {
+    last_trade: &'a Mutex<HashMap<Direction, Instant>>,
+    direction: Direction,
+    claimed_at: Instant,
+    previous: Option<Instant>,
+}
+
+impl Drop for DirectionClaim<'_> {
+    fn drop(&mut self) {
+        let mut last_trade = self.last_trade.lock().unwrap();
+        if last_trade.get(&self.direction) == Some(&self.claimed_at) {
+            match self.previous {
+                Some(previous) => last_trade.insert(self.direction, previous),
+                None => last_trade.remove(&self.direction),
+            };
+        }
+    }
+}
+
+/// Composite executor that handles both DEX and HL legs
 pub struct ArbitrageExecutor<P> {
     dex_executor: UniV3Executor<P>,
-    hl_executor: HyperliquidExecutor,
+    hl_executor: Box<dyn HedgeVenue>,
+    /// Hedge on Binance instead of Hyperliquid
+    binance_executor: Option<BinanceExecutor>,
     exec_manager: Arc<ExecutionManager>,
     cooldown_secs: u64,
+    slippage_monitor: Option<Arc<SlippageMonitor>>,
+    /// Replaces `cooldown_secs`: only the direction that traded waits this long
+    direction_cooldown: Option<Duration>,
+    last_trade: Mutex<HashMap<Direction, Instant>>,
+    notifier: Option<Arc<dyn Notifier>>,
+    twap: Option<TwapConfig>,
+    position_tracker: Option<Arc<PositionTracker>>,
+    /// HL fee charged on the hedge, for PnL reporting (negative for a rebate)
+    hl_fee_bps: f64,
+    circuit_breaker: Option<Arc<CircuitBreaker>>,
+    /// Flatten the DEX leg when its hedge fails; None leaves the position one-sided
+    unwind_max_loss_bps: Option<f64>,
+    /// Set when an unwind fails; no further executions until restart
+    unwind_halted: Arc<AtomicBool>,
+    dex_retry: RetryPolicy,
+    hl_retry: RetryPolicy,
+    /// Re-read the live edge once the permit is held and skip if it fell below the minimum
+    edge_recheck: Option<(Arc<EdgeGauge>, f64)>,
+    submission_mode: SubmissionMode,
+    /// Stop opening trades once reconciliation finds holdings drifted past its halt level
+    inventory_drift: Option<Arc<InventoryDrift>>,
+    last_latency: Mutex<Option<LegLatency>>,
 }
 
 impl<P> ArbitrageExecutor<P> {
     pub fn new(
         dex_executor: UniV3Executor<P>,
-        hl_executor: HyperliquidExecutor,
+        hl_executor: impl HedgeVenue + 'static,
         exec_manager: Arc<ExecutionManager>,
         cooldown_secs: u64,
     ) -> Self {
         Self {
             dex_executor,
-            hl_executor,
+            hl_executor: Box::new(hl_executor),
+            binance_executor: None,
             exec_manager,
             cooldown_secs,
+            slippage_monitor: None,
+            direction_cooldown: None,
+            last_trade: Mutex::new(HashMap::new()),
+            notifier: None,
+            twap: None,
+            position_tracker: None,
+            hl_fee_bps: 2.0,
+            circuit_breaker: None,
+            unwind_max_loss_bps: None,
+            unwind_halted: Arc::new(AtomicBool::new(false)),
+            dex_retry: RetryPolicy::default(),
+            hl_retry: RetryPolicy::default(),
+            edge_recheck: None,
+            submission_mode: SubmissionMode::Sequential,
+            inventory_drift: None,
+            last_latency: Mutex::new(None),
         }
     }
+
+    /// Skip executions while `drift` reports a reconciliation halt
+    pub fn with_inventory_drift(mut self, drift: Arc<InventoryDrift>) -> Self {
+        self.inventory_drift = Some(drift);
+        self
+    }
+
+    /// Submit the legs one after the other (default) or concurrently
+    pub fn with_submission_mode(mut self, mode: SubmissionMode) -> Self {
+        self.submission_mode = mode;
+        self
+    }
+
+    /// On a failed hedge, retry it once as a reduce-only IOC, then swap the DEX output back
+    /// accepting at most `max_loss_bps` on the input. If that fails too, alert and halt.
+    /// Binance spot has no reduce-only orders, so there the retry is a plain IOC.
+    pub fn with_auto_unwind(mut self, max_loss_bps: f64) -> Self {
+        self.unwind_max_loss_bps = Some(max_loss_bps);
+        self
+    }
+
+    /// Retry DEX sends and HL orders that failed before reaching the venue (connect
+    /// errors, rate limits). A DEX resend first checks that the signer's nonce is unused.
+    /// The HL retry only applies in hedge-first mode, where no swap has been sent yet.
+    pub fn with_retries(mut self, dex_retry: RetryPolicy, hl_retry: RetryPolicy) -> Self {
+        self.dex_retry = dex_retry;
+        self.hl_retry = hl_retry;
+        self
+    }
+
+    /// Latency of the most recent round trip whose action carried its event time
+    pub fn last_latency(&self) -> Option<LegLatency> {
+        *self.last_latency.lock().unwrap()
+    }
+
+    /// Shared flag raised when an unwind fails, e.g. for health checks
+    pub fn unwind_halt_flag(&self) -> Arc<AtomicBool> {
+        self.unwind_halted.clone()
+    }
+
+    /// Place hedge legs on Binance; `hl_order.coin` must then be the Binance symbol
+    pub fn with_binance_hedge(mut self, binance_executor: BinanceExecutor) -> Self {
+        self.binance_executor = Some(binance_executor);
+        self
+    }
+
+    /// Push alerts on fills and leg failures
+    pub fn with_notifier(mut self, notifier: Arc<dyn Notifier>) -> Self {
+        self.notifier = Some(notifier);
+        self
+    }
+
+    fn notify(&self, msg: String) {
+        if let Some(notifier) = &self.notifier {
+            notifier.send(msg);
+        }
+    }
+
+    /// Cool off only the direction that traded, so the opposite direction can act
+    /// immediately; replaces the cooldown across all directions
+    pub fn with_direction_cooldown(mut self, cooldown_secs: u64) -> Self {
+        self.direction_cooldown = Some(Duration::from_secs(cooldown_secs));
+        self
+    }
+
+    /// Time left before this direction may trade again, if it is cooling down
+    pub fn direction_cooldown_remaining(&self, direction: Direction) -> Option<Duration> {
+        let cooldown = self.direction_cooldown?;
+        let last = *self.last_trade.lock().unwrap().get(&direction)?;
+        cooldown.checked_sub(last.elapsed()).filter(|d| !d.is_zero())
+    }
+
+    /// Check the direction cooldown and stamp the direction under one lock; the stamp
+    /// holds off other executions of it until the claim is dropped. Err is the time left.
+    fn claim_direction(&self, direction: Direction) -> Result<Option<DirectionClaim<'_>>, Duration> {
+        let Some(cooldown) = self.direction_cooldown else {
+            return Ok(None);
+        };
+        let mut last_trade = self.last_trade.lock().unwrap();
+        let previous = last_trade.get(&direction).copied();
+        if let Some(remaining) = previous.and_then(|last| cooldown.checked_sub(last.elapsed())).filter(|d| !d.is_zero()) {
+            return Err(remaining);
+        }
+        let claimed_at = Instant::now();
+        last_trade.insert(direction, claimed_at);
+        Ok(Some(DirectionClaim { last_trade: &self.last_trade, direction, claimed_at, previous }))
+    }
+
+    /// Skip actions whose edge, as last published by the strategy, has dropped below
+    /// `min_edge_bps` by the time they are about to execute
+    pub fn with_edge_recheck(mut self, edge: Arc<EdgeGauge>, min_edge_bps: f64) -> Self {
+        self.edge_recheck = Some((edge, min_edge_bps));
+        self
+    }
+
+    /// Execute large actions as a TWAP of child round trips.
+    /// Each slice must still clear HL's $10 minimum order value.
+    pub fn with_twap(mut self, twap: TwapConfig) -> Self {
+        self.twap = Some(twap);
+        self
+    }
+
+    /// Stop executing after repeated failures; share one breaker to count across executors
+    pub fn with_circuit_breaker(mut self, breaker: Arc<CircuitBreaker>) -> Self {
+        self.circuit_breaker = Some(breaker);
+        self
+    }
+
+    /// HL fee rate used when reporting PnL
+    pub fn with_hl_fee_bps(mut self, hl_fee_bps: f64) -> Self {
+        self.hl_fee_bps = hl_fee_bps;
+        self
+    }
+
+    /// Report each completed round trip's notional to `tracker`
+    pub fn with_position_tracker(mut self, tracker: Arc<PositionTracker>) -> Self {
+        self.position_tracker = Some(tracker);
+        self
+    }
+
+    /// Measure realized slippage after each round trip and halt on repeated breaches
+    pub fn with_slippage_monitor(mut self, monitor: Arc<SlippageMonitor>) -> Self {
+        self.slippage_monitor = Some(monitor);
+        self
+    }
 }
 
 #[async_trait]
 impl<P: Provider + 'static> Executor<ArbitrageAction> for ArbitrageExecutor<P> {
     async fn execute(&self, action: ArbitrageAction) -> Result<()> {
+        if let Some(remaining) = self.exec_manager.cooldown_remaining() {
+            info!(direction = %action.direction, reason = "cooldown",
+                "⏸️  Skipping {} - cooling down ({:.0}s left)", action.direction, remaining.as_secs_f64());
+            return Ok(());
+        }
+
         // Try to acquire execution permit
         let _permit = match self.exec_manager.try_start() {
             Some(p) => p,
             None => {
-                info!("⏸️  Skipping {} - execution already in progress", action.direction);
+                info!(direction = %action.direction, reason = "busy",
+                    "⏸️  Skipping {} - execution already in progress", action.direction);
                 return Ok(());
             }
         };
 
-        info!("🚀 {}", action.direction);
+        // Checked and claimed in one step under the permit, so two executions of the
+        // same direction can't both pass
+        let _claim = match self.claim_direction(action.direction) {
+            Ok(claim) => claim,
+            Err(remaining) => {
+                info!(direction = %action.direction, reason = "direction_cooldown",
+                    "⏸️  Skipping {} - direction cooling down ({:.0}s left)", action.direction, remaining.as_secs_f64());
+                return Ok(());
+            }
+        };
 
-        // Execute DEX leg
-        if let Err(e) = self.dex_executor.execute(action.dex_swap.clone()).await {
-            error!("DEX failed: {}", e);
-            return Err(e);
+        if self.unwind_halted.load(Ordering::SeqCst) {
+            warn!(direction = %action.direction, reason = "unwind_halt",
+                "🛑 Skipping {} - halted after a failed unwind", action.direction);
+            return Ok(());
         }
 
-        // Execute HL leg
-        if let Err(e) = self.hl_executor.execute(action.hl_order.clone()).await {
-            error!("HL failed: {} ⚠️ ONE-SIDED!", e);
+        if self.inventory_drift.as_ref().is_some_and(|drift| drift.is_halted()) {
+            warn!(direction = %action.direction, reason = "inventory_drift",
+                "🛑 Skipping {} - halted on inventory drift", action.direction);
+            return Ok(());
+        }
+
+        if let Some(monitor) = &self.slippage_monitor {
+            if monitor.is_halted() {
+                warn!(direction = %action.direction, reason = "slippage_halt",
+                    "🛑 Skipping {} - halted on realized slippage", action.direction);
+                return Ok(());
+            }
+        }
+
+        if let Some(breaker) = &self.circuit_breaker {
+            breaker.check()?;
+        }
+
+        if let Some((edge, min_edge_bps)) = &self.edge_recheck {
+            match edge.edge_bps(action.direction) {
+                Some(bps) if bps >= *min_edge_bps => {}
+                bps => {
+                    info!(direction = %action.direction, reason = "stale", edge_bps = bps.unwrap_or(f64::NAN),
+                        "⏸️  Skipping {} - stale opportunity, edge now {:.2} bps < {} bps",
+                        action.direction, bps.unwrap_or(f64::NAN), min_edge_bps);
+                    return Ok(());
+                }
+            }
+        }
+
+        if let Err(e) = self.dex_executor.check_gas(Some(action.expected_gross_profit_usd), action.native_px_usd).await {
+            if let Some(guard) = e.downcast_ref::<GasGuardError>() {
+                info!(direction = %action.direction, reason = "gas",
+                    "⏸️  Skipping {} - {}", action.direction, guard);
+                return Ok(());
+            }
             return Err(e);
         }
 
+        let result = match &self.twap {
+            Some(twap) if twap.slices_for(action.notional_usd()) > 1 && action.notional_usd() >= twap.min_notional_usd => {
+                self.execute_twap(&action, twap).await
+            }
+            _ => self.execute_round_trip(&action).await,
+        };
+
+        if let Some(breaker) = &self.circuit_breaker {
+            match &result {
+                Ok(()) => breaker.record_success(),
+                Err(_) if breaker.record_failure() => {
+                    error!("🚨 Circuit breaker tripped after repeated execution failures - halting all executions");
+                    self.notify("🚨 Circuit breaker tripped after repeated execution failures".to_string());
+                }
+                Err(_) => {}
+            }
+        }
+        result?;
+
+        // Cooldown is gated in `try_start` (or per direction); the permit auto-releases here via Drop
+        if self.direction_cooldown.is_none() {
+            self.exec_manager.start_cooldown(Duration::from_secs(self.cooldown_secs));
+        }
+        Ok(())
+    }
+}
+
+impl<P: Provider + 'static> ArbitrageExecutor<P> {
+    /// Send both legs of one round trip
+    async fn execute_round_trip(&self, action: &ArbitrageAction) -> Result<()> {
+        // Refuse before the DEX leg commits capital to a hedge HL would reject
+        let hedge_value = action.hl_order.size * action.hl_order.limit_px;
+        if self.binance_executor.is_none() && hedge_value < HL_MIN_ORDER_VALUE_USD {
+            anyhow::bail!("Hedge value ${:.2} below HL minimum, not sending {}", hedge_value, action.direction);
+        }
+
+        info!(
+            direction = %action.direction,
+            net_profit_bps = action.net_profit_bps,
+            size = action.hl_order.size,
+            notional_usd = action.notional_usd(),
+            "🚀 {}", action.direction
+        );
+        
+        let dex_leg = async {
+            let send = || self.dex_executor.send_swap(action.dex_swap.clone(), Some(action.expected_dex_out));
+            (self.send_dex_with_retry(send).await, Instant::now())
+        };
+        // Not retried: the swap is already out (or on its way) by the time a retry would run
+        let hl_leg = async {
+            (self.place_hedge(&action.hl_order).await, Instant::now())
+        };
+
+        let (tx_hash, dex_submitted, hl_result, hl_submitted) = match self.submission_mode {
+            SubmissionMode::HedgeFirst => return self.execute_hedge_first(action).await,
+            SubmissionMode::Sequential => {
+                let (dex_result, dex_submitted) = dex_leg.await;
+                let tx_hash = match dex_result {
+                    Ok(tx_hash) => tx_hash,
+                    Err(e) => {
+                        error!(direction = %action.direction, leg = "dex", error = %e, "DEX failed: {}", e);
+                        self.notify(format!("❌ {} DEX leg failed: {}", action.direction, e));
+                        return Err(e);
+                    }
+                };
+                let (hl_result, hl_submitted) = hl_leg.await;
+                (tx_hash, dex_submitted, hl_result, hl_submitted)
+            }
+            SubmissionMode::Concurrent => {
+                let ((dex_result, dex_submitted), (hl_result, hl_submitted)) = tokio::join!(dex_leg, hl_leg);
+                let tx_hash = match dex_result {
+                    Ok(tx_hash) => tx_hash,
+                    Err(e) => {
+                        match hl_result {
+                            Ok(_) => {
+                                error!(direction = %action.direction, leg = "dex", error = %e, "DEX failed: {} ⚠️ ONE-SIDED!", e);
+                                self.notify(format!("⚠️ ONE-SIDED! {} HL leg sent but DEX failed: {}", action.direction, e));
+                            }
+                            Err(hl_error) => {
+                                error!(direction = %action.direction, leg = "both", error = %e, "DEX failed: {} | HL failed: {}", e, hl_error);
+                                self.notify(format!("❌ {} both legs failed: {} | {}", action.direction, e, hl_error));
+                            }
+                        }
+                        return Err(e);
+                    }
+                };
+                (tx_hash, dex_submitted, hl_result, hl_submitted)
+            }
+        };
+
+        self.complete_hedge(action, tx_hash, dex_submitted, hl_result, hl_submitted).await
+    }
+
+    /// The DEX leg is out: settle once the hedge went through, else unwind (when enabled)
+    /// or report the position one-sided
+    async fn complete_hedge(
+        &self,
+        action: &ArbitrageAction,
+        tx_hash: TxHash,
+        dex_submitted: Instant,
+        hl_result: Result<Option<HlFill>>,
+        hl_submitted: Instant,
+    ) -> Result<()> {
+        let hl_fill = match hl_result {
+            Ok(fill) => fill,
+            Err(e) => {
+                error!(direction = %action.direction, leg = "hl", error = %e, "HL failed: {} ⚠️ ONE-SIDED!", e);
+                if let Some(max_loss_bps) = self.unwind_max_loss_bps {
+                    return self.unwind(action, tx_hash, dex_submitted, max_loss_bps, e).await;
+                }
+                self.notify(format!("⚠️ ONE-SIDED! {} DEX leg filled but HL failed: {}", action.direction, e));
+                return Err(e);
+            }
+        };
+        self.settle(action, tx_hash, dex_submitted, hl_submitted, hl_fill).await
+    }
+
+    /// Hedge first, then swap exactly the filled quantity on the DEX: exact output when
+    /// buying HYPE there (spending at most `slippage_bps` over the scaled quote), exact
+    /// input of the filled size when selling it
+    async fn execute_hedge_first(&self, action: &ArbitrageAction) -> Result<()> {
+        let hl_result = with_retry(&self.hl_retry, "HL order", || self.place_hedge(&action.hl_order)).await;
+        let hl_submitted = Instant::now();
+        let hl_fill = match hl_result {
+            Ok(Some(fill)) => fill,
+            Ok(None) => {
+                warn!(direction = %action.direction, leg = "hl", "HL reported no fill - DEX leg not sent");
+                self.notify(format!("⚠️ {} hedge reported no fill, DEX leg not sent; check for a resting HL order", action.direction));
+                anyhow::bail!("{} hedge reported no fill", action.direction);
+            }
+            Err(e) => {
+                error!(direction = %action.direction, leg = "hl", error = %e, "HL failed: {}", e);
+                self.notify(format!("❌ {} HL leg failed: {}", action.direction, e));
+                return Err(e);
+            }
+        };
+
+        let filled = hl_fill.total_sz.min(action.hl_order.size);
+        if filled < action.hl_order.size {
+            info!(direction = %action.direction, "↔️  Hedge filled {} of {} - sizing DEX leg to match",
+                hl_fill.total_sz, action.hl_order.size);
+        }
+        let matched = action.sized_to_fill(filled);
+
+        let dex_result = if matched.direction.buys_dex() {
+            let exact = matched.exact_output();
+            self.send_dex_with_retry(|| self.dex_executor.send_exact_output(&exact)).await
+        } else {
+            self.send_dex_with_retry(|| self.dex_executor.send_swap(matched.dex_swap.clone(), Some(matched.expected_dex_out))).await
+        };
+        let dex_submitted = Instant::now();
+        let tx_hash = match dex_result {
+            Ok(tx_hash) => tx_hash,
+            Err(e) => {
+                error!(direction = %action.direction, leg = "dex", error = %e, "DEX failed: {} ⚠️ ONE-SIDED!", e);
+                self.notify(format!("⚠️ ONE-SIDED! {} HL leg filled but DEX failed: {}", action.direction, e));
+                return Err(e);
+            }
+        };
+
+        self.settle(&matched, tx_hash, dex_submitted, hl_submitted, Some(hl_fill)).await
+    }
+
+    /// Send the DEX leg under the DEX retry policy. Before a resend the signer's pending
+    /// nonce is re-read: if it moved past the nonce seen before the first attempt, that
+    /// attempt was broadcast despite the error and is not sent again.
+    async fn send_dex_with_retry<F, Fut>(&self, send: F) -> Result<TxHash>
+    where
+        F: FnMut() -> Fut,
+        Fut: std::future::Future<Output = Result<TxHash>>,
+    {
+        if self.dex_retry.max_retries == 0 {
+            return with_retry(&self.dex_retry, "DEX send", send).await;
+        }
+        let nonce = self.dex_executor.pending_nonce().await?;
+        let unused = || async move {
+            match self.dex_executor.pending_nonce().await {
+                Ok(pending) if pending > nonce => {
+                    error!("DEX send errored but nonce {} was taken - the swap may be live", nonce);
+                    false
+                }
+                Ok(_) => true,
+                Err(e) => {
+                    warn!("DEX nonce check failed: {}", e);
+                    false
+                }
+            }
+        };
+        with_retry_guarded(&self.dex_retry, "DEX send", send, unused).await
+    }
+
+    /// Log latency, read the swap receipt, record PnL and check realized slippage once
+    /// both legs are out
+    async fn settle(
+        &self,
+        action: &ArbitrageAction,
+        tx_hash: TxHash,
+        dex_submitted: Instant,
+        hl_submitted: Instant,
+        hl_fill: Option<HlFill>,
+    ) -> Result<()> {
+        let legs_submitted = dex_submitted.max(hl_submitted);
+
+        if let Some(observed_at) = action.observed_at {
+            let latency = LegLatency::measure(observed_at, dex_submitted, hl_submitted);
+            info!(
+                direction = %action.direction,
+                dex_submit_ms = latency.dex_submit_ms,
+                hl_submit_ms = latency.hl_submit_ms,
+                total_ms = latency.total_ms,
+                "⏱️  Event→DEX {:.0}ms | DEX→HL {:.0}ms | total {:.0}ms",
+                latency.dex_submit_ms, latency.hl_submit_ms, latency.total_ms
+            );
+            *self.last_latency.lock().unwrap() = Some(latency);
+        }
+
+        self.last_trade.lock().unwrap().insert(action.direction, legs_submitted);
+
+        // Read what the swap actually did (output, gas) from its receipt
+        let dex_fill = match self.dex_executor.wait_for_fill(tx_hash, &action.dex_swap).await {
+            Ok(fill) => Some(fill),
+            Err(e) => {
+                warn!("DEX receipt unavailable: {}", e);
+                None
+            }
+        };
+
+        if let (Some(drift), Some(fill)) = (&self.inventory_drift, &dex_fill) {
+            drift.record_gas(fill.gas_used as f64 * fill.effective_gas_price as f64 / 1e18);
+        }
+
         // Log PnL
-        Self::log_pnl(&action);
+        let (trade_size, total_fees) = self.log_pnl(action, dex_fill.as_ref(), hl_fill.as_ref());
+        if let Some(tracker) = &self.position_tracker {
+            tracker.record_fill(trade_size);
+        }
+        self.notify(format!("✅ {} filled | Size ${:.1} | Fees ${:.2} | Expected net {:+.2} bps",
+            action.direction, trade_size, total_fees, action.net_profit_bps));
+
+        if let Some(monitor) = &self.slippage_monitor {
+            Self::check_realized_slippage(monitor, action, dex_fill.as_ref(), hl_fill.as_ref());
+        }
+
+        Ok(())
+    }
+
+    /// Flatten a DEX leg whose hedge failed. The hedge is retried once as a reduce-only IOC,
+    /// which can close HL inventory but never open new exposure; if it goes through the round
+    /// trip settles as usual. Otherwise the DEX leg is swapped back and the hedge error
+    /// returned, since the round trip failed even if exposure was closed.
+    async fn unwind(
+        &self,
+        action: &ArbitrageAction,
+        tx_hash: TxHash,
+        dex_submitted: Instant,
+        max_loss_bps: f64,
+        hedge_error: anyhow::Error,
+    ) -> Result<()> {
+        let retry = HyperliquidOrderAction { tif: TimeInForce::Ioc, reduce_only: true, ..action.hl_order.clone() };
+        match self.place_hedge(&retry).await {
+            Ok(hl_fill) => {
+                warn!(direction = %action.direction, "↩️  Reduce-only hedge retry went through");
+                self.notify(format!("⚠️ {} hedge failed, reduce-only retry went through: {}", action.direction, hedge_error));
+                return self.settle(action, tx_hash, dex_submitted, Instant::now(), hl_fill).await;
+            }
+            Err(e) => warn!(direction = %action.direction, "↩️  Reduce-only hedge retry failed: {} - reversing DEX leg", e),
+        }
 
-        // Cooldown
-        tokio::time::sleep(tokio::time::Duration::from_secs(self.cooldown_secs)).await;
+        let result: Result<()> = async {
+            let fill = self.dex_executor.wait_for_fill(tx_hash, &action.dex_swap).await?;
+            if !fill.success {
+                // Reverted swap: nothing to flatten
+                return Ok(());
+            }
+            let keep_bps = 10_000u64.saturating_sub(max_loss_bps.max(0.0).round() as u64);
+            let reverse = UniV3SwapAction {
+                token_in: action.dex_swap.token_out,
+                token_out: action.dex_swap.token_in,
+                fee: action.dex_swap.fee,
+                amount_in: fill.amount_out,
+                amount_out_min: action.dex_swap.amount_in * U256::from(keep_bps) / U256::from(10_000u64),
+            };
+            let unwind_hash = self.dex_executor.send_swap(reverse.clone(), None).await?;
+            let unwound = self.dex_executor.wait_for_fill(unwind_hash, &reverse).await?;
+            if !unwound.success {
+                anyhow::bail!("unwind swap 0x{:x} reverted", unwind_hash);
+            }
+            Ok(())
+        }.await;
+
+        match result {
+            Ok(()) => {
+                warn!(direction = %action.direction, "↩️  DEX leg unwound");
+                self.notify(format!("↩️ {} HL leg failed, DEX leg unwound: {}", action.direction, hedge_error));
+            }
+            Err(e) => {
+                self.unwind_halted.store(true, Ordering::SeqCst);
+                error!(direction = %action.direction, error = %e, "🚨 Unwind failed: {} - halting new trades", e);
+                self.notify(format!("🚨 ONE-SIDED! {} unwind failed: {} - trading halted", action.direction, e));
+            }
+        }
+        Err(hedge_error)
+    }
+
+    /// Place the HL leg; a post-only hedge that would cross is retried as IOC so the
+    /// DEX leg is not left unhedged
+    async fn place_hedge(&self, order: &HyperliquidOrderAction) -> Result<Option<HlFill>> {
+        if let Some(binance) = &self.binance_executor {
+            return binance.place_order(BinanceOrderAction::from(order)).await;
+        }
+        match self.hl_executor.place_order(order.clone()).await {
+            Err(e) if matches!(
+                e.downcast_ref::<HyperliquidExecutorError>(),
+                Some(HyperliquidExecutorError::PostOnlyWouldCross { .. })
+            ) => {
+                warn!("HL: {} - retrying as IOC", e);
+                let taker = HyperliquidOrderAction { tif: TimeInForce::Ioc, ..order.clone() };
+                self.hl_executor.place_order(taker).await
+            }
+            result => result,
+        }
+    }
+
+    /// Run the sliced round trips in order, stopping early if the edge is gone
+    async fn execute_twap(&self, action: &ArbitrageAction, twap: &TwapConfig) -> Result<()> {
+        // Clips run inside this one execution, so the manager sees a single opportunity
+        let slices = action.split(twap.slices_for(action.notional_usd()));
+        let total = slices.len();
+        info!("🧩 TWAP {}: {} slices every {:.1}s", action.direction, total, twap.interval.as_secs_f64());
 
-        // Permit auto-releases here via Drop
+        for (i, slice) in slices.iter().enumerate() {
+            if i > 0 {
+                tokio::time::sleep(twap.interval).await;
+                match twap.edge.edge_bps(action.direction) {
+                    Some(edge) if edge >= twap.min_edge_bps => {}
+                    edge => {
+                        warn!("🧩 TWAP {}: edge {:.2} bps below {} bps, abandoning {} of {} slices",
+                            action.direction, edge.unwrap_or(f64::NAN), twap.min_edge_bps, total - i, total);
+                        return Ok(());
+                    }
+                }
+            }
+            info!("🧩 Slice {}/{}", i + 1, total);
+            self.execute_round_trip(slice).await?;
+        }
         Ok(())
     }
 }
 
 impl<P> ArbitrageExecutor<P> {
-    fn log_pnl(action: &ArbitrageAction) {
-        let trade_size = action.hl_order.size * action.hl_order.limit_px;
-        let dex_fee = trade_size * 0.003;
-        let hl_fee = trade_size * 0.0002;
-        let gas = 0.50;
+    fn check_realized_slippage(
+        monitor: &SlippageMonitor,
+        action: &ArbitrageAction,
+        dex_fill: Option<&DexFill>,
+        hl_fill: Option<&HlFill>,
+    ) {
+        let Some(dex_fill) = dex_fill else {
+            warn!("Slippage check skipped: no DEX receipt");
+            return;
+        };
+        let Some(hl_fill) = hl_fill else {
+            warn!("Slippage check skipped: no HL fill reported");
+            return;
+        };
+
+        let slippage_bps = Self::realized_slippage_bps(action, dex_fill, hl_fill);
+        info!(direction = %action.direction, slippage_bps, "📐 Realized slippage: {:+.2} bps", slippage_bps);
+
+        if monitor.record(slippage_bps) {
+            error!("🛑 Realized slippage limit breached repeatedly - halting new trades");
+        }
+    }
+
+    /// Adverse slippage across both legs in bps (positive = worse than decided)
+    fn realized_slippage_bps(action: &ArbitrageAction, dex_fill: &DexFill, hl_fill: &HlFill) -> f64 {
+        let expected_out = action.expected_dex_out.to_string().parse::<f64>().unwrap_or(0.0);
+        let actual_out = dex_fill.amount_out.to_string().parse::<f64>().unwrap_or(0.0);
+        let dex_bps = if expected_out > 0.0 {
+            (expected_out - actual_out) / expected_out * 10000.0
+        } else {
+            0.0
+        };
+
+        let side = if action.hl_order.is_buy { 1.0 } else { -1.0 };
+        let hl_bps = side * (hl_fill.avg_px - action.decision_hl_px) / action.decision_hl_px * 10000.0;
+
+        dex_bps + hl_bps
+    }
+
+    /// Log size and fee breakdown from the fills where available; returns
+    /// (trade size, total fees) in USD
+    fn log_pnl(&self, action: &ArbitrageAction, dex_fill: Option<&DexFill>, hl_fill: Option<&HlFill>) -> (f64, f64) {
+        let trade_size = hl_fill
+            .map(|fill| fill.total_sz * fill.avg_px)
+            .unwrap_or(action.hl_order.size * action.hl_order.limit_px);
+        // Pool fee tiers are in hundredths of a bip
+        let dex_fee = trade_size * action.dex_swap.fee as f64 / 1_000_000.0;
+        let hl_fee = trade_size * self.hl_fee_bps / 10000.0;
+        let gas = dex_fill
+            .map(|fill| fill.gas_used as f64 * fill.effective_gas_price as f64 / 1e18 * action.native_px_usd)
+            .unwrap_or(0.0);
         let total_fees = dex_fee + hl_fee + gas;
         
-        info!("💰 Size: ${:.1} | Fees: ${:.2} (DEX ${:.2} + HL ${:.2} + Gas ${:.2})",
-            trade_size, total_fees, dex_fee, hl_fee, gas);
+        info!(
+            direction = %action.direction,
+            trade_size,
+            dex_fee,
+            hl_fee,
+            gas,
+            total_fees,
+            "💰 Size: ${:.1} | Fees: ${:.2} (DEX ${:.2} + HL ${:.2} + Gas ${:.2})",
+            trade_size, total_fees, dex_fee, hl_fee, gas
+        );
+
+        (trade_size, total_fees)
     }
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::Address;
+    use alloy::transports::mock::Asserter;
+    use crate::executors::hyperliquid::{HyperliquidExecutor, OrderMode};
+
+    fn action() -> ArbitrageAction {
+        ArbitrageAction {
+            dex_swap: UniV3SwapAction {
+                token_in: Address::repeat_byte(0x11),
+                token_out: Address::repeat_byte(0x55),
+                fee: 3000,
+                amount_in: U256::from(100_000_000u64),
+                amount_out_min: U256::from(2_000_000_000_000_000_000u128),
+            },
+            hl_order: HyperliquidOrderAction {
+                coin: "HYPE/USDC".to_string(),
+                is_buy: false,
+                size: 2.5,
+                limit_px: 39.8,
+                tif: TimeInForce::Ioc,
+                mode: OrderMode::MarketIoc { max_slippage_bps: 20.0 },
+                reduce_only: false,
+            },
+            direction: Direction::BuyDexSellCex,
+            expected_dex_out: U256::from(2_500_000_000_000_000_000u128),
+            decision_hl_px: 40.0,
+            net_profit_bps: 12.5,
+            expected_gross_profit_usd: 0.2,
+            native_px_usd: 40.0,
+            hype_decimals: 18,
+            slippage_bps: 50.0,
+            observed_at: Some(Instant::now()),
+        }
+    }
+
+    #[test]
+    fn dex_leg_matches_partial_hedge_fill() {
+        // Hedge sold 1.2 of 2.5 HYPE: buy exactly 1.2 on the DEX
+        let matched = action().sized_to_fill(1.2);
+        assert_eq!(matched.expected_dex_out, U256::from(1_200_000_000_000_000_000u128));
+        assert_eq!(matched.hl_order.size, 1.2);
+        let exact = matched.exact_output();
+        assert_eq!(exact.amount_out, matched.expected_dex_out);
+        // 48% of the $100 input, plus 50 bps
+        assert_eq!(matched.dex_swap.amount_in, U256::from(48_000_000u64));
+        assert_eq!(exact.amount_in_max, U256::from(48_240_000u64));
+
+        // Selling on the DEX after buying the hedge: exact input of the filled size
+        let mut sell = action();
+        sell.direction = Direction::BuyCexSellDex;
+        let matched = sell.sized_to_fill(3.0);
+        assert_eq!(matched.dex_swap.amount_in, U256::from(2_500_000_000_000_000_000u128));
+    }
+
+    #[test]
+    fn large_order_is_cut_into_max_clip_usd_pieces() {
+        let twap = TwapConfig {
+            slices: 1,
+            max_clip_usd: Some(250.0),
+            interval: Duration::from_millis(500),
+            min_notional_usd: 0.0,
+            min_edge_bps: 5.0,
+            edge: Arc::new(EdgeGauge::new()),
+        };
+        // 25 HYPE at $40 is a $1000 order
+        let mut order = action();
+        order.hl_order.size = 25.0;
+        order.hl_order.limit_px = 40.0;
+        let slices = twap.slices_for(order.notional_usd());
+        assert_eq!(slices, 4);
+
+        let clips = order.split(slices);
+        assert_eq!(clips.len(), 4);
+        for clip in &clips {
+            assert!((clip.notional_usd() - 250.0).abs() < 1e-9, "{}", clip.notional_usd());
+        }
+        // The clips add back up to the parent exactly
+        let total_in = clips.iter().fold(U256::ZERO, |sum, clip| sum + clip.dex_swap.amount_in);
+        assert_eq!(total_in, order.dex_swap.amount_in);
+        // An order within the clip still goes out whole
+        assert_eq!(twap.slices_for(250.0), 1);
+    }
+
+    // Well-known dev key; nothing is sent
+    const DEV_KEY: &str = "0x59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d";
+
+    fn executor() -> ArbitrageExecutor<impl Provider> {
+        let provider = alloy::providers::ProviderBuilder::new().connect_http("http://127.0.0.1:8545".parse().unwrap());
+        let dex = UniV3Executor::new(Arc::new(provider), DEV_KEY, Address::repeat_byte(0x77)).unwrap();
+        let hl = HyperliquidExecutor::new(DEV_KEY.to_string()).unwrap();
+        ArbitrageExecutor::new(dex, hl, Arc::new(ExecutionManager::new(1)), 15)
+    }
+
+    #[test]
+    fn direction_cooldown_leaves_the_other_direction_free() {
+        let executor = executor().with_direction_cooldown(60);
+        executor.last_trade.lock().unwrap().insert(Direction::BuyDexSellCex, Instant::now());
+
+        let remaining = executor.direction_cooldown_remaining(Direction::BuyDexSellCex).unwrap();
+        assert!(remaining > Duration::from_secs(59) && remaining <= Duration::from_secs(60), "{:?}", remaining);
+        assert!(executor.direction_cooldown_remaining(Direction::BuyCexSellDex).is_none());
+
+        // Once the cooldown has passed the direction is free again
+        let long_ago = Instant::now() - Duration::from_secs(61);
+        executor.last_trade.lock().unwrap().insert(Direction::BuyDexSellCex, long_ago);
+        assert!(executor.direction_cooldown_remaining(Direction::BuyDexSellCex).is_none());
+    }
+
+    #[test]
+    fn back_to_back_directions_do_not_block_each_other() {
+        let executor = executor().with_direction_cooldown(60);
+        // Both directions trade within the same instant; each only gates itself
+        executor.last_trade.lock().unwrap().insert(Direction::BuyDexSellCex, Instant::now());
+        assert!(executor.direction_cooldown_remaining(Direction::BuyCexSellDex).is_none());
+        executor.last_trade.lock().unwrap().insert(Direction::BuyCexSellDex, Instant::now());
+        assert!(executor.direction_cooldown_remaining(Direction::BuyDexSellCex).is_some());
+        assert!(executor.direction_cooldown_remaining(Direction::BuyCexSellDex).is_some());
+    }
+
+    #[test]
+    fn without_direction_cooldown_no_direction_is_gated() {
+        // cooldown_secs then applies to both directions through the execution manager
+        let executor = executor();
+        executor.last_trade.lock().unwrap().insert(Direction::BuyDexSellCex, Instant::now());
+        assert!(executor.direction_cooldown_remaining(Direction::BuyDexSellCex).is_none());
+    }
+
+    #[test]
+    fn action_round_trips_through_json() {
+        let json = serde_json::to_string(&action()).unwrap();
+        let back: ArbitrageAction = serde_json::from_str(&json).unwrap();
+        assert_eq!(serde_json::to_string(&back).unwrap(), json);
+        assert_eq!(back.direction, Direction::BuyDexSellCex);
+        assert_eq!(back.expected_dex_out, action().expected_dex_out);
+        // Receive time is process-local and not recorded
+        assert!(back.observed_at.is_none());
+    }
+
+    /// Hedge venue that rejects every opening order and, if `fill_reduce_only`, fills
+    /// reduce-only ones in full
+    struct MockHedge {
+        fill_reduce_only: bool,
+        orders: Arc<Mutex<Vec<HyperliquidOrderAction>>>,
+    }
+
+    #[async_trait]
+    impl HedgeVenue for MockHedge {
+        async fn place_order(&self, action: HyperliquidOrderAction) -> Result<Option<HlFill>> {
+            self.orders.lock().unwrap().push(action.clone());
+            if action.reduce_only && self.fill_reduce_only {
+                return Ok(Some(HlFill { oid: 1, total_sz: action.size, avg_px: action.limit_px }));
+            }
+            anyhow::bail!("HL rejected {} order: insufficient margin", action.coin)
+        }
+    }
+
+    /// Auto-unwinding executor whose DEX calls all fail: no RPC responses are queued
+    fn unwinding_executor(fill_reduce_only: bool) -> (ArbitrageExecutor<impl Provider>, Arc<Mutex<Vec<HyperliquidOrderAction>>>) {
+        let orders = Arc::new(Mutex::new(Vec::new()));
+        let provider = alloy::providers::ProviderBuilder::new().connect_mocked_client(Asserter::new());
+        let dex = UniV3Executor::new(Arc::new(provider), DEV_KEY, Address::repeat_byte(0x77)).unwrap();
+        let hedge = MockHedge { fill_reduce_only, orders: orders.clone() };
+        let executor = ArbitrageExecutor::new(dex, hedge, Arc::new(ExecutionManager::new(1)), 15).with_auto_unwind(50.0);
+        (executor, orders)
+    }
+
+    #[tokio::test]
+    async fn failed_hedge_settles_through_a_reduce_only_retry() {
+        let (executor, orders) = unwinding_executor(true);
+        let tracker = Arc::new(PositionTracker::new(Duration::from_secs(3600)));
+        let executor = executor.with_position_tracker(tracker.clone());
+        let action = action();
+
+        let hl_result = executor.place_hedge(&action.hl_order).await;
+        assert!(hl_result.is_err());
+        let result = executor.complete_hedge(&action, TxHash::ZERO, Instant::now(), hl_result, Instant::now()).await;
+        assert!(result.is_ok(), "{:?}", result);
+
+        let orders = orders.lock().unwrap();
+        assert_eq!(orders.len(), 2);
+        assert!(!orders[0].reduce_only);
+        assert!(orders[1].reduce_only);
+        assert_eq!(orders[1].tif, TimeInForce::Ioc);
+        // Settled like any round trip
+        assert!((tracker.notional_in_window() - 2.5 * 39.8).abs() < 1e-9);
+        assert!(executor.last_trade.lock().unwrap().contains_key(&action.direction));
+        assert!(!executor.unwind_halt_flag().load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn failed_unwind_halts_trading() {
+        let (executor, orders) = unwinding_executor(false);
+        let action = action();
+
+        let hl_result = executor.place_hedge(&action.hl_order).await;
+        let result = executor.complete_hedge(&action, TxHash::ZERO, Instant::now(), hl_result, Instant::now()).await;
+        // The hedge error is returned; reversing the DEX leg can't read its receipt
+        assert!(result.unwrap_err().to_string().contains("insufficient margin"));
+        assert_eq!(orders.lock().unwrap().len(), 2);
+        assert!(executor.unwind_halt_flag().load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn direction_is_claimed_atomically() {
+        let executor = executor().with_direction_cooldown(60);
+        let claim = executor.claim_direction(Direction::BuyDexSellCex).unwrap();
+        assert!(claim.is_some());
+        // A second execution of the same direction is held off while the first runs
+        assert!(executor.claim_direction(Direction::BuyDexSellCex).is_err());
+        assert!(executor.claim_direction(Direction::BuyCexSellDex).unwrap().is_some());
+
+        // Dropped without settling: the direction is free again
+        drop(claim);
+        assert!(executor.direction_cooldown_remaining(Direction::BuyDexSellCex).is_none());
+
+        // Settled: the submission stamp stays and the direction cools down
+        let claim = executor.claim_direction(Direction::BuyDexSellCex).unwrap();
+        executor.last_trade.lock().unwrap().insert(Direction::BuyDexSellCex, Instant::now() + Duration::from_millis(1));
+        drop(claim);
+        assert!(executor.claim_direction(Direction::BuyDexSellCex).is_err());
+    }
+
+    #[test]
+    fn without_direction_cooldown_nothing_is_claimed() {
+        let executor = executor();
+        assert!(executor.claim_direction(Direction::BuyDexSellCex).unwrap().is_none());
+        assert!(executor.claim_direction(Direction::BuyDexSellCex).unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn hedge_below_the_hl_minimum_sends_no_swap() {
+        let (executor, orders) = unwinding_executor(true);
+        // 0.1 HYPE at $39.8 is a $3.98 hedge
+        let mut tiny = action();
+        tiny.hl_order.size = 0.1;
+
+        let err = executor.execute_round_trip(&tiny).await.unwrap_err();
+        // Refused up front: a swap attempt would have failed on the empty mock transport
+        assert!(err.to_string().contains("below HL minimum"), "{}", err);
+        assert!(orders.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn swap_is_skipped_above_the_gas_cap() {
+        let asserter = Asserter::new();
+        // 100 gwei against a 50 gwei cap; nothing else is queued, so any swap call would fail
+        asserter.push_success(&"0x174876e800");
+        let provider = alloy::providers::ProviderBuilder::new().connect_mocked_client(asserter);
+        let dex = UniV3Executor::new(Arc::new(provider), DEV_KEY, Address::repeat_byte(0x77)).unwrap().with_max_gas_price(50.0);
+        let orders = Arc::new(Mutex::new(Vec::new()));
+        let hedge = MockHedge { fill_reduce_only: true, orders: orders.clone() };
+        let executor = ArbitrageExecutor::new(dex, hedge, Arc::new(ExecutionManager::new(1)), 15);
+
+        executor.execute(action()).await.unwrap();
+        assert!(orders.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn trade_is_skipped_once_its_edge_is_gone() {
+        let edge = Arc::new(EdgeGauge::new());
+        let (executor, orders) = unwinding_executor(true);
+        let executor = executor.with_edge_recheck(edge.clone(), 10.0);
+
+        // Detected at 12.5 bps; by execution time the strategy quotes 3 bps
+        edge.update(3.0, 25.0);
+        executor.execute(action()).await.unwrap();
+        assert!(orders.lock().unwrap().is_empty());
+
+        // With the edge still there the swap goes out, and fails on the empty mock node
+        edge.update(15.0, 0.0);
+        assert!(executor.execute(action()).await.is_err());
+    }
+
+    /// Hedge venue that fills every order in full, then optionally quotes the edge at
+    /// `edge_after_fill`, as if the fill had moved the market
+    struct FillingHedge {
+        orders: Arc<Mutex<Vec<HyperliquidOrderAction>>>,
+        edge_after_fill: Option<(Arc<EdgeGauge>, f64)>,
+    }
+
+    #[async_trait]
+    impl HedgeVenue for FillingHedge {
+        async fn place_order(&self, action: HyperliquidOrderAction) -> Result<Option<HlFill>> {
+            self.orders.lock().unwrap().push(action.clone());
+            if let Some((edge, bps)) = &self.edge_after_fill {
+                edge.update(*bps, *bps);
+            }
+            Ok(Some(HlFill { oid: 1, total_sz: action.size, avg_px: action.limit_px }))
+        }
+    }
+
+    /// Three-slice TWAP executor whose node accepts `swaps` swaps, each without a receipt
+    fn twap_executor(swaps: usize, edge_after_fill: Option<f64>) -> (ArbitrageExecutor<impl Provider>, Arc<Mutex<Vec<HyperliquidOrderAction>>>) {
+        let asserter = Asserter::new();
+        for _ in 0..swaps {
+            asserter.push_success(&format!("0x{}", "ab".repeat(32)));
+            asserter.push_failure_msg("receipt not found");
+        }
+        let provider = alloy::providers::ProviderBuilder::new().disable_recommended_fillers().connect_mocked_client(asserter);
+        let dex = UniV3Executor::new(Arc::new(provider), DEV_KEY, Address::repeat_byte(0x77)).unwrap();
+
+        let edge = Arc::new(EdgeGauge::new());
+        edge.update(12.5, 12.5);
+        let orders = Arc::new(Mutex::new(Vec::new()));
+        let hedge = FillingHedge { orders: orders.clone(), edge_after_fill: edge_after_fill.map(|bps| (edge.clone(), bps)) };
+        let twap = TwapConfig {
+            slices: 3,
+            max_clip_usd: None,
+            interval: Duration::from_millis(10),
+            min_notional_usd: 0.0,
+            min_edge_bps: 5.0,
+            edge,
+        };
+        let executor = ArbitrageExecutor::new(dex, hedge, Arc::new(ExecutionManager::new(1)), 15).with_twap(twap);
+        (executor, orders)
+    }
+
+    #[tokio::test]
+    async fn twap_sends_every_slice_while_the_edge_holds() {
+        let (executor, orders) = twap_executor(3, None);
+        executor.execute(action()).await.unwrap();
+
+        let orders = orders.lock().unwrap();
+        assert_eq!(orders.len(), 3);
+        for order in orders.iter() {
+            assert!((order.size - 2.5 / 3.0).abs() < 1e-12, "{}", order.size);
+        }
+    }
+
+    #[tokio::test]
+    async fn twap_abandons_the_remaining_slices_once_the_edge_is_gone() {
+        // The first clip takes the edge down to 2 bps, under the 5 bps floor
+        let (executor, orders) = twap_executor(1, Some(2.0));
+        executor.execute(action()).await.unwrap();
+        assert_eq!(orders.lock().unwrap().len(), 1);
+    }
+
+    type Stamp = Arc<Mutex<Option<Instant>>>;
+
+    /// Node that takes 150 ms to accept a swap, noting when it answered, and errors on
+    /// everything else (so no receipt is found)
+    async fn slow_node() -> (reqwest::Url, Stamp) {
+        use axum::{routing::post, Json, Router};
+        use serde_json::{json, Value};
+
+        let answered = Stamp::default();
+        let stamp = answered.clone();
+        let app = Router::new().route("/", post(move |Json(request): Json<Value>| {
+            let stamp = stamp.clone();
+            async move {
+                if request["method"] != "eth_sendTransaction" {
+                    let error = json!({ "code": -32601, "message": "not available" });
+                    return Json(json!({ "jsonrpc": "2.0", "id": request["id"], "error": error }));
+                }
+                tokio::time::sleep(Duration::from_millis(150)).await;
+                *stamp.lock().unwrap() = Some(Instant::now());
+                Json(json!({ "jsonrpc": "2.0", "id": request["id"], "result": format!("0x{}", "ab".repeat(32)) }))
+            }
+        }));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        (format!("http://{}", addr).parse().unwrap(), answered)
+    }
+
+    /// Hedge venue that fills immediately, noting when the order was placed
+    struct TimedHedge(Stamp);
+
+    #[async_trait]
+    impl HedgeVenue for TimedHedge {
+        async fn place_order(&self, action: HyperliquidOrderAction) -> Result<Option<HlFill>> {
+            *self.0.lock().unwrap() = Some(Instant::now());
+            Ok(Some(HlFill { oid: 1, total_sz: action.size, avg_px: action.limit_px }))
+        }
+    }
+
+    /// When the hedge was placed and when the swap was accepted, in `mode`
+    async fn leg_times(mode: SubmissionMode) -> (Instant, Instant) {
+        let (url, swapped) = slow_node().await;
+        let provider = alloy::providers::ProviderBuilder::new().disable_recommended_fillers().connect_http(url);
+        let dex = UniV3Executor::new(Arc::new(provider), DEV_KEY, Address::repeat_byte(0x77)).unwrap();
+        let hedged = Stamp::default();
+        let executor = ArbitrageExecutor::new(dex, TimedHedge(hedged.clone()), Arc::new(ExecutionManager::new(1)), 15)
+            .with_submission_mode(mode);
+
+        executor.execute_round_trip(&action()).await.unwrap();
+        let hedged = hedged.lock().unwrap().unwrap();
+        let swapped = swapped.lock().unwrap().unwrap();
+        (hedged, swapped)
+    }
+
+    #[tokio::test]
+    async fn concurrent_mode_hedges_while_the_swap_is_in_flight() {
+        let (hedged, swapped) = leg_times(SubmissionMode::Concurrent).await;
+        assert!(hedged < swapped);
+        // Sequential waits for the swap to be accepted first
+        let (hedged, swapped) = leg_times(SubmissionMode::Sequential).await;
+        assert!(hedged > swapped);
+    }
+
+    #[tokio::test]
+    async fn latency_is_measured_from_the_event_time() {
+        let (url, _) = slow_node().await;
+        let provider = alloy::providers::ProviderBuilder::new().disable_recommended_fillers().connect_http(url);
+        let dex = UniV3Executor::new(Arc::new(provider), DEV_KEY, Address::repeat_byte(0x77)).unwrap();
+        let executor = ArbitrageExecutor::new(dex, TimedHedge(Stamp::default()), Arc::new(ExecutionManager::new(1)), 15);
+        assert!(executor.last_latency().is_none());
+
+        // Seen 100ms ago; the node takes 150ms to accept the swap, then the hedge goes out
+        let mut observed = action();
+        observed.observed_at = Some(Instant::now() - Duration::from_millis(100));
+        executor.execute_round_trip(&observed).await.unwrap();
+
+        let latency = executor.last_latency().unwrap();
+        assert!(latency.dex_submit_ms >= 250.0, "{:?}", latency);
+        assert!(latency.hl_submit_ms >= 0.0 && latency.hl_submit_ms < 100.0, "{:?}", latency);
+        assert!((latency.total_ms - latency.dex_submit_ms - latency.hl_submit_ms).abs() < 1e-6, "{:?}", latency);
+    }
+
+    #[test]
+    fn hedge_landing_first_gives_a_negative_hl_latency() {
+        let observed_at = Instant::now();
+        let latency = LegLatency::measure(observed_at, observed_at + Duration::from_millis(80), observed_at + Duration::from_millis(30));
+        assert!((latency.dex_submit_ms - 80.0).abs() < 1e-6);
+        assert!((latency.hl_submit_ms + 50.0).abs() < 1e-6);
+        assert!((latency.total_ms - 80.0).abs() < 1e-6);
+    }
+}
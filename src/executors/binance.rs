@@ -0,0 +1,370 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use anyhow::Result;
+use async_trait::async_trait;
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use tracing::info;
+
+use crate::executors::hyperliquid::{HlFill, HyperliquidOrderAction, OrderMode, TimeInForce};
+use crate::types::Executor;
+
+const BINANCE_API_URL: &str = "https://api.binance.com";
+const RECV_WINDOW_MS: u64 = 5000;
+
+/// How a Binance spot order executes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BinanceOrderType {
+    /// LIMIT, timeInForce=IOC
+    LimitIoc,
+    /// LIMIT, timeInForce=GTC
+    LimitGtc,
+    /// LIMIT_MAKER: rejected instead of taking liquidity
+    LimitMaker,
+    /// MARKET: fills at any price; `price` only sizes the notional check
+    Market,
+}
+
+/// Spot order for a Binance hedge leg
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BinanceOrderAction {
+    pub symbol: String,
+    pub is_buy: bool,
+    pub quantity: f64,
+    pub price: f64,
+    pub order_type: BinanceOrderType,
+}
+
+impl From<&HyperliquidOrderAction> for BinanceOrderAction {
+    fn from(order: &HyperliquidOrderAction) -> Self {
+        let order_type = match (order.mode, order.tif) {
+            (OrderMode::MarketIoc { .. }, _) => BinanceOrderType::Market,
+            (_, TimeInForce::Alo) => BinanceOrderType::LimitMaker,
+            (_, TimeInForce::Ioc) => BinanceOrderType::LimitIoc,
+            (_, TimeInForce::Gtc) => BinanceOrderType::LimitGtc,
+        };
+        Self {
+            symbol: order.coin.clone(),
+            is_buy: order.is_buy,
+            quantity: order.size,
+            price: order.limit_px,
+            order_type,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum BinanceExecutorError {
+    /// Size rounds down to zero at the symbol's LOT_SIZE step
+    SizeRoundsToZero { symbol: String, size: f64 },
+    /// Order value below the symbol's NOTIONAL / MIN_NOTIONAL filter
+    BelowMinNotional { symbol: String, notional: f64, min_notional: f64 },
+    /// Non-2xx response; `body` carries Binance's error code and message
+    Rejected { status: u16, body: String },
+}
+
+impl fmt::Display for BinanceExecutorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BinanceExecutorError::SizeRoundsToZero { symbol, size } => {
+                write!(f, "Binance {} order size {} rounds to zero", symbol, size)
+            }
+            BinanceExecutorError::BelowMinNotional { symbol, notional, min_notional } => {
+                write!(f, "Binance {} order value {:.2} below min notional {}", symbol, notional, min_notional)
+            }
+            BinanceExecutorError::Rejected { status, body } => {
+                write!(f, "Binance order rejected ({}): {}", status, body)
+            }
+        }
+    }
+}
+
+impl std::error::Error for BinanceExecutorError {}
+
+/// A filter increment such as "0.00100000", with the decimal places it needs printed
+#[derive(Debug, Clone, Copy)]
+struct Increment {
+    size: f64,
+    decimals: usize,
+}
+
+impl Increment {
+    fn parse(text: &str) -> Option<Self> {
+        let size = text.parse().ok()?;
+        let decimals = text.split_once('.').map_or(0, |(_, frac)| frac.trim_end_matches('0').len());
+        Some(Self { size, decimals })
+    }
+
+    /// `value` rounded to a whole number of increments, as a number and as the exact
+    /// decimal string to send (so 3 steps of 0.1 go out as "0.3", not 0.30000000000000004)
+    fn round(&self, value: f64, up: bool) -> (f64, String) {
+        if self.size <= 0.0 {
+            return (value, value.to_string());
+        }
+        // Tolerance keeps values already on the grid in place: 0.3 / 0.1 = 2.9999999999999996
+        let steps = value / self.size;
+        let steps = if up { (steps - 1e-9).ceil() } else { (steps + 1e-9).floor() };
+        let rounded = steps * self.size;
+        (rounded, format!("{:.*}", self.decimals, rounded))
+    }
+}
+
+/// Quantity step, price tick and minimum order value from the symbol's filters
+#[derive(Debug, Clone, Copy)]
+struct SymbolFilters {
+    step_size: Increment,
+    tick_size: Increment,
+    min_notional: f64,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct OrderResponse {
+    order_id: u64,
+    executed_qty: String,
+    cummulative_quote_qty: String,
+}
+
+/// Places signed spot orders through Binance's REST API
+pub struct BinanceExecutor {
+    client: reqwest::Client,
+    api_url: String,
+    api_key: String,
+    api_secret: String,
+    filters: Mutex<HashMap<String, SymbolFilters>>,
+}
+
+impl BinanceExecutor {
+    pub fn new(api_key: String, api_secret: String) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            api_url: BINANCE_API_URL.to_string(),
+            api_key,
+            api_secret,
+            filters: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Build from `BINANCE_API_KEY` and `BINANCE_API_SECRET`
+    pub fn from_env() -> Result<Self> {
+        let api_key = std::env::var("BINANCE_API_KEY")?;
+        let api_secret = std::env::var("BINANCE_API_SECRET")?;
+        Ok(Self::new(api_key, api_secret))
+    }
+
+    /// Override the REST base URL (e.g. the testnet host)
+    pub fn with_api_url(mut self, api_url: String) -> Self {
+        self.api_url = api_url;
+        self
+    }
+
+    fn sign(&self, query: &str) -> Result<String> {
+        let mut mac = Hmac::<Sha256>::new_from_slice(self.api_secret.as_bytes())?;
+        mac.update(query.as_bytes());
+        Ok(hex::encode(mac.finalize().into_bytes()))
+    }
+
+    /// Fetch and cache the symbol's size/price increments
+    async fn symbol_filters(&self, symbol: &str) -> Result<SymbolFilters> {
+        if let Some(filters) = self.filters.lock().unwrap().get(symbol).copied() {
+            return Ok(filters);
+        }
+
+        let url = format!("{}/api/v3/exchangeInfo", self.api_url);
+        let info: serde_json::Value = self.client.get(&url)
+            .query(&[("symbol", symbol)])
+            .send().await?
+            .error_for_status()?
+            .json().await?;
+
+        let filter = |kind: &str, field: &str| -> Option<String> {
+            info["symbols"][0]["filters"].as_array()?
+                .iter()
+                .find(|f| f["filterType"] == kind)?[field]
+                .as_str()
+                .map(str::to_string)
+        };
+        let filters = SymbolFilters {
+            step_size: filter("LOT_SIZE", "stepSize").as_deref().and_then(Increment::parse)
+                .ok_or_else(|| anyhow::anyhow!("No LOT_SIZE filter for {}", symbol))?,
+            tick_size: filter("PRICE_FILTER", "tickSize").as_deref().and_then(Increment::parse)
+                .ok_or_else(|| anyhow::anyhow!("No PRICE_FILTER for {}", symbol))?,
+            // Newer symbols carry NOTIONAL, older ones MIN_NOTIONAL; neither means no minimum
+            min_notional: filter("NOTIONAL", "minNotional")
+                .or_else(|| filter("MIN_NOTIONAL", "minNotional"))
+                .and_then(|min| min.parse().ok())
+                .unwrap_or(0.0),
+        };
+        self.filters.lock().unwrap().insert(symbol.to_string(), filters);
+        Ok(filters)
+    }
+
+    /// Submit an order and report what executed immediately, if anything
+    pub async fn place_order(&self, action: BinanceOrderAction) -> Result<Option<HlFill>> {
+        let filters = self.symbol_filters(&action.symbol).await?;
+        let (size, quantity) = filters.step_size.round(action.quantity, false);
+        // Round away from the book: a sell rounded down could cross as a taker
+        let (_, price) = filters.tick_size.round(action.price, !action.is_buy);
+        if size <= 0.0 {
+            return Err(BinanceExecutorError::SizeRoundsToZero { symbol: action.symbol, size: action.quantity }.into());
+        }
+        let notional = size * action.price;
+        if notional < filters.min_notional {
+            return Err(BinanceExecutorError::BelowMinNotional {
+                symbol: action.symbol,
+                notional,
+                min_notional: filters.min_notional,
+            }.into());
+        }
+
+        let timestamp = SystemTime::now().duration_since(UNIX_EPOCH)?.as_millis();
+        let side = if action.is_buy { "BUY" } else { "SELL" };
+        // Post-only maps to LIMIT_MAKER, which takes no timeInForce; MARKET takes no price
+        let order_type = match action.order_type {
+            BinanceOrderType::Market => "type=MARKET".to_string(),
+            BinanceOrderType::LimitMaker => format!("type=LIMIT_MAKER&price={}", price),
+            BinanceOrderType::LimitIoc => format!("type=LIMIT&timeInForce=IOC&price={}", price),
+            BinanceOrderType::LimitGtc => format!("type=LIMIT&timeInForce=GTC&price={}", price),
+        };
+        let query = format!(
+            "symbol={}&side={}&{}&quantity={}&newOrderRespType=FULL&recvWindow={}&timestamp={}",
+            action.symbol, side, order_type, quantity, RECV_WINDOW_MS, timestamp
+        );
+        let signature = self.sign(&query)?;
+
+        let url = format!("{}/api/v3/order?{}&signature={}", self.api_url, query, signature);
+        let response = self.client.post(&url)
+            .header("X-MBX-APIKEY", &self.api_key)
+            .send().await?;
+        if !response.status().is_success() {
+            let status = response.status().as_u16();
+            let body = response.text().await.unwrap_or_default();
+            return Err(BinanceExecutorError::Rejected { status, body }.into());
+        }
+        let order: OrderResponse = response.json().await?;
+
+        let executed: f64 = order.executed_qty.parse()?;
+        let quote: f64 = order.cummulative_quote_qty.parse()?;
+        info!("Binance: {} {} {} @ {} (order {}) filled {}", side, quantity, action.symbol, price, order.order_id, executed);
+        if executed <= 0.0 {
+            return Ok(None);
+        }
+        Ok(Some(HlFill { oid: order.order_id, total_sz: executed, avg_px: quote / executed }))
+    }
+}
+
+#[async_trait]
+impl Executor<BinanceOrderAction> for BinanceExecutor {
+    async fn execute(&self, action: BinanceOrderAction) -> Result<()> {
+        self.place_order(action).await.map(|_| ())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use axum::{extract::{RawQuery, State}, http::HeaderMap, routing::{get, post}, Json, Router};
+    use serde_json::{json, Value};
+
+    use super::*;
+
+    type Captured = Arc<Mutex<Vec<(String, String)>>>;
+
+    async fn exchange_info() -> Json<Value> {
+        Json(json!({ "symbols": [{ "symbol": "HYPEUSDT", "filters": [
+            { "filterType": "PRICE_FILTER", "tickSize": "0.00100000" },
+            { "filterType": "LOT_SIZE", "stepSize": "0.01000000" },
+            { "filterType": "NOTIONAL", "minNotional": "5.00000000" },
+        ]}]}))
+    }
+
+    async fn order(State(captured): State<Captured>, headers: HeaderMap, RawQuery(query): RawQuery) -> Json<Value> {
+        let api_key = headers["X-MBX-APIKEY"].to_str().unwrap().to_string();
+        captured.lock().unwrap().push((api_key, query.unwrap_or_default()));
+        Json(json!({ "orderId": 7, "executedQty": "0.30000000", "cummulativeQuoteQty": "12.00000000" }))
+    }
+
+    /// Executor pointed at a local mock of the exchangeInfo and order endpoints
+    async fn mock_binance() -> (BinanceExecutor, Captured) {
+        let captured = Captured::default();
+        let app = Router::new()
+            .route("/api/v3/exchangeInfo", get(exchange_info))
+            .route("/api/v3/order", post(order))
+            .with_state(captured.clone());
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        let executor = BinanceExecutor::new("key".to_string(), "secret".to_string())
+            .with_api_url(format!("http://{}", addr));
+        (executor, captured)
+    }
+
+    fn order_action(is_buy: bool, quantity: f64, price: f64, order_type: BinanceOrderType) -> BinanceOrderAction {
+        BinanceOrderAction { symbol: "HYPEUSDT".to_string(), is_buy, quantity, price, order_type }
+    }
+
+    #[test]
+    fn increments_round_to_exact_decimals() {
+        let step = Increment::parse("0.10000000").unwrap();
+        assert_eq!(step.decimals, 1);
+        assert_eq!(step.round(0.3, false).1, "0.3");
+        assert_eq!(step.round(0.39, false).1, "0.3");
+        assert_eq!(step.round(0.31, true).1, "0.4");
+        // On the grid already: neither direction moves it
+        assert_eq!(step.round(0.3, true).1, "0.3");
+        assert_eq!(Increment::parse("1.00000000").unwrap().round(12.7, false).1, "12");
+    }
+
+    #[test]
+    fn signs_like_binance_docs() {
+        // Example from Binance's SIGNED endpoint documentation
+        let executor = BinanceExecutor::new(
+            "key".to_string(),
+            "NhqPtmdSJYdKjVHjA7PZj4Mge3R5YNiP1e3UZjInClVN65XAbvqqM6A7H5fATj0j".to_string(),
+        );
+        let query = "symbol=LTCBTC&side=BUY&type=LIMIT&timeInForce=GTC&quantity=1&price=0.1&recvWindow=5000&timestamp=1499827319559";
+        assert_eq!(executor.sign(query).unwrap(), "c8db56825ae71d6d79447849e617115f4a920fa2acdcab2b053c4b2838bd6b71");
+    }
+
+    #[tokio::test]
+    async fn sends_signed_orders_rounded_to_filters() {
+        let (executor, captured) = mock_binance().await;
+
+        let fill = executor.place_order(order_action(false, 1.239, 40.1231, BinanceOrderType::LimitMaker)).await.unwrap().unwrap();
+        assert_eq!((fill.oid, fill.total_sz, fill.avg_px), (7, 0.3, 40.0));
+        executor.place_order(order_action(true, 1.239, 40.1239, BinanceOrderType::LimitIoc)).await.unwrap();
+        executor.place_order(order_action(true, 0.5, 40.0, BinanceOrderType::Market)).await.unwrap();
+
+        let captured = captured.lock().unwrap();
+        let expected = [
+            // Sell price rounds up so the maker order cannot cross
+            "symbol=HYPEUSDT&side=SELL&type=LIMIT_MAKER&price=40.124&quantity=1.23",
+            "symbol=HYPEUSDT&side=BUY&type=LIMIT&timeInForce=IOC&price=40.123&quantity=1.23",
+            "symbol=HYPEUSDT&side=BUY&type=MARKET&quantity=0.50",
+        ];
+        assert_eq!(captured.len(), expected.len());
+        for ((api_key, query), expected) in captured.iter().zip(expected) {
+            assert_eq!(api_key, "key");
+            let (signed, signature) = query.split_once("&signature=").unwrap();
+            assert!(signed.starts_with(&format!("{}&newOrderRespType=FULL&recvWindow=5000&timestamp=", expected)), "{}", signed);
+            assert_eq!(signature, executor.sign(signed).unwrap());
+        }
+    }
+
+    #[tokio::test]
+    async fn rejects_orders_the_filters_would_refuse() {
+        let (executor, captured) = mock_binance().await;
+
+        let error = executor.place_order(order_action(true, 0.009, 40.0, BinanceOrderType::LimitIoc)).await.unwrap_err();
+        assert!(matches!(error.downcast_ref(), Some(BinanceExecutorError::SizeRoundsToZero { .. })), "{}", error);
+        // 0.1 * 40 = $4, under the $5 minimum
+        let error = executor.place_order(order_action(true, 0.1, 40.0, BinanceOrderType::LimitIoc)).await.unwrap_err();
+        assert!(matches!(error.downcast_ref(), Some(BinanceExecutorError::BelowMinNotional { .. })), "{}", error);
+        assert!(captured.lock().unwrap().is_empty());
+    }
+}
@@ -1,98 +1,763 @@
+use std::{collections::HashMap, fmt, sync::{Arc, Mutex}, time::{Duration, Instant}};
+
 use anyhow::Result;
 use async_trait::async_trait;
-use alloy::signers::local::PrivateKeySigner;
+use alloy::{primitives::Address, signers::local::PrivateKeySigner};
 use hyperliquid_rust_sdk::{
-    BaseUrl, ExchangeClient, ExchangeResponseStatus, InfoClient,
-    ClientOrderRequest, ClientOrder, ClientLimit
+    BaseUrl, ExchangeClient, ExchangeDataStatus, ExchangeResponseStatus, InfoClient,
+    ClientCancelRequest, ClientCancelRequestCloid, ClientOrderRequest, ClientOrder, ClientLimit
 };
-use tracing::{error, info};
+use serde::{Deserialize, Serialize};
+use tokio::sync::OnceCell;
+use tracing::{error, info, warn};
+use uuid::Uuid;
 
 use crate::types::Executor;
 
-#[derive(Debug, Clone)]
+/// Hyperliquid allows prices with at most this many decimals minus `szDecimals`
+const MAX_PRICE_DECIMALS_PERP: u32 = 6;
+const MAX_PRICE_DECIMALS_SPOT: u32 = 8;
+/// Prices are also limited to this many significant figures
+const MAX_SIGNIFICANT_FIGURES: i32 = 5;
+/// Hyperliquid rejects orders worth less than this
+pub const HL_MIN_ORDER_VALUE_USD: f64 = 10.0;
+/// Info endpoint, for queries the SDK doesn't expose (order status by cloid)
+const HL_INFO_URL: &str = "https://api.hyperliquid.xyz/info";
+
+#[derive(Debug)]
+pub enum HyperliquidExecutorError {
+    /// Coin not present in either the perp or the spot universe
+    UnknownCoin(String),
+    /// Post-only order rejected because it would have crossed; callers may retry as IOC
+    PostOnlyWouldCross { coin: String, px: f64 },
+    /// IOC order found nothing to match at its limit; no exposure was opened
+    NotFilled { coin: String, px: f64 },
+    /// Order refused by the exchange (per-order status error, e.g. margin or tick size)
+    Rejected { coin: String, reason: String },
+    /// Order call failed and the exchange couldn't be asked whether it was placed;
+    /// sending it again could double the position
+    Unconfirmed { coin: String, cloid: Uuid },
+}
+
+impl fmt::Display for HyperliquidExecutorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HyperliquidExecutorError::UnknownCoin(coin) => {
+                write!(f, "Coin '{}' not found in HL perp or spot metadata", coin)
+            }
+            HyperliquidExecutorError::PostOnlyWouldCross { coin, px } => {
+                write!(f, "Post-only {} order @ {} would cross the book", coin, px)
+            }
+            HyperliquidExecutorError::NotFilled { coin, px } => {
+                write!(f, "IOC {} order @ {} did not fill", coin, px)
+            }
+            HyperliquidExecutorError::Rejected { coin, reason } => {
+                write!(f, "HL rejected {} order: {}", coin, reason)
+            }
+            HyperliquidExecutorError::Unconfirmed { coin, cloid } => {
+                write!(f, "HL {} order {} may have been placed, status unknown", coin, cloid)
+            }
+        }
+    }
+}
+
+impl std::error::Error for HyperliquidExecutorError {}
+
+/// Size step and price precision for one coin, derived from exchange metadata
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AssetPrecision {
+    pub sz_decimals: u32,
+    pub max_price_decimals: u32,
+}
+
+impl AssetPrecision {
+    pub fn perp(sz_decimals: u32) -> Self {
+        Self { sz_decimals, max_price_decimals: MAX_PRICE_DECIMALS_PERP.saturating_sub(sz_decimals) }
+    }
+
+    pub fn spot(sz_decimals: u32) -> Self {
+        Self { sz_decimals, max_price_decimals: MAX_PRICE_DECIMALS_SPOT.saturating_sub(sz_decimals) }
+    }
+
+    /// Spot or perp precision depending on how the coin is named
+    pub fn for_coin(coin: &str, sz_decimals: u32) -> Self {
+        if is_spot_coin(coin) { Self::spot(sz_decimals) } else { Self::perp(sz_decimals) }
+    }
+
+    pub fn round_size(&self, size: f64) -> f64 {
+        let multiplier = 10_f64.powi(self.sz_decimals as i32);
+        (size * multiplier).round() / multiplier
+    }
+
+    /// Round to 5 significant figures, capped at the asset's max price decimals
+    pub fn round_price(&self, price: f64) -> f64 {
+        if price <= 0.0 {
+            return price;
+        }
+        let sig_decimals = MAX_SIGNIFICANT_FIGURES - 1 - price.log10().floor() as i32;
+        let decimals = sig_decimals.clamp(0, self.max_price_decimals as i32);
+        let multiplier = 10_f64.powi(decimals);
+        (price * multiplier).round() / multiplier
+    }
+}
+
+/// Spot pairs are named `BASE/QUOTE` or `@index`; anything else is a perp
+fn is_spot_coin(coin: &str) -> bool {
+    coin.contains('/') || coin.starts_with('@')
+}
+
+/// One pair from spot metadata, with its tokens resolved
+struct SpotListing<'a> {
+    name: &'a str,
+    index: String,
+    base: &'a str,
+    quote: &'a str,
+    /// Size decimals of the base token
+    sz_decimals: u32,
+}
+
+/// Precision of a perp, given each asset's name and szDecimals from `meta`
+fn perp_precision<'a>(mut assets: impl Iterator<Item = (&'a str, u32)>, coin: &str) -> Option<AssetPrecision> {
+    assets.find(|(name, _)| *name == coin).map(|(_, sz_decimals)| AssetPrecision::perp(sz_decimals))
+}
+
+/// Precision of a spot pair named by its listed name, `@index` or `BASE/QUOTE`
+fn spot_precision<'a>(mut pairs: impl Iterator<Item = SpotListing<'a>>, coin: &str) -> Option<AssetPrecision> {
+    pairs.find(|pair| pair.name == coin || pair.index == coin || format!("{}/{}", pair.base, pair.quote) == coin)
+        .map(|pair| AssetPrecision::spot(pair.sz_decimals))
+}
+
+/// Time-in-force for Hyperliquid limit orders
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum TimeInForce {
+    /// Immediate-or-cancel: take liquidity, cancel any unfilled remainder
+    #[default]
+    Ioc,
+    /// Good-til-cancelled: rest on the book until filled or cancelled
+    Gtc,
+    /// Add-liquidity-only (post-only): rejected if it would immediately match
+    Alo,
+}
+
+impl TimeInForce {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TimeInForce::Ioc => "Ioc",
+            TimeInForce::Gtc => "Gtc",
+            TimeInForce::Alo => "Alo",
+        }
+    }
+}
+
+/// How the hedge price is chosen at submission time
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(tag = "type")]
+pub enum OrderMode {
+    /// Limit order at the action's `limit_px` with its `tif`
+    #[default]
+    Limit,
+    /// Cross the spread: IOC priced off the live BBO, at most `max_slippage_bps` through
+    /// the touch. Trades price for certainty of fill when a one-sided position is worse.
+    MarketIoc { max_slippage_bps: f64 },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HyperliquidOrderAction {
     pub coin: String,
     pub is_buy: bool,
     pub size: f64,
     pub limit_px: f64,
+    pub tif: TimeInForce,
+    pub mode: OrderMode,
+    /// Only shrink an open position; the exchange rejects the part that would open or flip one
+    #[serde(default)]
+    pub reduce_only: bool,
+}
+
+/// Venue that places the hedge leg of a round trip
+#[async_trait]
+pub trait HedgeVenue: Send + Sync {
+    /// Place an order and return the fill, if the venue reported one
+    async fn place_order(&self, action: HyperliquidOrderAction) -> Result<Option<HlFill>>;
+}
+
+/// How a resting post-only hedge is re-pegged before giving up and crossing
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct RepricePolicy {
+    /// Cancel/repost rounds after the initial post
+    pub max_reprices: u32,
+    /// How long each post rests before it is repriced
+    pub interval: Duration,
+    /// Overall budget for the maker attempt, measured from the first post
+    pub deadline: Duration,
+}
+
+/// Executed quantity and average price reported by the exchange
+#[derive(Debug, Clone, PartialEq)]
+pub struct HlFill {
+    pub oid: u64,
+    pub total_sz: f64,
+    pub avg_px: f64,
+}
+
+impl HlFill {
+    /// Merge partial fills into one size-weighted fill, keeping the last order id
+    fn combine(fills: &[HlFill]) -> Option<Self> {
+        let last = fills.last()?;
+        let total_sz: f64 = fills.iter().map(|f| f.total_sz).sum();
+        if total_sz <= 0.0 {
+            return None;
+        }
+        let notional: f64 = fills.iter().map(|f| f.total_sz * f.avg_px).sum();
+        Some(Self { oid: last.oid, total_sz, avg_px: notional / total_sz })
+    }
+
+    fn from_filled(filled: &hyperliquid_rust_sdk::FilledOrder) -> Option<Self> {
+        Some(Self {
+            oid: filled.oid,
+            total_sz: filled.total_sz.parse().ok()?,
+            avg_px: filled.avg_px.parse().ok()?,
+        })
+    }
+}
+
+/// Order state from HL's `orderStatus` query
+#[derive(Debug, Clone, PartialEq)]
+struct OrderLookup {
+    oid: u64,
+    /// "open", "filled", "canceled", "rejected", ...
+    status: String,
+    /// Size still on the book and size when placed
+    open_sz: f64,
+    orig_sz: f64,
+    limit_px: f64,
+}
+
+impl OrderLookup {
+    /// Parse an `orderStatus` response; None when the exchange has never seen the order
+    fn parse(body: &serde_json::Value) -> Result<Option<Self>> {
+        match body["status"].as_str() {
+            Some("unknownOid") => Ok(None),
+            Some("order") => {
+                let order = &body["order"]["order"];
+                let field = |name: &str| -> Result<f64> {
+                    order[name].as_str()
+                        .and_then(|value| value.parse().ok())
+                        .ok_or_else(|| anyhow::anyhow!("HL order status without {}", name))
+                };
+                Ok(Some(Self {
+                    oid: order["oid"].as_u64().ok_or_else(|| anyhow::anyhow!("HL order status without oid"))?,
+                    status: body["order"]["status"].as_str().unwrap_or_default().to_string(),
+                    open_sz: field("sz")?,
+                    orig_sz: field("origSz")?,
+                    limit_px: field("limitPx")?,
+                }))
+            }
+            _ => anyhow::bail!("Unexpected HL order status response: {}", body),
+        }
+    }
+
+    /// What the order amounts to; fills are priced at the limit, the response has no average
+    fn into_outcome(self, cloid: Uuid) -> OrderOutcome {
+        if self.status == "open" {
+            return OrderOutcome::Resting { oid: self.oid, cloid };
+        }
+        let filled = (self.orig_sz - self.open_sz).max(0.0);
+        if filled > 0.0 {
+            OrderOutcome::Filled(HlFill { oid: self.oid, total_sz: filled, avg_px: self.limit_px })
+        } else {
+            OrderOutcome::NotFilled
+        }
+    }
+}
+
+/// Signed exchange connection that orders and resting-order cancels go through
+#[async_trait]
+pub trait HlConnection: Sized + Send + Sync {
+    /// Connect as `signer`, trading for `vault_address` if set
+    async fn connect(signer: PrivateKeySigner, vault_address: Option<Address>) -> Result<Self>;
+    /// Cancel by client order id; true only if the exchange accepted the cancel
+    async fn cancel_cloid(&self, coin: &str, cloid: Uuid) -> Result<bool>;
+}
+
+#[async_trait]
+impl HlConnection for ExchangeClient {
+    async fn connect(signer: PrivateKeySigner, vault_address: Option<Address>) -> Result<Self> {
+        let client = ExchangeClient::new(None, signer, Some(BaseUrl::Mainnet), None, vault_address).await?;
+        info!("✓ HL exchange client ready");
+        Ok(client)
+    }
+
+    async fn cancel_cloid(&self, coin: &str, cloid: Uuid) -> Result<bool> {
+        let cancel = ClientCancelRequestCloid { asset: coin.to_string(), cloid };
+        match self.cancel_by_cloid(cancel, None).await? {
+            ExchangeResponseStatus::Ok(resp) => match resp.data.as_ref().and_then(|data| data.statuses.first()) {
+                Some(ExchangeDataStatus::Error(msg)) => {
+                    info!("HL: {} {} not cancelled: {}", coin, cloid, msg);
+                    Ok(false)
+                }
+                _ => Ok(true),
+            },
+            ExchangeResponseStatus::Err(e) => anyhow::bail!("{}", e),
+        }
+    }
+}
+
+/// GTC/ALO orders an executor may have left on the book, keyed by client order id,
+/// so they can be cancelled before the process exits
+pub struct RestingOrders<C = ExchangeClient> {
+    signer: PrivateKeySigner,
+    vault_address: Option<Address>,
+    orders: Mutex<HashMap<Uuid, String>>,
+    /// Built on first use; the executor places its orders through the same client
+    client: OnceCell<C>,
+}
+
+impl<C> RestingOrders<C> {
+    fn new(signer: PrivateKeySigner, vault_address: Option<Address>) -> Self {
+        Self { signer, vault_address, orders: Mutex::new(HashMap::new()), client: OnceCell::new() }
+    }
+
+    fn track(&self, cloid: Uuid, coin: &str) {
+        self.orders.lock().unwrap().insert(cloid, coin.to_string());
+    }
+
+    fn untrack(&self, cloid: Uuid) {
+        self.orders.lock().unwrap().remove(&cloid);
+    }
+
+    pub fn len(&self) -> usize {
+        self.orders.lock().unwrap().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<C: HlConnection> RestingOrders<C> {
+    /// Signed client, connected once; concurrent first callers share the one connection
+    async fn client(&self) -> Result<&C> {
+        self.client.get_or_try_init(|| C::connect(self.signer.clone(), self.vault_address)).await
+    }
+
+    /// Cancel every tracked order and return how many the exchange accepted cancels for.
+    /// Orders that filled in the meantime are rejected by the exchange and just dropped.
+    pub async fn cancel_all(&self) -> Result<usize> {
+        let orders: Vec<(Uuid, String)> = self.orders.lock().unwrap().drain().collect();
+        if orders.is_empty() {
+            return Ok(0);
+        }
+
+        let client = self.client().await?;
+
+        let mut cancelled = 0;
+        for (cloid, coin) in orders {
+            match client.cancel_cloid(&coin, cloid).await {
+                Ok(true) => cancelled += 1,
+                Ok(false) => {}
+                Err(e) => warn!("HL: cancel {} {} failed: {}", coin, cloid, e),
+            }
+        }
+        Ok(cancelled)
+    }
 }
 
 pub struct HyperliquidExecutor {
     signer: PrivateKeySigner,
+    /// Vault the signer trades on behalf of; orders are attributed to it instead of the signer
+    vault_address: Option<Address>,
+    /// Main account an API (agent) wallet signer acts for; order queries are made against it
+    account_address: Option<Address>,
+    /// Per-coin precision and when it was fetched; refreshed once older than `meta_refresh`
+    precision_cache: Mutex<HashMap<String, (AssetPrecision, Instant)>>,
+    meta_refresh: Duration,
+    /// Repricing for post-only orders; without it an ALO order is posted once
+    reprice_policy: Option<RepricePolicy>,
+    /// Used when a coin's metadata can't be fetched or doesn't list it
+    fallback_precision: Option<AssetPrecision>,
+    /// Built lazily so `new` stays sync; `OnceCell` makes concurrent first use safe
+    info_client: OnceCell<InfoClient>,
+    /// Non-IOC orders that may still be resting; also holds the shared exchange client
+    resting: Arc<RestingOrders>,
+    http: reqwest::Client,
 }
 
 impl HyperliquidExecutor {
     pub fn new(private_key: String) -> Result<Self> {
         let signer = private_key.parse::<PrivateKeySigner>()?;
-        Ok(Self { signer })
+        Ok(Self {
+            resting: Arc::new(RestingOrders::new(signer.clone(), None)),
+            signer,
+            vault_address: None,
+            account_address: None,
+            precision_cache: Mutex::new(HashMap::new()),
+            meta_refresh: Duration::from_secs(3600),
+            reprice_policy: None,
+            fallback_precision: None,
+            info_client: OnceCell::new(),
+            http: reqwest::Client::new(),
+        })
+    }
+
+    /// Route orders through a vault. The signer must be the vault leader; the exchange
+    /// rejects orders from unauthorized signers, which surfaces as an HL error.
+    pub fn with_vault_address(mut self, vault_address: Address) -> Self {
+        self.vault_address = Some(vault_address);
+        self.resting = Arc::new(RestingOrders::new(self.signer.clone(), self.vault_address));
+        self
+    }
+
+    /// Re-peg resting post-only orders, then cross whatever is left
+    pub fn with_repricing(mut self, policy: RepricePolicy) -> Self {
+        self.reprice_policy = Some(policy);
+        self
+    }
+
+    /// Keep trading with this precision if metadata is unavailable instead of failing the order
+    pub fn with_fallback_precision(mut self, precision: AssetPrecision) -> Self {
+        self.fallback_precision = Some(precision);
+        self
+    }
+
+    /// How long fetched metadata is trusted before it is queried again (default 1h)
+    pub fn with_meta_refresh(mut self, meta_refresh: Duration) -> Self {
+        self.meta_refresh = meta_refresh;
+        self
+    }
+
+    /// Sign with an API wallet approved for `account` rather than the account's own key,
+    /// so the key held by the bot can trade but not withdraw or move on-chain funds
+    pub fn with_account_address(mut self, account: Address) -> Self {
+        self.account_address = Some(account);
+        self
+    }
+
+    pub fn vault_address(&self) -> Option<Address> {
+        self.vault_address
+    }
+
+    /// Address whose orders and fills the exchange reports: the vault, else the account
+    /// an API wallet acts for, else the signer itself
+    pub fn user_address(&self) -> Address {
+        self.vault_address
+            .or(self.account_address)
+            .unwrap_or_else(|| self.signer.address())
+    }
+
+    /// Shared handle to the orders left on the book, usable after the executor is boxed
+    pub fn resting_orders(&self) -> Arc<RestingOrders> {
+        self.resting.clone()
+    }
+
+    /// Resolve size/price precision for a coin, querying perp or spot metadata once per coin
+    /// per `meta_refresh`. If a refresh fails the stale entry is kept; with nothing cached it
+    /// falls back to the configured default (uncached, so metadata is retried next order).
+    async fn asset_precision(&self, coin: &str) -> Result<AssetPrecision> {
+        self.cached_precision(coin, self.fetch_precision(coin)).await
+    }
+
+    /// [Self::asset_precision] with the metadata query given as `fetch`, which only runs
+    /// on a cache miss
+    async fn cached_precision(
+        &self,
+        coin: &str,
+        fetch: impl std::future::Future<Output = Result<AssetPrecision>>,
+    ) -> Result<AssetPrecision> {
+        let cached = self.precision_cache.lock().unwrap().get(coin).copied();
+        if let Some((precision, fetched_at)) = cached {
+            if fetched_at.elapsed() < self.meta_refresh {
+                return Ok(precision);
+            }
+        }
+
+        match fetch.await {
+            Ok(precision) => {
+                self.precision_cache.lock().unwrap().insert(coin.to_string(), (precision, Instant::now()));
+                Ok(precision)
+            }
+            Err(e) => match (cached, self.fallback_precision) {
+                (Some((stale, _)), _) => {
+                    warn!("HL: metadata refresh failed, keeping cached precision: {}", e);
+                    Ok(stale)
+                }
+                (None, Some(fallback)) => {
+                    warn!("HL: {} - using fallback precision {:?}", e, fallback);
+                    Ok(fallback)
+                }
+                (None, None) => Err(e),
+            },
+        }
+    }
+
+    async fn fetch_precision(&self, coin: &str) -> Result<AssetPrecision> {
+        let info_client = self.info_client().await?;
+        let precision = if is_spot_coin(coin) {
+            let spot_meta = info_client.spot_meta().await?;
+            let pairs = spot_meta.universe.iter().filter_map(|pair| {
+                let base = spot_meta.tokens.iter().find(|t| t.index == pair.tokens[0])?;
+                let quote = spot_meta.tokens.iter().find(|t| t.index == pair.tokens[1])?;
+                Some(SpotListing {
+                    name: &pair.name,
+                    index: format!("@{}", pair.index),
+                    base: &base.name,
+                    quote: &quote.name,
+                    sz_decimals: base.sz_decimals as u32,
+                })
+            });
+            spot_precision(pairs, coin)
+        } else {
+            let meta = info_client.meta().await?;
+            perp_precision(meta.universe.iter().map(|asset| (asset.name.as_str(), asset.sz_decimals as u32)), coin)
+        };
+
+        Ok(precision.ok_or_else(|| HyperliquidExecutorError::UnknownCoin(coin.to_string()))?)
+    }
+
+    /// Build the SDK order request for an already-rounded size and price
+    fn build_order_request(action: &HyperliquidOrderAction, sz: f64, limit_px: f64, cloid: Uuid) -> ClientOrderRequest {
+        ClientOrderRequest {
+            asset: action.coin.clone(),
+            is_buy: action.is_buy,
+            reduce_only: action.reduce_only,
+            limit_px,
+            sz,
+            cloid: Some(cloid),
+            order_type: ClientOrder::Limit(ClientLimit {
+                tif: action.tif.as_str().to_string(),
+            }),
+        }
+    }
+
+    /// What the first order status in an exchange response means for `action`. Errors
+    /// other than the expected post-only and IOC misses are rejections.
+    fn classify(
+        status: Option<&ExchangeDataStatus>,
+        action: &HyperliquidOrderAction,
+        rounded_size: f64,
+        rounded_price: f64,
+        cloid: Uuid,
+    ) -> Result<OrderOutcome> {
+        Ok(match status {
+            Some(ExchangeDataStatus::Filled(filled)) => match HlFill::from_filled(filled) {
+                Some(fill) => {
+                    if fill.total_sz < rounded_size * (1.0 - 1e-9) {
+                        warn!("HL: partial fill {} of {:.4} @ ${:.3} (order {})",
+                            fill.total_sz, rounded_size, fill.avg_px, fill.oid);
+                    } else {
+                        info!("HL: filled {} @ ${:.3} (order {})", fill.total_sz, fill.avg_px, fill.oid);
+                    }
+                    OrderOutcome::Filled(fill)
+                }
+                None => {
+                    warn!("HL: {:.1} @ ${:.3} filled, unparseable fill report", rounded_size, rounded_price);
+                    OrderOutcome::Unreported { cloid }
+                }
+            },
+            Some(ExchangeDataStatus::Resting(resting)) => {
+                info!("HL: {:.1} @ ${:.3} resting", rounded_size, rounded_price);
+                OrderOutcome::Resting { oid: resting.oid, cloid }
+            }
+            Some(ExchangeDataStatus::Error(msg))
+                if action.tif == TimeInForce::Alo && Self::is_post_only_rejection(msg) =>
+            {
+                warn!("HL: post-only {:.1} @ ${:.3} would cross, not placed", rounded_size, rounded_price);
+                OrderOutcome::WouldCross
+            }
+            Some(ExchangeDataStatus::Error(msg))
+                if action.tif == TimeInForce::Ioc && Self::is_ioc_no_match(msg) =>
+            {
+                warn!("HL: IOC {:.1} @ ${:.3} matched nothing", rounded_size, rounded_price);
+                OrderOutcome::NotFilled
+            }
+            Some(ExchangeDataStatus::Error(msg)) => {
+                error!("HL: {:.1} @ ${:.3} rejected: {}", rounded_size, rounded_price, msg);
+                return Err(HyperliquidExecutorError::Rejected {
+                    coin: action.coin.clone(),
+                    reason: msg.clone(),
+                }.into());
+            }
+            _ => {
+                info!("HL: {:.1} @ ${:.3} accepted, no fill reported", rounded_size, rounded_price);
+                OrderOutcome::Unreported { cloid }
+            }
+        })
+    }
+
+    /// ALO orders that would cross the book are rejected by the exchange rather than filled
+    fn is_post_only_rejection(msg: &str) -> bool {
+        msg.contains("Post only order would have immediately matched")
+    }
+
+    /// IOC orders with nothing to match are reported as an error status, not a zero fill
+    fn is_ioc_no_match(msg: &str) -> bool {
+        msg.contains("could not immediately match")
+    }
+}
+
+/// What the exchange did with one submitted order
+enum OrderOutcome {
+    Filled(HlFill),
+    Resting { oid: u64, cloid: Uuid },
+    /// Not placed: a post-only order that would have crossed
+    WouldCross,
+    /// IOC order that matched nothing
+    NotFilled,
+    /// Accepted without a fill or resting report in the response
+    Unreported { cloid: Uuid },
+}
+
+impl OrderOutcome {
+    fn into_fill(self) -> Option<HlFill> {
+        match self {
+            OrderOutcome::Filled(fill) => Some(fill),
+            _ => None,
+        }
+    }
+
+    /// Fill for a taker order, treating an unmatched IOC as an error
+    fn into_taker_fill(self, action: &HyperliquidOrderAction) -> Result<Option<HlFill>> {
+        match self {
+            OrderOutcome::NotFilled => Err(HyperliquidExecutorError::NotFilled {
+                coin: action.coin.clone(),
+                px: action.limit_px,
+            }.into()),
+            outcome => Ok(outcome.into_fill()),
+        }
     }
 }
 
+/// Book operations the repricing loop runs on, so it can be driven by a simulated market
 #[async_trait]
-impl Executor<HyperliquidOrderAction> for HyperliquidExecutor {
-    async fn execute(&self, action: HyperliquidOrderAction) -> Result<()> {
-        let client = ExchangeClient::new(
-            None,
-            self.signer.clone(),
-            Some(BaseUrl::Mainnet),
-            None,
-            None,
-        )
-        .await?;
-
-        // Get asset metadata for size decimals
-        let info_client = InfoClient::new(None, Some(BaseUrl::Mainnet)).await?;
-        let meta = info_client.meta().await?;
-        
-        let asset_info = meta.universe.iter()
-            .find(|asset| {
-                let pair = format!("{}/USDC", asset.name);
-                pair == action.coin || asset.name == action.coin
-            });
-        
-        let sz_decimals = asset_info.map(|info| info.sz_decimals as u32).unwrap_or(1);
-        
-        // Round size and price to HL requirements
-        let tick_size = 0.001; // HYPE/USDC tick size
-        let size_multiplier = 10_f64.powi(sz_decimals as i32);
-        let rounded_size = (action.size * size_multiplier).round() / size_multiplier;
-        let rounded_price = (action.limit_px / tick_size).round() * tick_size;
+trait MakerBook: Sync {
+    /// Price to post at: the top of our side, never past the action's limit
+    async fn target_price(&self, action: &HyperliquidOrderAction) -> Result<f64>;
+    /// Round, validate and send one order
+    async fn submit(&self, action: &HyperliquidOrderAction, size: f64, limit_px: f64) -> Result<OrderOutcome>;
+    async fn resolve_unreported(&self, coin: &str, cloid: Uuid) -> Result<OrderOutcome>;
+    /// True only if the exchange confirmed the cancel
+    async fn cancel(&self, coin: &str, oid: u64) -> bool;
+    /// Size still open on a (possibly cancelled) order, `fallback` if unknown
+    async fn open_size(&self, oid: u64, fallback: f64) -> f64;
+    /// The order is off the book
+    fn untrack(&self, cloid: Uuid);
+}
+
+/// The executor's live Hyperliquid book
+struct LiveBook<'a> {
+    executor: &'a HyperliquidExecutor,
+    client: &'a ExchangeClient,
+    info_client: &'a InfoClient,
+}
+
+#[async_trait]
+impl MakerBook for LiveBook<'_> {
+    async fn target_price(&self, action: &HyperliquidOrderAction) -> Result<f64> {
+        HyperliquidExecutor::maker_target_price(self.info_client, action).await
+    }
+
+    async fn submit(&self, action: &HyperliquidOrderAction, size: f64, limit_px: f64) -> Result<OrderOutcome> {
+        self.executor.submit(self.client, action, size, limit_px).await
+    }
+
+    async fn resolve_unreported(&self, coin: &str, cloid: Uuid) -> Result<OrderOutcome> {
+        self.executor.resolve_unreported(coin, cloid).await
+    }
+
+    async fn cancel(&self, coin: &str, oid: u64) -> bool {
+        HyperliquidExecutor::cancel_resting(self.client, coin, oid).await
+    }
+
+    async fn open_size(&self, oid: u64, fallback: f64) -> f64 {
+        HyperliquidExecutor::open_size(self.info_client, self.executor.user_address(), oid, fallback).await
+    }
+
+    fn untrack(&self, cloid: Uuid) {
+        self.executor.resting.untrack(cloid);
+    }
+}
+
+impl HyperliquidExecutor {
+    /// Place an order and return the fill, if the exchange reported one.
+    /// Post-only orders go through the repricing loop when one is configured; otherwise a
+    /// crossing post-only order fails with [HyperliquidExecutorError::PostOnlyWouldCross].
+    /// An IOC that matches nothing fails with [HyperliquidExecutorError::NotFilled]; a
+    /// partial IOC fill returns only the executed size.
+    pub async fn place_order(&self, action: HyperliquidOrderAction) -> Result<Option<HlFill>> {
+        let client = self.exchange_client().await?;
+        if let OrderMode::MarketIoc { max_slippage_bps } = action.mode {
+            let book = self.info_client().await?.l2_snapshot(action.coin.clone()).await?;
+            // Buys cross to the best ask, sells to the best bid
+            let touch = Self::best_price(&book.levels, action.is_buy)
+                .ok_or_else(|| anyhow::anyhow!("Empty HL book for {}", action.coin))?;
+            let action = Self::market_ioc(action, touch, max_slippage_bps);
+            let outcome = self.submit(client, &action, action.size, action.limit_px).await?;
+            return outcome.into_taker_fill(&action);
+        }
+        if action.tif == TimeInForce::Alo {
+            if let Some(policy) = self.reprice_policy {
+                return self.place_maker_with_repricing(client, action, policy).await;
+            }
+        }
+        match self.submit(client, &action, action.size, action.limit_px).await? {
+            OrderOutcome::WouldCross => Err(HyperliquidExecutorError::PostOnlyWouldCross {
+                coin: action.coin,
+                px: action.limit_px,
+            }.into()),
+            outcome => outcome.into_taker_fill(&action),
+        }
+    }
+
+    /// Signed exchange client, built on first use and shared by all later orders and by
+    /// the shutdown cancels
+    async fn exchange_client(&self) -> Result<&ExchangeClient> {
+        self.resting.client().await
+    }
+
+    async fn info_client(&self) -> Result<&InfoClient> {
+        self.info_client.get_or_try_init(|| async {
+            Ok(InfoClient::new(None, Some(BaseUrl::Mainnet)).await?)
+        })
+        .await
+    }
+
+    /// Round, validate and send one order with `action`'s side and TIF
+    async fn submit(
+        &self,
+        client: &ExchangeClient,
+        action: &HyperliquidOrderAction,
+        size: f64,
+        limit_px: f64,
+    ) -> Result<OrderOutcome> {
+        // Round size and price to HL requirements for this coin
+        let precision = self.asset_precision(&action.coin).await?;
+        let rounded_size = precision.round_size(size);
+        let rounded_price = precision.round_price(limit_px);
         
         let order_value = rounded_size * rounded_price;
-        if order_value < 10.0 {
+        if order_value < HL_MIN_ORDER_VALUE_USD {
             anyhow::bail!("Order value ${:.2} below HL minimum", order_value);
         }
 
-        let order = ClientOrderRequest {
-            asset: action.coin.clone(),
-            is_buy: action.is_buy,
-            reduce_only: false,
-            limit_px: rounded_price,
-            sz: rounded_size,
-            cloid: None,
-            order_type: ClientOrder::Limit(ClientLimit {
-                tif: "Ioc".to_string(),
-            }),
-        };
+        let cloid = Uuid::new_v4();
+        let order = Self::build_order_request(action, rounded_size, rounded_price, cloid);
 
-        let response = client.order(order, None).await?;
+        let response = match client.order(order, None).await {
+            Ok(response) => response,
+            Err(e) => return self.recover_unacknowledged(action, cloid, e.into()).await,
+        };
 
         match response {
             ExchangeResponseStatus::Ok(resp) => {
-                // Log fill info if available
-                if let Some(data) = &resp.data {
-                    if let Some(status) = data.statuses.first() {
-                        match status {
-                            hyperliquid_rust_sdk::ExchangeDataStatus::Filled(_) => {
-                                info!("HL: {:.1} @ ${:.3}", rounded_size, rounded_price);
-                            }
-                            _ => {
-                                info!("HL: {:.1} @ ${:.3}", rounded_size, rounded_price);
-                            }
-                        }
-                    }
+                let status = resp.data.as_ref().and_then(|data| data.statuses.first());
+                let outcome = Self::classify(status, action, rounded_size, rounded_price, cloid)?;
+                // An IOC order can't rest; anything else might still be on the book
+                if action.tif != TimeInForce::Ioc
+                    && matches!(outcome, OrderOutcome::Resting { .. } | OrderOutcome::Unreported { .. })
+                {
+                    self.resting.track(cloid, &action.coin);
                 }
-                Ok(())
+                Ok(outcome)
             }
             ExchangeResponseStatus::Err(e) => {
                 error!("HL: {:?}", e);
@@ -100,6 +765,609 @@ impl Executor<HyperliquidOrderAction> for HyperliquidExecutor {
             }
         }
     }
+
+    /// Look an order up by client order id; None if the exchange has no such order
+    async fn lookup_by_cloid(&self, cloid: Uuid) -> Result<Option<OrderLookup>> {
+        let request = serde_json::json!({
+            "type": "orderStatus",
+            "user": format!("{:#x}", self.user_address()),
+            "oid": format!("0x{}", cloid.simple()),
+        });
+        let body: serde_json::Value = self.http.post(HL_INFO_URL)
+            .json(&request)
+            .send().await?
+            .error_for_status()?
+            .json().await?;
+        OrderLookup::parse(&body)
+    }
+
+    /// An order call that errored (timeout, dropped connection) may still have reached
+    /// the exchange. Look it up by cloid: a placed order is reported as such, so callers
+    /// never send it twice; an unknown one returns `error`, and is safe to send again.
+    async fn recover_unacknowledged(
+        &self,
+        action: &HyperliquidOrderAction,
+        cloid: Uuid,
+        error: anyhow::Error,
+    ) -> Result<OrderOutcome> {
+        let lookup = match self.lookup_by_cloid(cloid).await {
+            Ok(lookup) => lookup,
+            Err(lookup_error) => {
+                error!("HL: {} order {} failed ({}) and its status lookup failed: {}", action.coin, cloid, error, lookup_error);
+                return Err(HyperliquidExecutorError::Unconfirmed { coin: action.coin.clone(), cloid }.into());
+            }
+        };
+        let Some(lookup) = lookup else {
+            return Err(error);
+        };
+        warn!("HL: {} order {} failed ({}) but the exchange has it as {}", action.coin, cloid, error, lookup.status);
+        let outcome = lookup.into_outcome(cloid);
+        if action.tif != TimeInForce::Ioc && matches!(outcome, OrderOutcome::Resting { .. }) {
+            self.resting.track(cloid, &action.coin);
+        }
+        Ok(outcome)
+    }
+
+    /// Top price of one side of an L2 snapshot (`levels[0]` bids, `levels[1]` asks)
+    fn best_price(levels: &[Vec<hyperliquid_rust_sdk::Level>], asks: bool) -> Option<f64> {
+        levels.get(usize::from(asks))?.first()?.px.parse().ok()
+    }
+
+    /// Turn an action into an IOC limit `max_slippage_bps` through the opposite touch
+    fn market_ioc(action: HyperliquidOrderAction, touch: f64, max_slippage_bps: f64) -> HyperliquidOrderAction {
+        let slippage = max_slippage_bps / 10000.0;
+        let limit_px = if action.is_buy { touch * (1.0 + slippage) } else { touch * (1.0 - slippage) };
+        HyperliquidOrderAction { limit_px, tif: TimeInForce::Ioc, mode: OrderMode::Limit, ..action }
+    }
+
+    /// Best price on our own side of the book, never more aggressive than the action's limit
+    async fn maker_target_price(info_client: &InfoClient, action: &HyperliquidOrderAction) -> Result<f64> {
+        let book = info_client.l2_snapshot(action.coin.clone()).await?;
+        let best = Self::best_price(&book.levels, !action.is_buy)
+            .ok_or_else(|| anyhow::anyhow!("Empty HL book for {}", action.coin))?;
+
+        Ok(if action.is_buy { best.min(action.limit_px) } else { best.max(action.limit_px) })
+    }
+
+    /// Size still open on a (possibly cancelled) order
+    async fn open_size(info_client: &InfoClient, user: Address, oid: u64, fallback: f64) -> f64 {
+        match info_client.query_order_by_oid(user, oid).await {
+            Ok(resp) => match resp.order {
+                Some(info) if info.status == "filled" => 0.0,
+                Some(info) => info.order.sz.parse().unwrap_or(fallback),
+                None => fallback,
+            },
+            Err(e) => {
+                warn!("HL: order status for {} unavailable: {}", oid, e);
+                fallback
+            }
+        }
+    }
+
+    /// Cancel a resting order; true only if the exchange confirmed the cancel
+    async fn cancel_resting(client: &ExchangeClient, coin: &str, oid: u64) -> bool {
+        let cancel = ClientCancelRequest { asset: coin.to_string(), oid };
+        match client.cancel(cancel, None).await {
+            Ok(ExchangeResponseStatus::Ok(resp)) => match resp.data.as_ref().and_then(|data| data.statuses.first()) {
+                Some(ExchangeDataStatus::Error(msg)) => {
+                    warn!("HL: cancel {} not accepted: {}", oid, msg);
+                    false
+                }
+                _ => true,
+            },
+            Ok(ExchangeResponseStatus::Err(e)) => {
+                warn!("HL: cancel {} failed: {}", oid, e);
+                false
+            }
+            Err(e) => {
+                warn!("HL: cancel {} failed: {}", oid, e);
+                false
+            }
+        }
+    }
+
+    /// What an order reported without a fill or resting status amounts to, looked up by
+    /// cloid; an order the exchange doesn't know was never placed
+    async fn resolve_unreported(&self, coin: &str, cloid: Uuid) -> Result<OrderOutcome> {
+        match self.lookup_by_cloid(cloid).await {
+            Ok(Some(lookup)) => Ok(lookup.into_outcome(cloid)),
+            Ok(None) => Ok(OrderOutcome::NotFilled),
+            Err(e) => {
+                warn!("HL: status of {} order {} unavailable: {}", coin, cloid, e);
+                Err(HyperliquidExecutorError::Unconfirmed { coin: coin.to_string(), cloid }.into())
+            }
+        }
+    }
+
+    /// Report what already filled instead of failing a partly filled hedge
+    fn partial_or_err(fills: &[HlFill], coin: &str, error: anyhow::Error) -> Result<Option<HlFill>> {
+        if fills.is_empty() {
+            return Err(error);
+        }
+        warn!("HL: {} hedge stopped after a partial fill: {}", coin, error);
+        Ok(HlFill::combine(fills))
+    }
+
+    /// Run the repricing loop against the live book
+    async fn place_maker_with_repricing(
+        &self,
+        client: &ExchangeClient,
+        action: HyperliquidOrderAction,
+        policy: RepricePolicy,
+    ) -> Result<Option<HlFill>> {
+        let info_client = self.info_client().await?;
+        let precision = self.asset_precision(&action.coin).await?;
+        let book = LiveBook { executor: self, client, info_client };
+        Self::reprice_maker(&book, action, policy, precision).await
+    }
+
+    /// Keep a post-only hedge pegged to the top of our side of the book: each round posts at
+    /// the current target, waits, then cancels and reposts the unfilled remainder. Once the
+    /// reprice budget or deadline is spent, the remainder crosses with IOC at the action's limit.
+    /// Reposting stops early if a cancel isn't confirmed or an order's state can't be resolved,
+    /// so at most one order is ever live; a remainder under HL's minimum is left unhedged.
+    async fn reprice_maker(
+        book: &impl MakerBook,
+        action: HyperliquidOrderAction,
+        policy: RepricePolicy,
+        precision: AssetPrecision,
+    ) -> Result<Option<HlFill>> {
+        let deadline = Instant::now() + policy.deadline;
+        let below_minimum = |size: f64, px: f64| precision.round_size(size) * precision.round_price(px) < HL_MIN_ORDER_VALUE_USD;
+
+        let mut fills = Vec::new();
+        let mut remaining = action.size;
+
+        for attempt in 0..=policy.max_reprices {
+            if Instant::now() >= deadline || precision.round_size(remaining) <= 0.0 {
+                break;
+            }
+            if attempt > 0 {
+                info!("HL: repricing {} (attempt {}/{})", action.coin, attempt, policy.max_reprices);
+            }
+
+            let target = match book.target_price(&action).await {
+                Ok(target) => target,
+                Err(e) => return Self::partial_or_err(&fills, &action.coin, e),
+            };
+            if !fills.is_empty() && below_minimum(remaining, target) {
+                break;
+            }
+            let outcome = match book.submit(&action, remaining, target).await {
+                Ok(OrderOutcome::Unreported { cloid }) => book.resolve_unreported(&action.coin, cloid).await,
+                outcome => outcome,
+            };
+            match outcome {
+                Ok(OrderOutcome::Filled(fill)) => {
+                    remaining -= fill.total_sz;
+                    fills.push(fill);
+                }
+                Ok(OrderOutcome::Resting { oid, cloid }) => {
+                    let wait = policy.interval.min(deadline.saturating_duration_since(Instant::now()));
+                    tokio::time::sleep(wait).await;
+
+                    let cancelled = book.cancel(&action.coin, oid).await;
+                    let open = book.open_size(oid, remaining).await;
+                    let executed = remaining - open;
+                    if executed > 0.0 {
+                        fills.push(HlFill { oid, total_sz: executed, avg_px: precision.round_price(target) });
+                    }
+                    remaining = open;
+                    if cancelled || open <= 0.0 {
+                        book.untrack(cloid);
+                    } else {
+                        warn!("HL: {} order {} may still be resting with {:.4} open - not reposting", action.coin, oid, open);
+                        return Ok(HlFill::combine(&fills));
+                    }
+                }
+                // The book moved through our price; re-read it on the next round
+                Ok(OrderOutcome::WouldCross | OrderOutcome::NotFilled) => {}
+                Ok(OrderOutcome::Unreported { .. }) => unreachable!("resolved above"),
+                Err(e) => return Self::partial_or_err(&fills, &action.coin, e),
+            }
+        }
+
+        if precision.round_size(remaining) > 0.0 {
+            let taker = HyperliquidOrderAction { tif: TimeInForce::Ioc, ..action };
+            if !fills.is_empty() && below_minimum(remaining, taker.limit_px) {
+                warn!("HL: {:.4} {} left after repricing is below the ${} minimum - not crossing",
+                    remaining, taker.coin, HL_MIN_ORDER_VALUE_USD);
+                return Ok(HlFill::combine(&fills));
+            }
+            warn!("HL: maker hedge unfilled after repricing, crossing {:.4} {}", remaining, taker.coin);
+            match book.submit(&taker, remaining, taker.limit_px).await {
+                Ok(outcome) if fills.is_empty() => return outcome.into_taker_fill(&taker),
+                Ok(outcome) => fills.extend(outcome.into_fill()),
+                Err(e) => return Self::partial_or_err(&fills, &taker.coin, e),
+            }
+        }
+
+        Ok(HlFill::combine(&fills))
+    }
+}
+
+#[async_trait]
+impl HedgeVenue for HyperliquidExecutor {
+    async fn place_order(&self, action: HyperliquidOrderAction) -> Result<Option<HlFill>> {
+        HyperliquidExecutor::place_order(self, action).await
+    }
 }
 
+#[async_trait]
+impl Executor<HyperliquidOrderAction> for HyperliquidExecutor {
+    async fn execute(&self, action: HyperliquidOrderAction) -> Result<()> {
+        self.place_order(action).await.map(|_| ())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use super::*;
+
+    fn order(tif: TimeInForce) -> HyperliquidOrderAction {
+        HyperliquidOrderAction {
+            coin: "HYPE".to_string(),
+            is_buy: true,
+            size: 2.0,
+            limit_px: 40.0,
+            tif,
+            mode: OrderMode::Limit,
+            reduce_only: false,
+        }
+    }
+
+    /// Outcome of the first status in an order response, as the exchange sends it
+    fn classify(status: serde_json::Value, tif: TimeInForce) -> Result<OrderOutcome> {
+        let status: ExchangeDataStatus = serde_json::from_value(status).unwrap();
+        HyperliquidExecutor::classify(Some(&status), &order(tif), 2.0, 40.0, Uuid::nil())
+    }
+
+    #[test]
+    fn order_statuses_map_to_outcomes() {
+        let partial = serde_json::json!({ "filled": { "totalSz": "1.25", "avgPx": "39.98", "oid": 9 } });
+        match classify(partial, TimeInForce::Ioc).unwrap() {
+            OrderOutcome::Filled(fill) => assert_eq!(fill, HlFill { oid: 9, total_sz: 1.25, avg_px: 39.98 }),
+            _ => panic!("filled status should report the executed size"),
+        }
+
+        let resting = serde_json::json!({ "resting": { "oid": 10 } });
+        assert!(matches!(classify(resting, TimeInForce::Gtc).unwrap(), OrderOutcome::Resting { oid: 10, .. }));
+
+        let no_match = serde_json::json!({ "error": "Order could not immediately match against any resting orders. asset=135" });
+        assert!(matches!(classify(no_match.clone(), TimeInForce::Ioc).unwrap(), OrderOutcome::NotFilled));
+        // An unmatched IOC is an error for the caller, distinct from a rejection
+        let not_filled = classify(no_match, TimeInForce::Ioc).unwrap().into_taker_fill(&order(TimeInForce::Ioc)).unwrap_err();
+        assert!(matches!(not_filled.downcast_ref(), Some(HyperliquidExecutorError::NotFilled { .. })), "{}", not_filled);
+
+        let would_cross = serde_json::json!({ "error": "Post only order would have immediately matched, bbo was 39.99@40.01. asset=135" });
+        assert!(matches!(classify(would_cross, TimeInForce::Alo).unwrap(), OrderOutcome::WouldCross));
+
+        let rejected = classify(serde_json::json!({ "error": "Insufficient margin to place order. asset=135" }), TimeInForce::Ioc);
+        let rejected = rejected.err().unwrap();
+        assert!(matches!(rejected.downcast_ref(), Some(HyperliquidExecutorError::Rejected { .. })), "{}", rejected);
+
+        let unparseable = serde_json::json!({ "filled": { "totalSz": "?", "avgPx": "39.98", "oid": 9 } });
+        assert!(matches!(classify(unparseable, TimeInForce::Ioc).unwrap(), OrderOutcome::Unreported { .. }));
+        let unreported = HyperliquidExecutor::classify(None, &order(TimeInForce::Gtc), 2.0, 40.0, Uuid::nil()).unwrap();
+        assert!(matches!(unreported, OrderOutcome::Unreported { .. }));
+    }
+
+    #[test]
+    fn order_lookup_parses_status_responses() {
+        let unknown = serde_json::json!({ "status": "unknownOid" });
+        assert_eq!(OrderLookup::parse(&unknown).unwrap(), None);
+
+        let partly_filled = serde_json::json!({
+            "status": "order",
+            "order": {
+                "order": { "coin": "HYPE", "side": "A", "limitPx": "39.5", "sz": "0.75", "oid": 77, "origSz": "2.0" },
+                "status": "canceled",
+                "statusTimestamp": 1_700_000_000_000u64,
+            },
+        });
+        let lookup = OrderLookup::parse(&partly_filled).unwrap().unwrap();
+        assert_eq!(lookup.oid, 77);
+        match lookup.into_outcome(Uuid::nil()) {
+            OrderOutcome::Filled(fill) => assert_eq!(fill, HlFill { oid: 77, total_sz: 1.25, avg_px: 39.5 }),
+            _ => panic!("cancelled order with fills should report them"),
+        }
+
+        assert!(OrderLookup::parse(&serde_json::json!({ "status": "error" })).is_err());
+    }
 
+    fn request_tif(request: &ClientOrderRequest) -> &str {
+        match &request.order_type {
+            ClientOrder::Limit(limit) => &limit.tif,
+            _ => panic!("hedge orders are always limit orders"),
+        }
+    }
+
+    #[test]
+    fn order_requests_carry_the_time_in_force() {
+        let cloid = Uuid::from_u128(7);
+        for (tif, wire) in [(TimeInForce::Ioc, "Ioc"), (TimeInForce::Gtc, "Gtc"), (TimeInForce::Alo, "Alo")] {
+            let request = HyperliquidExecutor::build_order_request(&order(tif), 1.5, 39.9, cloid);
+            assert_eq!(request_tif(&request), wire);
+            assert_eq!(request.asset, "HYPE");
+            assert!(request.is_buy && !request.reduce_only);
+            assert_eq!((request.sz, request.limit_px, request.cloid), (1.5, 39.9, Some(cloid)));
+        }
+
+        let reduce = HyperliquidOrderAction { reduce_only: true, ..order(TimeInForce::Ioc) };
+        assert!(HyperliquidExecutor::build_order_request(&reduce, 1.5, 39.9, cloid).reduce_only);
+    }
+
+    #[test]
+    fn market_ioc_crosses_the_touch_by_at_most_the_slippage() {
+        let market = HyperliquidOrderAction { mode: OrderMode::MarketIoc { max_slippage_bps: 50.0 }, ..order(TimeInForce::Alo) };
+
+        let buy = HyperliquidExecutor::market_ioc(market.clone(), 40.0, 50.0);
+        assert!((buy.limit_px - 40.2).abs() < 1e-9, "{}", buy.limit_px);
+        assert_eq!((buy.tif, buy.mode), (TimeInForce::Ioc, OrderMode::Limit));
+        let request = HyperliquidExecutor::build_order_request(&buy, buy.size, buy.limit_px, Uuid::nil());
+        assert_eq!(request_tif(&request), "Ioc");
+
+        let sell = HyperliquidExecutor::market_ioc(HyperliquidOrderAction { is_buy: false, ..market }, 40.0, 50.0);
+        assert!((sell.limit_px - 39.8).abs() < 1e-9, "{}", sell.limit_px);
+        assert_eq!(sell.tif, TimeInForce::Ioc);
+    }
+
+    #[test]
+    fn limit_orders_keep_the_decision_price() {
+        let limit = order(TimeInForce::Gtc);
+        let request = HyperliquidExecutor::build_order_request(&limit, limit.size, limit.limit_px, Uuid::nil());
+        assert_eq!((request.sz, request.limit_px), (2.0, 40.0));
+        assert_eq!(request_tif(&request), "Gtc");
+    }
+
+    /// Perp universe as `meta` lists it: HYPE trades in 0.01, BTC in 0.00001
+    const PERPS: [(&str, u32); 2] = [("HYPE", 2), ("BTC", 5)];
+
+    #[test]
+    fn precision_comes_from_each_coins_metadata() {
+        let hype = perp_precision(PERPS.into_iter(), "HYPE").unwrap();
+        assert_eq!(hype, AssetPrecision { sz_decimals: 2, max_price_decimals: 4 });
+        assert_eq!(hype.round_size(1.23456), 1.23);
+        assert_eq!(hype.round_price(39.87654), 39.877);
+
+        let btc = perp_precision(PERPS.into_iter(), "BTC").unwrap();
+        assert_eq!(btc, AssetPrecision { sz_decimals: 5, max_price_decimals: 1 });
+        assert_eq!(btc.round_size(0.0123456), 0.01235);
+        assert_eq!(btc.round_price(97_123.45), 97_123.0);
+
+        assert!(perp_precision(PERPS.into_iter(), "DOGE").is_none());
+    }
+
+    #[test]
+    fn prices_round_to_five_significant_figures_within_the_decimal_cap() {
+        // Spot allows 8 - szDecimals decimals, perps 6 - szDecimals
+        let spot = AssetPrecision::for_coin("HYPE/USDC", 2);
+        assert_eq!(spot, AssetPrecision { sz_decimals: 2, max_price_decimals: 6 });
+        assert_eq!(spot.round_price(0.001234567), 0.001235);
+        assert_eq!(spot.round_price(40.123456), 40.123);
+
+        let perp = AssetPrecision::for_coin("HYPE", 2);
+        assert_eq!(perp.round_price(0.001234567), 0.0012);
+        assert_eq!(perp.round_price(40.123456), 40.123);
+
+        // Wide size steps leave no price decimals; whole prices are always accepted
+        let coarse = AssetPrecision::perp(6);
+        assert_eq!(coarse.max_price_decimals, 0);
+        assert_eq!(coarse.round_price(1.5), 2.0);
+        assert_eq!(AssetPrecision::perp(0).round_price(123_456.7), 123_457.0);
+        assert_eq!(AssetPrecision::perp(0).round_size(2.6), 3.0);
+    }
+
+    /// Market whose best bid rises 10 cents on every read and where nothing ever trades
+    /// against a resting order; IOC orders fill in full at their limit
+    #[derive(Default)]
+    struct DriftingBook {
+        reads: Mutex<u32>,
+        posts: Mutex<Vec<(TimeInForce, f64)>>,
+        cancels: Mutex<u32>,
+    }
+
+    #[async_trait]
+    impl MakerBook for DriftingBook {
+        async fn target_price(&self, action: &HyperliquidOrderAction) -> Result<f64> {
+            let mut reads = self.reads.lock().unwrap();
+            let bid = 39.5 + 0.1 * *reads as f64;
+            *reads += 1;
+            Ok(bid.min(action.limit_px))
+        }
+
+        async fn submit(&self, action: &HyperliquidOrderAction, size: f64, limit_px: f64) -> Result<OrderOutcome> {
+            let mut posts = self.posts.lock().unwrap();
+            posts.push((action.tif, limit_px));
+            Ok(match action.tif {
+                TimeInForce::Alo => OrderOutcome::Resting { oid: posts.len() as u64, cloid: Uuid::nil() },
+                _ => OrderOutcome::Filled(HlFill { oid: posts.len() as u64, total_sz: size, avg_px: limit_px }),
+            })
+        }
+
+        async fn resolve_unreported(&self, _coin: &str, _cloid: Uuid) -> Result<OrderOutcome> {
+            unreachable!("every order is reported")
+        }
+
+        async fn cancel(&self, _coin: &str, _oid: u64) -> bool {
+            *self.cancels.lock().unwrap() += 1;
+            true
+        }
+
+        async fn open_size(&self, _oid: u64, fallback: f64) -> f64 {
+            fallback
+        }
+
+        fn untrack(&self, _cloid: Uuid) {}
+    }
+
+    #[tokio::test]
+    async fn maker_hedge_follows_a_drifting_market_then_crosses() {
+        let book = DriftingBook::default();
+        let policy = RepricePolicy { max_reprices: 2, interval: Duration::from_millis(1), deadline: Duration::from_secs(5) };
+        let fill = HyperliquidExecutor::reprice_maker(&book, order(TimeInForce::Alo), policy, AssetPrecision::perp(2))
+            .await
+            .unwrap()
+            .unwrap();
+
+        // Posted at each new bid, every post cancelled, then the remainder crossed at the limit
+        let posts = book.posts.lock().unwrap();
+        assert_eq!(posts.len(), 4);
+        for (post, bid) in posts.iter().zip([39.5, 39.6, 39.7]) {
+            assert_eq!(post.0, TimeInForce::Alo);
+            assert!((post.1 - bid).abs() < 1e-9, "{:?}", post);
+        }
+        assert_eq!(posts[3], (TimeInForce::Ioc, 40.0));
+        assert_eq!(*book.cancels.lock().unwrap(), 3);
+        assert_eq!((fill.total_sz, fill.avg_px), (2.0, 40.0));
+    }
+
+    fn listing<'a>(name: &'a str, index: u32, base: &'a str, sz_decimals: u32) -> SpotListing<'a> {
+        SpotListing { name, index: format!("@{}", index), base, quote: "USDC", sz_decimals }
+    }
+
+    #[test]
+    fn hype_usdc_resolves_to_its_spot_listing() {
+        // Only canonical pairs are listed by name; HYPE/USDC is listed as "@107"
+        let spot = || [listing("PURR/USDC", 0, "PURR", 0), listing("@107", 107, "HYPE", 2)].into_iter();
+        assert!(is_spot_coin("HYPE/USDC") && is_spot_coin("@107") && !is_spot_coin("HYPE"));
+
+        for coin in ["HYPE/USDC", "@107"] {
+            assert_eq!(spot_precision(spot(), coin), Some(AssetPrecision::spot(2)), "{}", coin);
+        }
+        assert_eq!(spot_precision(spot(), "PURR/USDC"), Some(AssetPrecision::spot(0)));
+        assert!(spot_precision(spot(), "HYPE/USDT").is_none());
+        // Spot prices keep 8 - szDecimals decimals, two more than the perp
+        assert_eq!(AssetPrecision::spot(2).max_price_decimals, 6);
+    }
+
+    // Well-known dev key; nothing is sent
+    const DEV_KEY: &str = "0x59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d";
+
+    #[tokio::test]
+    async fn metadata_is_fetched_once_per_refresh() {
+        let executor = HyperliquidExecutor::new(DEV_KEY.to_string()).unwrap();
+        let counter = AtomicU32::new(0);
+        let fetches = &counter;
+        let fetch = || async move {
+            fetches.fetch_add(1, Ordering::SeqCst);
+            Ok(AssetPrecision::spot(2))
+        };
+
+        for _ in 0..3 {
+            assert_eq!(executor.cached_precision("HYPE/USDC", fetch()).await.unwrap(), AssetPrecision::spot(2));
+        }
+        assert_eq!(fetches.load(Ordering::SeqCst), 1);
+        // Cached per coin
+        executor.cached_precision("HYPE", fetch()).await.unwrap();
+        assert_eq!(fetches.load(Ordering::SeqCst), 2);
+
+        // Once stale it is queried again, and a failed refresh keeps the old entry
+        let executor = executor.with_meta_refresh(Duration::ZERO);
+        let failing = async { Err(anyhow::anyhow!("meta unavailable")) };
+        assert_eq!(executor.cached_precision("HYPE/USDC", failing).await.unwrap(), AssetPrecision::spot(2));
+        executor.cached_precision("HYPE/USDC", fetch()).await.unwrap();
+        assert_eq!(fetches.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn fallback_precision_is_not_cached() {
+        let executor = HyperliquidExecutor::new(DEV_KEY.to_string()).unwrap().with_fallback_precision(AssetPrecision::perp(1));
+        let failing = async { Err(anyhow::anyhow!("meta unavailable")) };
+        assert_eq!(executor.cached_precision("HYPE", failing).await.unwrap(), AssetPrecision::perp(1));
+        // Metadata is tried again on the next order
+        assert_eq!(executor.cached_precision("HYPE", async { Ok(AssetPrecision::perp(2)) }).await.unwrap(), AssetPrecision::perp(2));
+
+        let strict = HyperliquidExecutor::new(DEV_KEY.to_string()).unwrap();
+        assert!(strict.cached_precision("HYPE", async { Err(anyhow::anyhow!("meta unavailable")) }).await.is_err());
+    }
+
+    /// Exchange connection that records cancels; orders in `filled` are already gone
+    struct FakeConnection {
+        cancels: Mutex<Vec<(String, Uuid)>>,
+        filled: Vec<Uuid>,
+    }
+
+    #[async_trait]
+    impl HlConnection for FakeConnection {
+        async fn connect(_signer: PrivateKeySigner, _vault_address: Option<Address>) -> Result<Self> {
+            Ok(Self { cancels: Mutex::new(Vec::new()), filled: vec![Uuid::from_u128(2)] })
+        }
+
+        async fn cancel_cloid(&self, coin: &str, cloid: Uuid) -> Result<bool> {
+            self.cancels.lock().unwrap().push((coin.to_string(), cloid));
+            Ok(!self.filled.contains(&cloid))
+        }
+    }
+
+    fn fake_resting() -> RestingOrders<FakeConnection> {
+        RestingOrders::new(DEV_KEY.parse().unwrap(), None)
+    }
+
+    #[tokio::test]
+    async fn cancel_all_cancels_every_tracked_order() {
+        let resting = fake_resting();
+        let (resting_cloid, filled_cloid) = (Uuid::from_u128(1), Uuid::from_u128(2));
+        resting.track(resting_cloid, "HYPE/USDC");
+        resting.track(filled_cloid, "HYPE/USDC");
+        resting.track(Uuid::from_u128(3), "HYPE/USDC");
+        resting.untrack(Uuid::from_u128(3));
+        assert_eq!(resting.len(), 2);
+
+        // The filled order's cancel is rejected and simply dropped
+        assert_eq!(resting.cancel_all().await.unwrap(), 1);
+        let mut cancels = resting.client().await.unwrap().cancels.lock().unwrap().clone();
+        cancels.sort();
+        assert_eq!(cancels, vec![("HYPE/USDC".to_string(), resting_cloid), ("HYPE/USDC".to_string(), filled_cloid)]);
+        assert!(resting.is_empty());
+        assert_eq!(resting.cancel_all().await.unwrap(), 0);
+    }
+
+    static CONNECTS: AtomicU32 = AtomicU32::new(0);
+
+    /// Connection that counts how often it is built, slow enough for callers to race
+    struct CountedConnection;
+
+    #[async_trait]
+    impl HlConnection for CountedConnection {
+        async fn connect(_signer: PrivateKeySigner, _vault_address: Option<Address>) -> Result<Self> {
+            CONNECTS.fetch_add(1, Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            Ok(Self)
+        }
+
+        async fn cancel_cloid(&self, _coin: &str, _cloid: Uuid) -> Result<bool> {
+            Ok(true)
+        }
+    }
+
+    #[tokio::test]
+    async fn exchange_client_is_constructed_once() {
+        let resting: RestingOrders<CountedConnection> = RestingOrders::new(DEV_KEY.parse().unwrap(), None);
+        let (a, b, c) = tokio::join!(resting.client(), resting.client(), resting.client());
+        assert!(a.is_ok() && b.is_ok() && c.is_ok());
+        resting.track(Uuid::from_u128(1), "HYPE/USDC");
+        resting.cancel_all().await.unwrap();
+        assert_eq!(CONNECTS.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn api_wallet_signs_for_the_on_chain_account() {
+        // Second well-known dev key, standing in for the on-chain PRIVATE_KEY
+        let account: PrivateKeySigner = "0xac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80".parse().unwrap();
+        let executor = HyperliquidExecutor::new(DEV_KEY.to_string()).unwrap().with_account_address(account.address());
+
+        // Orders are signed by the API wallet and reported against the account it acts for
+        assert_ne!(executor.signer.address(), account.address());
+        assert_eq!(executor.resting.signer.address(), executor.signer.address());
+        assert_eq!(executor.user_address(), account.address());
+
+        // A vault still takes precedence over the account
+        let vault = Address::repeat_byte(0xee);
+        let executor = executor.with_vault_address(vault);
+        assert_eq!(executor.user_address(), vault);
+        assert_eq!(executor.resting.vault_address, Some(vault));
+    }
+}
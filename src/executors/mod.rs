@@ -1,3 +1,7 @@
 pub mod arbitrage;
+pub mod binance;
 pub mod hyperliquid;
+pub mod null;
+pub mod retry;
 pub mod univ3;
+pub mod univ3_maker;
@@ -0,0 +1,43 @@
+use std::sync::{Arc, Mutex};
+
+use anyhow::Result;
+use async_trait::async_trait;
+use tracing::info;
+
+use crate::types::Executor;
+
+/// Executor that records actions instead of sending them (backtests, dry runs)
+pub struct NullExecutor<A> {
+    actions: Arc<Mutex<Vec<A>>>,
+}
+
+impl<A> NullExecutor<A> {
+    pub fn new() -> Self {
+        Self {
+            actions: Arc::new(Mutex::new(Vec::new())),
+        }
+    }
+
+    /// Shared handle to the recorded actions, usable after the executor is boxed
+    pub fn recorded(&self) -> Arc<Mutex<Vec<A>>> {
+        self.actions.clone()
+    }
+}
+
+impl<A> Default for NullExecutor<A> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl<A> Executor<A> for NullExecutor<A>
+where
+    A: Send + Sync + std::fmt::Debug + 'static,
+{
+    async fn execute(&self, action: A) -> Result<()> {
+        info!("📝 Recorded action: {:?}", action);
+        self.actions.lock().unwrap().push(action);
+        Ok(())
+    }
+}
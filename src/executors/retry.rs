@@ -0,0 +1,141 @@
+use std::marker::PhantomData;
+use std::time::Duration;
+
+use anyhow::Result;
+use async_trait::async_trait;
+
+use crate::types::Executor;
+use crate::utilities::retry::{is_retryable, is_terminal, with_retry_if, RetryPolicy};
+
+/// Error text fragments of failures that usually clear on a second try, on top of the
+/// unprocessed requests [is_retryable] accepts: timeouts, gateway errors, nonce races
+const TRANSIENT: &[&str] = &[
+    "timed out", "timeout", "deadline has elapsed", "502", "503", "504", "bad gateway",
+    "service unavailable", "nonce too low",
+];
+
+/// Never retried: the action may already have executed (an HL order whose status is
+/// unknown), or would execute worse than decided
+const FINAL: &[&str] = &["may have been placed", "slippage"];
+
+/// Whether a failed action is worth running again. Broader than [is_retryable]:
+/// timeouts and nonce races are included even though the first attempt can still land.
+pub fn is_transient(error: &anyhow::Error) -> bool {
+    let msg = format!("{:#}", error).to_lowercase();
+    if is_terminal(error) || FINAL.iter().any(|t| msg.contains(t)) {
+        return false;
+    }
+    is_retryable(error) || TRANSIENT.iter().any(|t| msg.contains(t))
+}
+
+/// Wraps an executor and re-runs actions that failed with an [is_transient] error, up to
+/// `max_attempts` runs with exponential backoff; everything else fails at once.
+///
+/// The whole action is re-executed, so only wrap executors whose actions are safe to
+/// repeat. A timed-out DEX swap may still be mined, so a retried one can swap twice; the
+/// Hyperliquid executor looks a failed order up by cloid first and reports an order it
+/// can't account for as unconfirmed, which is not retried. Multi-leg round trips are
+/// never safe to wrap.
+pub struct RetryExecutor<E, A> {
+    inner: E,
+    policy: RetryPolicy,
+    label: String,
+    _action: PhantomData<fn(A)>,
+}
+
+impl<E, A> RetryExecutor<E, A> {
+    /// Run each action at most `max_attempts` times; 1 disables retrying
+    pub fn new(inner: E, max_attempts: u32) -> Self {
+        Self {
+            inner,
+            policy: RetryPolicy { max_retries: max_attempts.saturating_sub(1), ..RetryPolicy::default() },
+            label: "Executor".to_string(),
+            _action: PhantomData,
+        }
+    }
+
+    /// Delay before the first retry, doubling up to `max` (default 100ms up to 2s)
+    pub fn with_backoff(mut self, initial: Duration, max: Duration) -> Self {
+        self.policy.initial_backoff = initial;
+        self.policy.max_backoff = max;
+        self
+    }
+
+    /// Name used for the wrapped executor in retry logs
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.label = label.into();
+        self
+    }
+}
+
+#[async_trait]
+impl<E, A> Executor<A> for RetryExecutor<E, A>
+where
+    E: Executor<A>,
+    A: Clone + Send + Sync + 'static,
+{
+    async fn execute(&self, action: A) -> Result<()> {
+        with_retry_if(&self.policy, &self.label, || self.inner.execute(action.clone()), is_transient).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use super::*;
+
+    /// Fails its first `failures` runs with `error`, then succeeds
+    struct Flaky {
+        failures: u32,
+        error: &'static str,
+        attempts: AtomicU32,
+    }
+
+    #[async_trait]
+    impl Executor<u32> for Flaky {
+        async fn execute(&self, _action: u32) -> Result<()> {
+            let attempt = self.attempts.fetch_add(1, Ordering::SeqCst);
+            if attempt < self.failures {
+                anyhow::bail!("{}", self.error);
+            }
+            Ok(())
+        }
+    }
+
+    fn retrying(failures: u32, error: &'static str) -> RetryExecutor<Flaky, u32> {
+        RetryExecutor::new(Flaky { failures, error, attempts: AtomicU32::new(0) }, 3)
+            .with_backoff(Duration::ZERO, Duration::ZERO)
+    }
+
+    #[tokio::test]
+    async fn fails_twice_then_succeeds_on_the_third_attempt() {
+        let executor = retrying(2, "request timed out");
+        executor.execute(1).await.unwrap();
+        assert_eq!(executor.inner.attempts.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn gives_up_after_max_attempts() {
+        let executor = retrying(5, "503 Service Unavailable");
+        assert!(executor.execute(1).await.is_err());
+        assert_eq!(executor.inner.attempts.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn non_transient_errors_fail_immediately() {
+        for error in ["execution reverted: Too little received", "max slippage exceeded", "HL HYPE order 0 may have been placed, status unknown"] {
+            let executor = retrying(2, error);
+            assert!(executor.execute(1).await.is_err());
+            assert_eq!(executor.inner.attempts.load(Ordering::SeqCst), 1, "{}", error);
+        }
+    }
+
+    #[test]
+    fn transient_covers_timeouts_and_nonce_races() {
+        assert!(is_transient(&anyhow::anyhow!("operation timed out")));
+        assert!(is_transient(&anyhow::anyhow!("nonce too low: next nonce 12, tx nonce 11")));
+        assert!(is_transient(&anyhow::anyhow!("HTTP 429 Too Many Requests")));
+        assert!(!is_transient(&anyhow::anyhow!("insufficient funds for gas * price + value")));
+    }
+}
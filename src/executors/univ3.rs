@@ -1,12 +1,14 @@
-use std::sync::Arc;
+use std::{fmt, sync::{atomic::{AtomicU64, Ordering}, Arc}, time::Duration};
 use anyhow::Result;
 use async_trait::async_trait;
-use tracing::info;
+use serde::{Deserialize, Serialize};
+use tracing::{error, info};
 use alloy::{
-    primitives::{aliases::{U160, U24}, Address, U256},
+    primitives::{address, aliases::{U160, U24}, Address, Bytes, TxHash, U256},
     providers::Provider,
     signers::local::PrivateKeySigner,
     sol,
+    sol_types::SolCall,
 };
 
 use crate::types::Executor;
@@ -24,12 +26,91 @@ sol! {
             uint160 sqrtPriceLimitX96;
         }
         
+        struct ExactOutputSingleParams {
+            address tokenIn;
+            address tokenOut;
+            uint24 fee;
+            address recipient;
+            uint256 amountOut;
+            uint256 amountInMaximum;
+            uint160 sqrtPriceLimitX96;
+        }
+        
         function exactInputSingle(ExactInputSingleParams calldata params) external payable returns (uint256 amountOut);
+        function exactOutputSingle(ExactOutputSingleParams calldata params) external payable returns (uint256 amountIn);
         function multicall(uint256 deadline, bytes[] calldata data) external payable returns (bytes[] memory results);
+        function unwrapWETH9(uint256 amountMinimum, address recipient) external payable;
+        function refundETH() external payable;
+    }
+
+    interface IERC20 {
+        event Transfer(address indexed from, address indexed to, uint256 value);
     }
 }
 
-#[derive(Debug, Clone)]
+/// How long to wait for a swap receipt before giving up on the fill report
+const RECEIPT_TIMEOUT_SECS: u64 = 30;
+
+/// SwapRouter02 sentinel recipient: keep output in the router for the next call
+const ADDRESS_THIS: Address = address!("0x0000000000000000000000000000000000000002");
+/// SwapRouter02 sentinel amountIn: spend the router's entire balance of tokenIn
+const CONTRACT_BALANCE: U256 = U256::ZERO;
+
+/// Wrapped HYPE on HyperEVM
+pub const WHYPE: Address = address!("0x5555555555555555555555555555555555555555");
+
+/// Why a preflight simulation stopped a swap from being broadcast
+#[derive(Debug)]
+pub enum DexSimulationError {
+    /// eth_call of the multicall reverted
+    Reverted(String),
+    /// Simulated output below the swap's own `amount_out_min`
+    BelowMinimum { simulated: U256, amount_out_min: U256 },
+    /// Simulated output short of the strategy's expected output by more than the tolerance
+    BelowExpected { simulated: U256, expected: U256, tolerance_bps: f64 },
+}
+
+impl fmt::Display for DexSimulationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DexSimulationError::Reverted(reason) => write!(f, "DEX simulation reverted: {}", reason),
+            DexSimulationError::BelowMinimum { simulated, amount_out_min } => {
+                write!(f, "DEX simulation out {} below amount_out_min {}", simulated, amount_out_min)
+            }
+            DexSimulationError::BelowExpected { simulated, expected, tolerance_bps } => {
+                write!(f, "DEX simulation out {} below expected {} (tolerance {} bps)", simulated, expected, tolerance_bps)
+            }
+        }
+    }
+}
+
+impl std::error::Error for DexSimulationError {}
+
+/// Why the gas guard refused to send a swap
+#[derive(Debug)]
+pub enum GasGuardError {
+    /// Suggested gas price above `max_gas_price_gwei`
+    AboveCap { gas_price_gwei: f64, cap_gwei: f64 },
+    /// Estimated gas cost would eat the whole expected gross profit
+    CostExceedsProfit { gas_cost_usd: f64, gross_profit_usd: f64 },
+}
+
+impl fmt::Display for GasGuardError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GasGuardError::AboveCap { gas_price_gwei, cap_gwei } => {
+                write!(f, "gas price {:.2} gwei above cap {} gwei", gas_price_gwei, cap_gwei)
+            }
+            GasGuardError::CostExceedsProfit { gas_cost_usd, gross_profit_usd } => {
+                write!(f, "gas cost ${:.4} exceeds expected gross profit ${:.4}", gas_cost_usd, gross_profit_usd)
+            }
+        }
+    }
+}
+
+impl std::error::Error for GasGuardError {}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UniV3SwapAction {
     pub token_in: Address,
     pub token_out: Address,
@@ -38,10 +119,93 @@ pub struct UniV3SwapAction {
     pub amount_out_min: U256,
 }
 
+/// Swap for exactly `amount_out`, spending at most `amount_in_max` (e.g. to match a
+/// filled hedge size)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UniV3ExactOutputAction {
+    pub token_in: Address,
+    pub token_out: Address,
+    pub fee: u32,
+    pub amount_out: U256,
+    pub amount_in_max: U256,
+}
+
+impl UniV3ExactOutputAction {
+    /// Buy exactly `amount_out`, allowing `slippage_bps` over the quoted input
+    pub fn from_quote(
+        token_in: Address,
+        token_out: Address,
+        fee: u32,
+        amount_out: U256,
+        quoted_in: U256,
+        slippage_bps: u32,
+    ) -> Self {
+        let amount_in_max = quoted_in * U256::from(10_000 + slippage_bps) / U256::from(10_000);
+        Self { token_in, token_out, fee, amount_out, amount_in_max }
+    }
+}
+
+/// One hop of a multi-hop route
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Hop {
+    pub token_in: Address,
+    pub token_out: Address,
+    pub fee: u32,
+    pub amount_out_min: U256,
+}
+
+/// Route where each hop's output feeds the next (e.g. USDC→WHYPE→token), executed atomically
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UniV3MultiHopAction {
+    pub amount_in: U256,
+    pub hops: Vec<Hop>,
+}
+
+/// Realized outcome of a mined swap, read from its receipt
+#[derive(Debug, Clone)]
+pub struct DexFill {
+    pub tx_hash: TxHash,
+    pub success: bool,
+    pub amount_in: U256,
+    /// Sum of `token_out` transfers to the signer (or the router, when auto-unwrapped)
+    pub amount_out: U256,
+    pub gas_used: u64,
+    pub effective_gas_price: u128,
+}
+
 pub struct UniV3Executor<P> {
     provider: Arc<P>,
     signer: PrivateKeySigner,
     router_address: Address,
+    /// When set, eth_call every multicall first and abort if it reverts or the simulated
+    /// output falls short of the expected output by more than this many bps
+    simulation_tolerance_bps: Option<f64>,
+    /// When set, swaps use native HYPE instead of this wrapped token: inputs are sent
+    /// as call value and outputs are unwrapped to the signer
+    wrapped_native: Option<Address>,
+    /// Assign nonces locally instead of leaving it to the provider, so concurrent
+    /// swaps from spawned actions never race for the same nonce
+    manual_nonce: bool,
+    /// Next nonce to use; `NONCE_UNSYNCED` until seeded from the pending count
+    next_nonce: AtomicU64,
+    /// Swaps not mined within this long revert instead of landing into a moved market
+    swap_deadline: Duration,
+    /// Refuse to send while the suggested gas price is above this (gwei)
+    max_gas_price_gwei: Option<f64>,
+    /// Where swap output is paid; the signer when unset. Inputs, approvals and the
+    /// native call value always come from the signer.
+    recipient: Option<Address>,
+}
+
+const NONCE_UNSYNCED: u64 = u64::MAX;
+const DEFAULT_SWAP_DEADLINE: Duration = Duration::from_secs(30);
+/// Typical gas of one router swap multicall, for pricing a send before it happens
+const SWAP_GAS_ESTIMATE: u64 = 200_000;
+
+/// Router multicall payload plus the native value it must carry
+struct EncodedBatch {
+    calls: Vec<Bytes>,
+    value: U256,
 }
 
 impl<P: Provider + 'static> UniV3Executor<P> {
@@ -51,45 +215,743 @@ impl<P: Provider + 'static> UniV3Executor<P> {
             provider,
             signer,
             router_address,
+            simulation_tolerance_bps: None,
+            wrapped_native: None,
+            manual_nonce: false,
+            next_nonce: AtomicU64::new(NONCE_UNSYNCED),
+            swap_deadline: DEFAULT_SWAP_DEADLINE,
+            max_gas_price_gwei: None,
+            recipient: None,
         })
     }
-}
 
-#[async_trait]
-impl<P: Provider + 'static> Executor<UniV3SwapAction> for UniV3Executor<P> {
-    async fn execute(&self, action: UniV3SwapAction) -> Result<()> {
-        let owner = self.signer.address();
-        
-        let deadline = U256::from(
+    /// Pay swap output to `recipient` (e.g. a settlement vault) instead of the signer
+    pub fn with_recipient(mut self, recipient: Address) -> Self {
+        self.recipient = Some(recipient);
+        self
+    }
+
+    /// Address swap output is paid to
+    pub fn recipient(&self) -> Address {
+        self.recipient.unwrap_or_else(|| self.signer.address())
+    }
+
+    /// Enable the gas guard ([Self::check_gas]) with this gas price cap
+    pub fn with_max_gas_price(mut self, cap_gwei: f64) -> Self {
+        self.max_gas_price_gwei = Some(cap_gwei);
+        self
+    }
+
+    /// With the gas guard enabled, fail with [GasGuardError] if the suggested gas price
+    /// is above the cap or a swap's estimated gas cost (at `native_px_usd`) exceeds
+    /// `gross_profit_usd`. Does nothing when the guard is off.
+    pub async fn check_gas(&self, gross_profit_usd: Option<f64>, native_px_usd: f64) -> Result<()> {
+        let Some(cap_gwei) = self.max_gas_price_gwei else {
+            return Ok(());
+        };
+        let gas_price = self.provider.get_gas_price().await?;
+        let gas_price_gwei = gas_price as f64 / 1e9;
+        if gas_price_gwei > cap_gwei {
+            return Err(GasGuardError::AboveCap { gas_price_gwei, cap_gwei }.into());
+        }
+        if let Some(gross_profit_usd) = gross_profit_usd {
+            let gas_cost_usd = gas_price as f64 * SWAP_GAS_ESTIMATE as f64 / 1e18 * native_px_usd;
+            if gas_cost_usd >= gross_profit_usd {
+                return Err(GasGuardError::CostExceedsProfit { gas_cost_usd, gross_profit_usd }.into());
+            }
+        }
+        Ok(())
+    }
+
+    /// How long after submission a swap may still execute (default 30s)
+    pub fn with_swap_deadline(mut self, deadline: Duration) -> Self {
+        self.swap_deadline = deadline;
+        self
+    }
+
+    /// Simulate swaps against the latest block before sending them
+    pub fn with_simulation(mut self, tolerance_bps: f64) -> Self {
+        self.simulation_tolerance_bps = Some(tolerance_bps);
+        self
+    }
+
+    /// Trade native HYPE on the DEX leg, wrapping/unwrapping `wrapped` (normally [WHYPE])
+    /// inside the router multicall. Swaps not touching `wrapped` are unaffected.
+    pub fn with_auto_wrap(mut self, wrapped: Address) -> Self {
+        self.wrapped_native = Some(wrapped);
+        self
+    }
+
+    /// Track nonces locally, seeded from the pending transaction count
+    pub fn with_manual_nonce(mut self) -> Self {
+        self.manual_nonce = true;
+        self
+    }
+
+    /// The signer's pending transaction count: the nonce its next new tx would take
+    pub async fn pending_nonce(&self) -> Result<u64> {
+        Ok(self.provider.get_transaction_count(self.signer.address()).pending().await?)
+    }
+
+    /// Re-seed the local nonce from the node's pending transaction count
+    pub async fn resync_nonce(&self) -> Result<u64> {
+        let pending = self.pending_nonce().await?;
+        self.next_nonce.store(pending, Ordering::SeqCst);
+        info!("DEX: nonce resynced to {}", pending);
+        Ok(pending)
+    }
+
+    /// Reserve the next nonce, seeding it on first use
+    async fn reserve_nonce(&self) -> Result<u64> {
+        if self.next_nonce.load(Ordering::SeqCst) == NONCE_UNSYNCED {
+            let pending = self.pending_nonce().await?;
+            // A concurrent send may have seeded it first; keep theirs
+            let _ = self.next_nonce.compare_exchange(NONCE_UNSYNCED, pending, Ordering::SeqCst, Ordering::SeqCst);
+        }
+        Ok(self.next_nonce.fetch_add(1, Ordering::SeqCst))
+    }
+
+    fn deadline(&self) -> Result<U256> {
+        Ok(U256::from(
             std::time::SystemTime::now()
                 .duration_since(std::time::UNIX_EPOCH)?
-                .as_secs() + 300
-        );
+                .as_secs() + self.swap_deadline.as_secs()
+        ))
+    }
+
+    /// eth_call the multicall for these swaps and decode each leg's `amountOut`
+    pub async fn simulate(&self, actions: &[UniV3SwapAction]) -> Result<Vec<U256>> {
+        let router = ISwapRouter02::new(self.router_address, &*self.provider);
+        let batch = self.encode_batch(actions);
+        let results = router
+            .multicall(self.deadline()?, batch.calls)
+            .from(self.signer.address())
+            .value(batch.value)
+            .call()
+            .await
+            .map_err(|e| DexSimulationError::Reverted(e.to_string()))?;
+
+        // Swap results come first; trailing unwrap calls return nothing
+        results.iter()
+            .take(actions.len())
+            .map(|result| ISwapRouter02::exactInputSingleCall::abi_decode_returns(result).map_err(Into::into))
+            .collect()
+    }
+
+    /// Abort before spending gas if the swaps would revert or under-deliver ([DexSimulationError])
+    async fn preflight(&self, actions: &[UniV3SwapAction], expected_out: Option<U256>) -> Result<()> {
+        let Some(tolerance_bps) = self.simulation_tolerance_bps else {
+            return Ok(());
+        };
+
+        let simulated = self.simulate(actions).await.inspect_err(|e| error!("{}", e))?;
+
+        for (action, amount_out) in actions.iter().zip(&simulated) {
+            if *amount_out < action.amount_out_min {
+                return Err(DexSimulationError::BelowMinimum {
+                    simulated: *amount_out,
+                    amount_out_min: action.amount_out_min,
+                }.into());
+            }
+        }
+
+        if let (Some(expected), Some(amount_out)) = (expected_out, simulated.last()) {
+            info!("DEX sim: expected {} | simulated {}", expected, amount_out);
+            let keep_bps = 10_000u64.saturating_sub(tolerance_bps.max(0.0).round() as u64);
+            let floor = expected * U256::from(keep_bps) / U256::from(10_000u64);
+            if *amount_out < floor {
+                return Err(DexSimulationError::BelowExpected {
+                    simulated: *amount_out,
+                    expected,
+                    tolerance_bps,
+                }.into());
+            }
+        }
+
+        Ok(())
+    }
+
+    fn encode_params(&self, params: ISwapRouter02::ExactInputSingleParams) -> Bytes {
+        let router = ISwapRouter02::new(self.router_address, &*self.provider);
+        router.exactInputSingle(params).calldata().to_owned()
+    }
+
+    fn is_wrapped_native(&self, token: Address) -> bool {
+        self.wrapped_native == Some(token)
+    }
+
+    /// Encode a route as chained `exactInputSingle` calls: intermediate outputs stay in
+    /// the router and the next hop spends the router's whole balance of that token.
+    /// Auto-wrap applies to the route's ends as it does to single swaps.
+    fn encode_multi_hop(&self, action: &UniV3MultiHopAction) -> Result<EncodedBatch> {
+        if action.hops.is_empty() {
+            anyhow::bail!("Empty multi-hop route");
+        }
+        for pair in action.hops.windows(2) {
+            if pair[0].token_out != pair[1].token_in {
+                anyhow::bail!("Hop output {} does not feed next hop input {}", pair[0].token_out, pair[1].token_in);
+            }
+        }
+
+        let last = action.hops.len() - 1;
+        let unwrap_output = self.is_wrapped_native(action.hops[last].token_out);
+        let final_recipient = if unwrap_output { ADDRESS_THIS } else { self.recipient() };
+        let mut calls: Vec<Bytes> = action.hops.iter().enumerate()
+            .map(|(i, hop)| self.encode_params(ISwapRouter02::ExactInputSingleParams {
+                tokenIn: hop.token_in,
+                tokenOut: hop.token_out,
+                fee: U24::from(hop.fee),
+                recipient: if i == last { final_recipient } else { ADDRESS_THIS },
+                amountIn: if i == 0 { action.amount_in } else { CONTRACT_BALANCE },
+                amountOutMinimum: hop.amount_out_min,
+                sqrtPriceLimitX96: U160::ZERO,
+            }))
+            .collect();
+        if unwrap_output {
+            let router = ISwapRouter02::new(self.router_address, &*self.provider);
+            calls.push(router.unwrapWETH9(action.hops[last].amount_out_min, self.recipient()).calldata().to_owned());
+        }
+
+        let value = if self.is_wrapped_native(action.hops[0].token_in) { action.amount_in } else { U256::ZERO };
+        Ok(EncodedBatch { calls, value })
+    }
+
+    /// Encode swaps in order as the `bytes[]` payload of a single router multicall.
+    /// With auto-wrap, native HYPE only enters and leaves at the ends, as in
+    /// [encode_multi_hop](Self::encode_multi_hop): a wrapped-native input to the first
+    /// swap is paid from call value, a wrapped-native output of the last swap is unwrapped
+    /// by one final call, and a wrapped-native output feeding the next swap stays in the
+    /// router, which that swap spends in full. Other wrapped-native legs trade the token.
+    fn encode_batch(&self, actions: &[UniV3SwapAction]) -> EncodedBatch {
+        let unwrap_output = actions.last().is_some_and(|last| self.is_wrapped_native(last.token_out));
+        // Whether swap i's wrapped-native output is left in the router for swap i + 1
+        let feeds_next = |i: usize| {
+            let action: &UniV3SwapAction = &actions[i];
+            self.is_wrapped_native(action.token_out)
+                && actions.get(i + 1).is_some_and(|next| next.token_in == action.token_out)
+        };
+
+        let last = actions.len().saturating_sub(1);
+        let mut calls: Vec<Bytes> = actions.iter().enumerate()
+            .map(|(i, action)| self.encode_params(ISwapRouter02::ExactInputSingleParams {
+                tokenIn: action.token_in,
+                tokenOut: action.token_out,
+                fee: U24::from(action.fee),
+                recipient: if feeds_next(i) || (i == last && unwrap_output) { ADDRESS_THIS } else { self.recipient() },
+                amountIn: if i > 0 && feeds_next(i - 1) { CONTRACT_BALANCE } else { action.amount_in },
+                amountOutMinimum: action.amount_out_min,
+                sqrtPriceLimitX96: U160::ZERO,
+            }))
+            .collect();
+
+        if unwrap_output {
+            let router = ISwapRouter02::new(self.router_address, &*self.provider);
+            calls.push(router.unwrapWETH9(actions[last].amount_out_min, self.recipient()).calldata().to_owned());
+        }
 
-        let params = ISwapRouter02::ExactInputSingleParams {
+        let value = match actions.first() {
+            Some(first) if self.is_wrapped_native(first.token_in) => first.amount_in,
+            _ => U256::ZERO,
+        };
+        EncodedBatch { calls, value }
+    }
+
+    /// Encode one `exactOutputSingle` call. With auto-wrap, a wrapped-native input is
+    /// paid from call value with the unspent part refunded, and a wrapped-native output
+    /// is unwrapped to the signer.
+    fn encode_exact_output(&self, action: &UniV3ExactOutputAction) -> EncodedBatch {
+        let router = ISwapRouter02::new(self.router_address, &*self.provider);
+        let unwrap_output = self.is_wrapped_native(action.token_out);
+        let mut calls = vec![router.exactOutputSingle(ISwapRouter02::ExactOutputSingleParams {
             tokenIn: action.token_in,
             tokenOut: action.token_out,
             fee: U24::from(action.fee),
-            recipient: owner,
-            amountIn: action.amount_in,
-            amountOutMinimum: action.amount_out_min,
+            recipient: if unwrap_output { ADDRESS_THIS } else { self.recipient() },
+            amountOut: action.amount_out,
+            amountInMaximum: action.amount_in_max,
             sqrtPriceLimitX96: U160::ZERO,
+        }).calldata().to_owned()];
+
+        if unwrap_output {
+            calls.push(router.unwrapWETH9(action.amount_out, self.recipient()).calldata().to_owned());
+        }
+        let value = if self.is_wrapped_native(action.token_in) {
+            calls.push(router.refundETH().calldata().to_owned());
+            action.amount_in_max
+        } else {
+            U256::ZERO
         };
+        EncodedBatch { calls, value }
+    }
+
+    /// Submit an exact-output swap; with simulation enabled, abort if it would revert
+    /// (e.g. needing more than `amount_in_max`)
+    pub async fn send_exact_output(&self, action: &UniV3ExactOutputAction) -> Result<TxHash> {
+        let batch = self.encode_exact_output(action);
+        if self.simulation_tolerance_bps.is_some() {
+            let router = ISwapRouter02::new(self.router_address, &*self.provider);
+            let results = router
+                .multicall(self.deadline()?, batch.calls.clone())
+                .from(self.signer.address())
+                .value(batch.value)
+                .call()
+                .await
+                .map_err(|e| DexSimulationError::Reverted(e.to_string()))?;
+            if let Some(result) = results.first() {
+                let amount_in = ISwapRouter02::exactOutputSingleCall::abi_decode_returns(result)?;
+                info!("DEX sim: {} out for {} in (max {})", action.amount_out, amount_in, action.amount_in_max);
+            }
+        }
+        self.send_multicall(batch.calls, batch.value).await
+    }
+
+    /// Execute several swaps atomically in one multicall transaction. Legs run in the
+    /// given order and the whole transaction reverts if any leg misses its `amount_out_min`.
+    pub async fn execute_batch(&self, actions: Vec<UniV3SwapAction>) -> Result<()> {
+        self.send_batch(actions).await.map(|_| ())
+    }
+
+    /// Submit swaps as one multicall and return the transaction hash without waiting
+    pub async fn send_batch(&self, actions: Vec<UniV3SwapAction>) -> Result<TxHash> {
+        if actions.is_empty() {
+            anyhow::bail!("Empty swap batch");
+        }
+        self.preflight(&actions, None).await?;
+        let batch = self.encode_batch(&actions);
+        self.send_multicall(batch.calls, batch.value).await
+    }
+
+    /// Submit one swap, checking the simulated output against the strategy's expectation
+    pub async fn send_swap(&self, action: UniV3SwapAction, expected_out: Option<U256>) -> Result<TxHash> {
+        let actions = vec![action];
+        self.preflight(&actions, expected_out).await?;
+        let batch = self.encode_batch(&actions);
+        self.send_multicall(batch.calls, batch.value).await
+    }
+
+    /// Submit a multi-hop route as one multicall
+    pub async fn send_multi_hop(&self, action: &UniV3MultiHopAction) -> Result<TxHash> {
+        let batch = self.encode_multi_hop(action)?;
+        self.send_multicall(batch.calls, batch.value).await
+    }
+
+    async fn send_multicall(&self, multicall_data: Vec<Bytes>, value: U256) -> Result<TxHash> {
+        let num_calls = multicall_data.len();
+        let owner = self.signer.address();
+        let deadline = self.deadline()?;
 
         let router = ISwapRouter02::new(self.router_address, &*self.provider);
-        let encoded_call = router.exactInputSingle(params).calldata().to_owned();
-        let multicall_data = vec![encoded_call.into()];
         
-        let pending_tx = router
+        let mut call = router
             .multicall(deadline, multicall_data)
             .from(owner)
-            .gas(500_000)
-            .send()
-            .await?;
+            .value(value)
+            .gas(500_000);
+        let nonce = if self.manual_nonce {
+            let nonce = self.reserve_nonce().await?;
+            call = call.nonce(nonce);
+            Some(nonce)
+        } else {
+            None
+        };
+
+        let pending_tx = match call.send().await {
+            Ok(pending_tx) => pending_tx,
+            Err(e) => {
+                // The reserved nonce may be unused or already taken; start over from the node
+                if self.manual_nonce {
+                    self.next_nonce.store(NONCE_UNSYNCED, Ordering::SeqCst);
+                }
+                return Err(e.into());
+            }
+        };
         
         let tx_hash = *pending_tx.tx_hash();
-        info!("DEX: 0x{:x}", tx_hash);
+        match nonce {
+            Some(nonce) => info!("DEX: 0x{:x} ({} calls, nonce {})", tx_hash, num_calls, nonce),
+            None => info!("DEX: 0x{:x} ({} calls)", tx_hash, num_calls),
+        }
         
-        Ok(())
+        Ok(tx_hash)
+    }
+
+    /// Wait for a submitted single swap to be mined and report what it actually did
+    pub async fn wait_for_fill(&self, tx_hash: TxHash, action: &UniV3SwapAction) -> Result<DexFill> {
+        let started = std::time::Instant::now();
+        let receipt = loop {
+            if let Some(receipt) = self.provider.get_transaction_receipt(tx_hash).await? {
+                break receipt;
+            }
+            if started.elapsed().as_secs() > RECEIPT_TIMEOUT_SECS {
+                anyhow::bail!("No receipt for 0x{:x} after {}s", tx_hash, RECEIPT_TIMEOUT_SECS);
+            }
+            tokio::time::sleep(Duration::from_millis(250)).await;
+        };
+
+        // Auto-unwrapped output lands in the router before being paid out natively
+        let recipient = if self.is_wrapped_native(action.token_out) { self.router_address } else { self.recipient() };
+        let amount_out = receipt.inner.logs().iter()
+            .filter(|log| log.address() == action.token_out)
+            .filter_map(|log| log.log_decode::<IERC20::Transfer>().ok())
+            .filter(|transfer| transfer.inner.data.to == recipient)
+            .fold(U256::ZERO, |total, transfer| total + transfer.inner.data.value);
+
+        // Exact-output swaps spend less than `amount_in`; ERC20 input is pulled from the
+        // signer, native input can't be seen in logs
+        let owner = self.signer.address();
+        let paid_in = receipt.inner.logs().iter()
+            .filter(|log| log.address() == action.token_in)
+            .filter_map(|log| log.log_decode::<IERC20::Transfer>().ok())
+            .filter(|transfer| transfer.inner.data.from == owner)
+            .fold(U256::ZERO, |total, transfer| total + transfer.inner.data.value);
+
+        Ok(DexFill {
+            tx_hash,
+            success: receipt.status(),
+            amount_in: if paid_in.is_zero() { action.amount_in } else { paid_in },
+            amount_out,
+            gas_used: receipt.gas_used,
+            effective_gas_price: receipt.effective_gas_price,
+        })
+    }
+}
+
+#[async_trait]
+impl<P: Provider + 'static> Executor<UniV3SwapAction> for UniV3Executor<P> {
+    async fn execute(&self, action: UniV3SwapAction) -> Result<()> {
+        self.check_gas(None, 0.0).await?;
+        self.execute_batch(vec![action]).await
+    }
+}
+
+/// Multi-leg routes (e.g. triangular cycles) plug in via `ExecutorMap` to this impl
+#[async_trait]
+impl<P: Provider + 'static> Executor<Vec<UniV3SwapAction>> for UniV3Executor<P> {
+    async fn execute(&self, actions: Vec<UniV3SwapAction>) -> Result<()> {
+        self.check_gas(None, 0.0).await?;
+        self.execute_batch(actions).await
+    }
+}
+
+#[async_trait]
+impl<P: Provider + 'static> Executor<UniV3ExactOutputAction> for UniV3Executor<P> {
+    async fn execute(&self, action: UniV3ExactOutputAction) -> Result<()> {
+        self.check_gas(None, 0.0).await?;
+        self.send_exact_output(&action).await.map(|_| ())
+    }
+}
+
+#[async_trait]
+impl<P: Provider + 'static> Executor<UniV3MultiHopAction> for UniV3Executor<P> {
+    async fn execute(&self, action: UniV3MultiHopAction) -> Result<()> {
+        self.check_gas(None, 0.0).await?;
+        self.send_multi_hop(&action).await.map(|_| ())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::providers::ProviderBuilder;
+    use axum::{routing::post, Json, Router};
+    use serde_json::{json, Value};
+
+    // Well-known dev key; nothing is sent
+    const DEV_KEY: &str = "0x59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d";
+    const USDC: Address = address!("0xb88339CB7199b77E23DB6E890353E22632Ba630f");
+
+    /// Serialize, read back and serialize again; the two encodings must match
+    fn assert_round_trip<T: Serialize + serde::de::DeserializeOwned>(value: &T) {
+        let json = serde_json::to_string(value).unwrap();
+        let back: T = serde_json::from_str(&json).unwrap();
+        assert_eq!(serde_json::to_string(&back).unwrap(), json);
+    }
+
+    fn executor() -> UniV3Executor<impl Provider> {
+        let provider = ProviderBuilder::new().connect_http("http://127.0.0.1:8545".parse().unwrap());
+        UniV3Executor::new(Arc::new(provider), DEV_KEY, Address::repeat_byte(0x77))
+            .unwrap()
+            .with_auto_wrap(WHYPE)
+    }
+
+    fn swap(token_in: Address, token_out: Address, amount_in: u64) -> UniV3SwapAction {
+        UniV3SwapAction { token_in, token_out, fee: 3000, amount_in: U256::from(amount_in), amount_out_min: U256::from(1u64) }
+    }
+
+    fn decode_swap(call: &Bytes) -> ISwapRouter02::ExactInputSingleParams {
+        ISwapRouter02::exactInputSingleCall::abi_decode(call).unwrap().params
+    }
+
+    #[test]
+    fn auto_wrap_pays_native_input_and_unwraps_output() {
+        let executor = executor();
+
+        let sell_native = executor.encode_batch(&[swap(WHYPE, USDC, 5_000)]);
+        assert_eq!(sell_native.value, U256::from(5_000u64));
+        assert_eq!(sell_native.calls.len(), 1);
+
+        let buy_native = executor.encode_batch(&[swap(USDC, WHYPE, 5_000)]);
+        assert_eq!(buy_native.value, U256::ZERO);
+        assert_eq!(decode_swap(&buy_native.calls[0]).recipient, ADDRESS_THIS);
+        assert_eq!(buy_native.calls[1][..4], ISwapRouter02::unwrapWETH9Call::SELECTOR);
+    }
+
+    #[test]
+    fn auto_wrap_keeps_intermediate_output_in_router() {
+        let executor = executor();
+        let batch = executor.encode_batch(&[swap(USDC, WHYPE, 5_000), swap(WHYPE, USDC, 9_999)]);
+
+        // No native value, no unwrap: WHYPE never leaves the router
+        assert_eq!(batch.value, U256::ZERO);
+        assert_eq!(batch.calls.len(), 2);
+        assert_eq!(decode_swap(&batch.calls[0]).recipient, ADDRESS_THIS);
+        let second = decode_swap(&batch.calls[1]);
+        assert_eq!(second.amountIn, CONTRACT_BALANCE);
+        assert_eq!(second.recipient, executor.recipient());
+    }
+
+    #[test]
+    fn swap_actions_round_trip() {
+        assert_round_trip(&UniV3SwapAction {
+            token_in: USDC,
+            token_out: WHYPE,
+            fee: 3000,
+            amount_in: U256::from(100_000_000u64),
+            amount_out_min: U256::MAX,
+        });
+        assert_round_trip(&UniV3ExactOutputAction::from_quote(USDC, WHYPE, 500, U256::from(10u64).pow(U256::from(18)), U256::from(1_000u64), 50));
+        assert_round_trip(&UniV3MultiHopAction {
+            amount_in: U256::from(42u64),
+            hops: vec![
+                Hop { token_in: USDC, token_out: WHYPE, fee: 500, amount_out_min: U256::ZERO },
+                Hop { token_in: WHYPE, token_out: USDC, fee: 3000, amount_out_min: U256::from(41u64) },
+            ],
+        });
+    }
+
+    #[test]
+    fn u256_beyond_u128_survives_round_trip() {
+        let amount = U256::from(u128::MAX) * U256::from(7u64);
+        let json = serde_json::to_string(&Hop { token_in: USDC, token_out: WHYPE, fee: 100, amount_out_min: amount }).unwrap();
+        let hop: Hop = serde_json::from_str(&json).unwrap();
+        assert_eq!(hop.amount_out_min, amount);
+        assert_eq!(hop.token_out, WHYPE);
+    }
+
+    const TOKEN: Address = Address::repeat_byte(0x99);
+
+    #[test]
+    fn two_hop_route_encodes_two_chained_calls() {
+        let executor = executor();
+        let route = UniV3MultiHopAction {
+            amount_in: U256::from(5_000u64),
+            hops: vec![
+                Hop { token_in: USDC, token_out: WHYPE, fee: 500, amount_out_min: U256::from(100u64) },
+                Hop { token_in: WHYPE, token_out: TOKEN, fee: 10_000, amount_out_min: U256::from(42u64) },
+            ],
+        };
+        let batch = executor.encode_multi_hop(&route).unwrap();
+        assert_eq!(batch.calls.len(), 2);
+        assert_eq!(batch.value, U256::ZERO);
+
+        let first = decode_swap(&batch.calls[0]);
+        assert_eq!((first.tokenIn, first.tokenOut, first.fee), (USDC, WHYPE, U24::from(500)));
+        assert_eq!((first.amountIn, first.amountOutMinimum), (U256::from(5_000u64), U256::from(100u64)));
+        // The intermediate WHYPE stays in the router for the second hop to spend in full
+        assert_eq!(first.recipient, ADDRESS_THIS);
+        let second = decode_swap(&batch.calls[1]);
+        assert_eq!((second.tokenIn, second.tokenOut, second.fee), (WHYPE, TOKEN, U24::from(10_000)));
+        assert_eq!((second.amountIn, second.amountOutMinimum), (CONTRACT_BALANCE, U256::from(42u64)));
+        assert_eq!(second.recipient, executor.recipient());
+
+        let broken = UniV3MultiHopAction { hops: vec![route.hops[1].clone(), route.hops[0].clone()], ..route };
+        assert!(executor.encode_multi_hop(&broken).is_err());
+    }
+
+    #[test]
+    fn batch_encodes_one_call_per_swap_in_order() {
+        let executor = executor();
+        let legs = [
+            UniV3SwapAction { amount_out_min: U256::from(11u64), ..swap(USDC, TOKEN, 1_000) },
+            UniV3SwapAction { amount_out_min: U256::from(22u64), ..swap(TOKEN, Address::repeat_byte(0x88), 2_000) },
+            UniV3SwapAction { amount_out_min: U256::from(33u64), ..swap(Address::repeat_byte(0x88), USDC, 3_000) },
+        ];
+        let batch = executor.encode_batch(&legs);
+        assert_eq!(batch.calls.len(), 3);
+        assert_eq!(batch.value, U256::ZERO);
+        for (call, leg) in batch.calls.iter().zip(&legs) {
+            assert_eq!(call[..4], ISwapRouter02::exactInputSingleCall::SELECTOR);
+            let params = decode_swap(call);
+            assert_eq!((params.tokenIn, params.tokenOut), (leg.token_in, leg.token_out));
+            assert_eq!((params.amountIn, params.amountOutMinimum), (leg.amount_in, leg.amount_out_min));
+            assert_eq!(params.recipient, executor.recipient());
+        }
+    }
+
+    fn decode_exact_output(call: &Bytes) -> ISwapRouter02::ExactOutputSingleParams {
+        ISwapRouter02::exactOutputSingleCall::abi_decode(call).unwrap().params
+    }
+
+    #[test]
+    fn each_swap_mode_encodes_its_router_function() {
+        let executor = executor();
+        let exact_in = executor.encode_batch(&[swap(USDC, TOKEN, 1_000)]);
+        assert_eq!(exact_in.calls[0][..4], ISwapRouter02::exactInputSingleCall::SELECTOR);
+
+        let action = UniV3ExactOutputAction { token_in: USDC, token_out: TOKEN, fee: 500, amount_out: U256::from(7u64), amount_in_max: U256::from(1_010u64) };
+        let exact_out = executor.encode_exact_output(&action);
+        assert_eq!(exact_out.calls.len(), 1);
+        assert_eq!(exact_out.calls[0][..4], ISwapRouter02::exactOutputSingleCall::SELECTOR);
+        let params = decode_exact_output(&exact_out.calls[0]);
+        assert_eq!((params.amountOut, params.amountInMaximum), (U256::from(7u64), U256::from(1_010u64)));
+        assert_eq!(params.recipient, executor.recipient());
+    }
+
+    #[test]
+    fn exact_output_with_native_legs_refunds_or_unwraps() {
+        let executor = executor();
+        let buy = UniV3ExactOutputAction::from_quote(WHYPE, USDC, 3000, U256::from(40_000_000u64), U256::from(1_000u64), 50);
+        let batch = executor.encode_exact_output(&buy);
+        // Native input: pay the maximum and take the unspent part back
+        assert_eq!(batch.value, U256::from(1_005u64));
+        assert_eq!(batch.calls.len(), 2);
+        assert_eq!(batch.calls[0][..4], ISwapRouter02::exactOutputSingleCall::SELECTOR);
+        assert_eq!(batch.calls[1][..4], ISwapRouter02::refundETHCall::SELECTOR);
+
+        let sell = UniV3ExactOutputAction { token_in: USDC, token_out: WHYPE, fee: 3000, amount_out: U256::from(5u64), amount_in_max: U256::from(300u64) };
+        let batch = executor.encode_exact_output(&sell);
+        assert_eq!(batch.value, U256::ZERO);
+        assert_eq!(decode_exact_output(&batch.calls[0]).recipient, ADDRESS_THIS);
+        assert_eq!(batch.calls[1][..4], ISwapRouter02::unwrapWETH9Call::SELECTOR);
+    }
+
+    type Sent = Arc<std::sync::Mutex<Vec<Value>>>;
+
+    /// Local JSON-RPC node that records each eth_sendTransaction request and answers it
+    /// with a hash, reports 5 pending transactions, and reverts every eth_call. Providers talking to it must not fill in
+    /// fields, so nothing else is asked.
+    async fn mock_node() -> (reqwest::Url, Sent) {
+        let sent = Sent::default();
+        let captured = sent.clone();
+        let app = Router::new().route("/", post(move |Json(request): Json<Value>| {
+            let captured = captured.clone();
+            async move {
+                let result = match request["method"].as_str() {
+                    Some("eth_sendTransaction") => {
+                        captured.lock().unwrap().push(request["params"][0].clone());
+                        json!(format!("0x{}", "ab".repeat(32)))
+                    }
+                    Some("eth_getTransactionCount") => json!("0x5"),
+                    Some("eth_call") => {
+                        let error = json!({ "code": 3, "message": "execution reverted: Too little received" });
+                        return Json(json!({ "jsonrpc": "2.0", "id": request["id"], "error": error }));
+                    }
+                    _ => Value::Null,
+                };
+                Json(json!({ "jsonrpc": "2.0", "id": request["id"], "result": result }))
+            }
+        }));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        (format!("http://{}", addr).parse().unwrap(), sent)
+    }
+
+    fn sent_multicall(tx: &Value) -> ISwapRouter02::multicallCall {
+        let input = tx.get("input").or_else(|| tx.get("data")).and_then(Value::as_str).unwrap();
+        ISwapRouter02::multicallCall::abi_decode(&input.parse::<Bytes>().unwrap()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn multicall_carries_the_configured_deadline() {
+        let (url, sent) = mock_node().await;
+        let provider = ProviderBuilder::new().disable_recommended_fillers().connect_http(url);
+        let executor = UniV3Executor::new(Arc::new(provider), DEV_KEY, Address::repeat_byte(0x77))
+            .unwrap()
+            .with_swap_deadline(Duration::from_secs(12));
+
+        let now = std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_secs();
+        executor.send_swap(swap(USDC, TOKEN, 1_000), None).await.unwrap();
+
+        let sent = sent.lock().unwrap();
+        assert_eq!(sent.len(), 1);
+        let multicall = sent_multicall(&sent[0]);
+        let deadline = multicall.deadline.to::<u64>();
+        assert!((now + 12..=now + 13).contains(&deadline), "deadline {} for now {}", deadline, now);
+        assert_eq!(multicall.data.len(), 1);
+    }
+
+    #[test]
+    fn recipient_override_receives_the_output() {
+        let vault = Address::repeat_byte(0xee);
+        let executor = executor().with_recipient(vault);
+        assert_eq!(executor.recipient(), vault);
+
+        let batch = executor.encode_batch(&[swap(USDC, TOKEN, 1_000)]);
+        assert_eq!(decode_swap(&batch.calls[0]).recipient, vault);
+
+        // Unwrapped native output is paid to the override too, not to the signer
+        let batch = executor.encode_batch(&[swap(USDC, WHYPE, 1_000)]);
+        let unwrap = ISwapRouter02::unwrapWETH9Call::abi_decode(&batch.calls[1]).unwrap();
+        assert_eq!(unwrap.recipient, vault);
+
+        let exact = UniV3ExactOutputAction { token_in: USDC, token_out: TOKEN, fee: 500, amount_out: U256::from(1u64), amount_in_max: U256::from(2u64) };
+        assert_eq!(decode_exact_output(&executor.encode_exact_output(&exact).calls[0]).recipient, vault);
+    }
+
+    /// Simulating executor on the mock node
+    async fn simulating_executor() -> (UniV3Executor<impl Provider>, Sent) {
+        let (url, sent) = mock_node().await;
+        let provider = ProviderBuilder::new().disable_recommended_fillers().connect_http(url);
+        let executor = UniV3Executor::new(Arc::new(provider), DEV_KEY, Address::repeat_byte(0x77))
+            .unwrap()
+            .with_simulation(50.0);
+        (executor, sent)
+    }
+
+    #[tokio::test]
+    async fn reverted_simulation_never_sends_the_swap() {
+        let (executor, sent) = simulating_executor().await;
+        let err = executor.send_swap(swap(USDC, TOKEN, 1_000), Some(U256::from(10u64))).await.unwrap_err();
+        assert!(matches!(err.downcast_ref(), Some(DexSimulationError::Reverted(_))), "{}", err);
+        assert!(sent.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn reverted_preflight_blocks_batches_and_exact_output() {
+        let (executor, sent) = simulating_executor().await;
+        let err = executor.send_batch(vec![swap(USDC, TOKEN, 1_000), swap(TOKEN, USDC, 2_000)]).await.unwrap_err();
+        assert!(matches!(err.downcast_ref(), Some(DexSimulationError::Reverted(_))), "{}", err);
+
+        let exact = UniV3ExactOutputAction { token_in: USDC, token_out: TOKEN, fee: 500, amount_out: U256::from(1u64), amount_in_max: U256::from(2u64) };
+        let err = executor.send_exact_output(&exact).await.unwrap_err();
+        assert!(matches!(err.downcast_ref(), Some(DexSimulationError::Reverted(_))), "{}", err);
+        assert!(sent.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn manual_nonces_increase_across_racing_swaps() {
+        let (url, sent) = mock_node().await;
+        let provider = ProviderBuilder::new().disable_recommended_fillers().connect_http(url);
+        let executor = UniV3Executor::new(Arc::new(provider), DEV_KEY, Address::repeat_byte(0x77))
+            .unwrap()
+            .with_manual_nonce();
+
+        // Two spawned actions racing on first use share one seed from the pending count
+        let (a, b) = tokio::join!(
+            executor.send_swap(swap(USDC, TOKEN, 1_000), None),
+            executor.send_swap(swap(TOKEN, USDC, 2_000), None),
+        );
+        a.unwrap();
+        b.unwrap();
+        executor.send_swap(swap(USDC, TOKEN, 3_000), None).await.unwrap();
+
+        let nonces: Vec<u64> = sent.lock().unwrap().iter()
+            .map(|tx| u64::from_str_radix(tx["nonce"].as_str().unwrap().trim_start_matches("0x"), 16).unwrap())
+            .collect();
+        let mut racing = nonces[..2].to_vec();
+        racing.sort();
+        assert_eq!(racing, vec![5, 6]);
+        assert_eq!(nonces[2], 7);
     }
 }
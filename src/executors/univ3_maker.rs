@@ -0,0 +1,306 @@
+use std::sync::{Arc, Mutex};
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use tracing::info;
+use alloy::{
+    primitives::{aliases::{I24, U24}, Address, Bytes, TxHash, U256},
+    providers::Provider,
+    signers::local::PrivateKeySigner,
+    sol,
+};
+
+use crate::strategies::common;
+use crate::types::Executor;
+
+sol! {
+    #[sol(rpc)]
+    interface INonfungiblePositionManager {
+        struct MintParams {
+            address token0;
+            address token1;
+            uint24 fee;
+            int24 tickLower;
+            int24 tickUpper;
+            uint256 amount0Desired;
+            uint256 amount1Desired;
+            uint256 amount0Min;
+            uint256 amount1Min;
+            address recipient;
+            uint256 deadline;
+        }
+
+        struct DecreaseLiquidityParams {
+            uint256 tokenId;
+            uint128 liquidity;
+            uint256 amount0Min;
+            uint256 amount1Min;
+            uint256 deadline;
+        }
+
+        struct CollectParams {
+            uint256 tokenId;
+            address recipient;
+            uint128 amount0Max;
+            uint128 amount1Max;
+        }
+
+        event IncreaseLiquidity(uint256 indexed tokenId, uint128 liquidity, uint256 amount0, uint256 amount1);
+
+        function mint(MintParams calldata params) external payable returns (uint256 tokenId, uint128 liquidity, uint256 amount0, uint256 amount1);
+        function decreaseLiquidity(DecreaseLiquidityParams calldata params) external payable returns (uint256 amount0, uint256 amount1);
+        function collect(CollectParams calldata params) external payable returns (uint256 amount0, uint256 amount1);
+        function burn(uint256 tokenId) external payable;
+        function positions(uint256 tokenId) external view returns (uint96 nonce, address operator, address token0, address token1, uint24 fee, int24 tickLower, int24 tickUpper, uint128 liquidity, uint256 feeGrowthInside0LastX128, uint256 feeGrowthInside1LastX128, uint128 tokensOwed0, uint128 tokensOwed1);
+        function multicall(bytes[] calldata data) external payable returns (bytes[] memory results);
+    }
+}
+
+/// Tick spacing of a Uniswap V3 fee tier
+pub fn tick_spacing(fee: u32) -> Option<i32> {
+    match fee {
+        100 => Some(1),
+        500 => Some(10),
+        3000 => Some(60),
+        10000 => Some(200),
+        _ => None,
+    }
+}
+
+/// Liquidity in `[tick_lower, tick_upper)` of one pool: a range order that fills as
+/// the price crosses the range. A range entirely above the current price takes only
+/// token0 and one below takes only token1. A range containing the current price takes
+/// both, in the pool's ratio, so it is already partly filled when minted.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UniV3MakerAction {
+    /// Pool tokens in Uniswap order (lower address first)
+    pub token0: Address,
+    pub token1: Address,
+    pub fee: u32,
+    pub tick_lower: i32,
+    pub tick_upper: i32,
+    pub amount0_desired: U256,
+    pub amount1_desired: U256,
+}
+
+impl UniV3MakerAction {
+    /// Range one tick spacing wide around `price` (token1 per token0, human units): the
+    /// spacing-aligned bucket containing it, which may also contain the current price
+    #[allow(clippy::too_many_arguments)]
+    pub fn at_price(
+        token0: Address,
+        token1: Address,
+        fee: u32,
+        price: f64,
+        token0_decimals: u8,
+        token1_decimals: u8,
+        amount0_desired: U256,
+        amount1_desired: U256,
+    ) -> Result<Self> {
+        let spacing = tick_spacing(fee)
+            .ok_or_else(|| anyhow::anyhow!("fee {} is not a Uniswap V3 tier", fee))?;
+        let tick = common::price_to_tick(price, token0_decimals, token1_decimals)
+            .ok_or_else(|| anyhow::anyhow!("price {} is outside the tick range", price))?;
+        let tick_lower = tick.div_euclid(spacing) * spacing;
+        Ok(Self {
+            token0,
+            token1,
+            fee,
+            tick_lower,
+            tick_upper: tick_lower + spacing,
+            amount0_desired,
+            amount1_desired,
+        })
+    }
+}
+
+/// Opens and closes range-order positions through the NFT position manager. The signer
+/// must have approved the position manager to spend both pool tokens. Minted positions
+/// are tracked until closed so they can be unwound on shutdown.
+pub struct UniV3MakerExecutor<P> {
+    provider: Arc<P>,
+    signer: PrivateKeySigner,
+    position_manager: Address,
+    positions: Mutex<Vec<U256>>,
+}
+
+impl<P: Provider + 'static> UniV3MakerExecutor<P> {
+    pub fn new(provider: Arc<P>, private_key: &str, position_manager: Address) -> Result<Self> {
+        let signer = private_key.parse::<PrivateKeySigner>()?;
+        Ok(Self {
+            provider,
+            signer,
+            position_manager,
+            positions: Mutex::new(Vec::new()),
+        })
+    }
+
+    fn deadline() -> Result<U256> {
+        Ok(U256::from(
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)?
+                .as_secs() + 300
+        ))
+    }
+
+    /// Encode the `mint` call for a range order paid out to the signer
+    pub fn encode_mint(&self, action: &UniV3MakerAction) -> Result<Bytes> {
+        if action.tick_lower >= action.tick_upper {
+            anyhow::bail!("Empty tick range [{}, {})", action.tick_lower, action.tick_upper);
+        }
+        let manager = INonfungiblePositionManager::new(self.position_manager, &*self.provider);
+        let params = INonfungiblePositionManager::MintParams {
+            token0: action.token0,
+            token1: action.token1,
+            fee: U24::from(action.fee),
+            tickLower: I24::try_from(action.tick_lower)?,
+            tickUpper: I24::try_from(action.tick_upper)?,
+            amount0Desired: action.amount0_desired,
+            amount1Desired: action.amount1_desired,
+            amount0Min: U256::ZERO,
+            amount1Min: U256::ZERO,
+            recipient: self.signer.address(),
+            deadline: Self::deadline()?,
+        };
+        Ok(manager.mint(params).calldata().to_owned())
+    }
+
+    /// Mint the position, wait for it to be mined and return its token id
+    pub async fn mint(&self, action: &UniV3MakerAction) -> Result<U256> {
+        let calldata = self.encode_mint(action)?;
+        let manager = INonfungiblePositionManager::new(self.position_manager, &*self.provider);
+        let receipt = manager
+            .multicall(vec![calldata])
+            .from(self.signer.address())
+            .gas(600_000)
+            .send().await?
+            .get_receipt().await?;
+        if !receipt.status() {
+            anyhow::bail!("Mint 0x{:x} reverted", receipt.transaction_hash);
+        }
+
+        let minted = receipt.inner.logs().iter()
+            .filter(|log| log.address() == self.position_manager)
+            .find_map(|log| log.log_decode::<INonfungiblePositionManager::IncreaseLiquidity>().ok())
+            .ok_or_else(|| anyhow::anyhow!("No IncreaseLiquidity event in 0x{:x}", receipt.transaction_hash))?;
+        let token_id = minted.inner.data.tokenId;
+        info!(
+            "DEX maker: minted position {} [{}, {}) liquidity {} (0x{:x})",
+            token_id, action.tick_lower, action.tick_upper, minted.inner.data.liquidity, receipt.transaction_hash
+        );
+        self.positions.lock().unwrap().push(token_id);
+        Ok(token_id)
+    }
+
+    /// Withdraw all liquidity, collect tokens and fees to the signer and burn the NFT,
+    /// atomically in one multicall. The position stays tracked unless the close is mined.
+    pub async fn close(&self, token_id: U256) -> Result<TxHash> {
+        let manager = INonfungiblePositionManager::new(self.position_manager, &*self.provider);
+        let position = manager.positions(token_id).call().await?;
+        let owner = self.signer.address();
+
+        let mut calls = Vec::with_capacity(3);
+        if position.liquidity > 0 {
+            calls.push(manager.decreaseLiquidity(INonfungiblePositionManager::DecreaseLiquidityParams {
+                tokenId: token_id,
+                liquidity: position.liquidity,
+                amount0Min: U256::ZERO,
+                amount1Min: U256::ZERO,
+                deadline: Self::deadline()?,
+            }).calldata().to_owned());
+        }
+        calls.push(manager.collect(INonfungiblePositionManager::CollectParams {
+            tokenId: token_id,
+            recipient: owner,
+            amount0Max: u128::MAX,
+            amount1Max: u128::MAX,
+        }).calldata().to_owned());
+        calls.push(manager.burn(token_id).calldata().to_owned());
+
+        let receipt = manager
+            .multicall(calls)
+            .from(owner)
+            .gas(400_000)
+            .send().await?
+            .get_receipt().await?;
+        let tx_hash = receipt.transaction_hash;
+        if !receipt.status() {
+            anyhow::bail!("Close of position {} 0x{:x} reverted", token_id, tx_hash);
+        }
+        self.positions.lock().unwrap().retain(|id| *id != token_id);
+        info!("DEX maker: closed position {} (0x{:x})", token_id, tx_hash);
+        Ok(tx_hash)
+    }
+
+    /// Token ids of positions minted and not yet closed
+    pub fn open_positions(&self) -> Vec<U256> {
+        self.positions.lock().unwrap().clone()
+    }
+
+    /// Close every tracked position, returning how many closes were sent
+    pub async fn close_all(&self) -> Result<usize> {
+        let mut closed = 0;
+        for token_id in self.open_positions() {
+            self.close(token_id).await?;
+            closed += 1;
+        }
+        Ok(closed)
+    }
+}
+
+#[async_trait]
+impl<P: Provider + 'static> Executor<UniV3MakerAction> for UniV3MakerExecutor<P> {
+    async fn execute(&self, action: UniV3MakerAction) -> Result<()> {
+        self.mint(&action).await.map(|_| ())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::{primitives::address, providers::ProviderBuilder, sol_types::SolCall};
+
+    use super::*;
+
+    const USDC: Address = address!("0xb88339CB7199b77E23DB6E890353E22632Ba630f");
+    const WHYPE: Address = address!("0x5555555555555555555555555555555555555555");
+
+    fn range_at(price: f64, token0_decimals: u8, token1_decimals: u8) -> UniV3MakerAction {
+        UniV3MakerAction::at_price(USDC, WHYPE, 3000, price, token0_decimals, token1_decimals, U256::from(1_000_000u64), U256::ZERO)
+            .unwrap()
+    }
+
+    #[test]
+    fn range_is_the_spacing_bucket_holding_the_price() {
+        // $40 HYPE as WHYPE per USDC: tick 239433, in the bucket starting at 60 * 3990
+        let range = range_at(0.025, 6, 18);
+        assert_eq!((range.tick_lower, range.tick_upper), (239_400, 239_460));
+        // Negative ticks round towards -inf, not zero: tick -239434
+        let range = range_at(40.0, 18, 6);
+        assert_eq!((range.tick_lower, range.tick_upper), (-239_460, -239_400));
+        assert!(UniV3MakerAction::at_price(USDC, WHYPE, 2500, 0.025, 6, 18, U256::ZERO, U256::ZERO).is_err());
+    }
+
+    #[test]
+    fn mint_encodes_the_action_range() {
+        let provider = ProviderBuilder::new().connect_http("http://127.0.0.1:8545".parse().unwrap());
+        // Well-known dev key; nothing is sent
+        let executor = UniV3MakerExecutor::new(
+            Arc::new(provider),
+            "0x59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d",
+            Address::repeat_byte(0x77),
+        ).unwrap();
+
+        let range = range_at(0.025, 6, 18);
+        let calldata = executor.encode_mint(&range).unwrap();
+        let params = INonfungiblePositionManager::mintCall::abi_decode(&calldata).unwrap().params;
+        assert_eq!(params.tickLower, I24::try_from(239_400).unwrap());
+        assert_eq!(params.tickUpper, I24::try_from(239_460).unwrap());
+        assert_eq!(params.fee, U24::from(3000));
+        assert_eq!(params.amount0Desired, U256::from(1_000_000u64));
+        assert_eq!(params.recipient, executor.signer.address());
+
+        let empty = UniV3MakerAction { tick_upper: range.tick_lower, ..range };
+        assert!(executor.encode_mint(&empty).is_err());
+    }
+}
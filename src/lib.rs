@@ -1,3 +1,6 @@
+/// This module contains the [StrategyBuilder](builder::StrategyBuilder), which wires
+/// a strategy config into engine components.
+pub mod builder;
 pub mod collectors;
 /// This module contains configuration structures and loading.
 pub mod config;
@@ -1,66 +1,285 @@
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 
 use anyhow::Result;
+use clap::{Parser, Subcommand};
 use alloy::{
     network::EthereumWallet,
-    primitives::Address,
-    providers::ProviderBuilder,
+    primitives::U256,
+    providers::{Provider, ProviderBuilder},
     signers::local::PrivateKeySigner,
     transports::ws::WsConnect,
 };
 use rustyarb::{
+    builder::StrategyBuilder,
     collectors::{
-        uniswapv3::UniV3Collector,
-        hyperliquid::HyperliquidCollector,
+        config_reload::ConfigReloadCollector,
+        recorder::EventRecorder,
+        replay::ReplayCollector,
     },
-    config::Config,
+    config::{Config, StrategyConfig},
     engine::Engine,
-    execution::ExecutionManager,
+    execution::CircuitBreaker,
     executors::{
-        arbitrage::ArbitrageExecutor,
-        univ3::UniV3Executor,
-        hyperliquid::HyperliquidExecutor,
+        null::NullExecutor,
+        retry::RetryExecutor,
+        univ3::{UniV3Executor, UniV3SwapAction},
+        hyperliquid::{HyperliquidExecutor, HyperliquidOrderAction, OrderMode, TimeInForce},
     },
     strategies::hype_usdc_cross_arbitrage::{HypeUsdcCrossArbitrage, Event, Action},
-    types::CollectorMap,
+    types::{CollectorMap, Executor},
+    utilities::{
+        alerts::{MultiNotifier, Notifier, TelegramNotifier, WebhookNotifier},
+        evaluate,
+        health::{self, HealthState},
+        logging::{self, LogFormat},
+        price_oracle::{CoinGeckoPoller, NativePriceOracle},
+        tokens::TokenRegistry,
+    },
 };
-use tracing::{info, Level};
-use tracing_subscriber::{filter, prelude::*};
+use tracing::{error, info, warn};
+
+/// Cross-venue arbitrage bot.
+///
+/// Exit codes: 0 on success (for `validate-config`: the config is valid), 1 on any
+/// error (invalid config, failed order, connection failure), 2 on bad arguments.
+#[derive(Parser)]
+#[command(name = "rustyarb", version)]
+struct Cli {
+    /// Config file; RUSTYARB_* env vars are used when it doesn't exist
+    #[arg(short, long, global = true, default_value = "config.toml")]
+    config: String,
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Run the live engine (the default)
+    Run,
+    /// Load and validate the config, then exit
+    ValidateConfig,
+    /// Replay a recording through one strategy, recording signals instead of executing
+    Simulate {
+        /// Event recording (JSON lines) from `record_path`
+        recording: String,
+        /// Strategy name; defaults to the first enabled strategy
+        #[arg(long)]
+        strategy: Option<String>,
+    },
+    /// Submit a single order to check keys, balances and connectivity
+    TestOrder {
+        #[command(subcommand)]
+        venue: TestOrder,
+    },
+}
+
+#[derive(Subcommand)]
+enum TestOrder {
+    /// IOC limit order on Hyperliquid
+    Hl {
+        coin: String,
+        size: f64,
+        limit_px: f64,
+        /// Buy instead of sell
+        #[arg(long)]
+        buy: bool,
+        /// Send the order up to this many times while it fails transiently (timeouts,
+        /// rate limits); orders the exchange may have taken are never resent
+        #[arg(long, default_value_t = 1)]
+        attempts: u32,
+    },
+    /// Single swap through the strategy's router
+    Dex {
+        token_in: String,
+        token_out: String,
+        /// Raw input amount (token units, no decimals)
+        amount_in: String,
+        /// Pool fee tier; defaults to the strategy's
+        #[arg(long)]
+        fee: Option<u32>,
+        /// Strategy whose router and RPC to use; defaults to the first enabled strategy
+        #[arg(long)]
+        strategy: Option<String>,
+    },
+}
+
+/// `path` if it exists, otherwise RUSTYARB_* env vars
+fn load_config(path: &str) -> Result<Config> {
+    if std::path::Path::new(path).exists() {
+        Config::load(path)
+    } else {
+        Config::from_env()
+    }
+}
+
+/// Named strategy, or the first enabled one
+fn pick_strategy<'a>(config: &'a Config, name: Option<&str>) -> Result<&'a StrategyConfig> {
+    match name {
+        Some(name) => config.strategies.iter().find(|s| s.name == name)
+            .ok_or_else(|| anyhow::anyhow!("No strategy named '{}'", name)),
+        None => config.strategies.iter().find(|s| s.enabled)
+            .ok_or_else(|| anyhow::anyhow!("No enabled strategies")),
+    }
+}
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    // Set up tracing
-    let filter = filter::Targets::new()
-        .with_target("rustyarb", Level::INFO);
-    tracing_subscriber::registry()
-        .with(tracing_subscriber::fmt::layer())
-        .with(filter)
-        .init();
+    let cli = Cli::parse();
 
     // Load environment variables
     dotenv::dotenv().ok();
     
-    // Load configuration
-    let config = Config::load("config.toml")?;
-    info!("✓ Loaded config with {} strategies", config.strategies.len());
+    let config = match load_config(&cli.config) {
+        Ok(config) => config,
+        Err(e) if matches!(cli.command, Some(Command::ValidateConfig)) => {
+            eprintln!("✗ Invalid config: {:#}", e);
+            std::process::exit(1);
+        }
+        Err(e) => return Err(e),
+    };
     
+    // Set up tracing
+    logging::init_tracing(LogFormat::from_env_or(config.log_format));
+    info!("✓ Loaded config with {} strategies", config.strategies.len());
+
+    match cli.command.unwrap_or(Command::Run) {
+        Command::Run => run(config, &cli.config).await,
+        Command::ValidateConfig => {
+            println!("✓ Config valid ({} strategies)", config.strategies.len());
+            Ok(())
+        }
+        Command::Simulate { recording, strategy } => {
+            simulate(pick_strategy(&config, strategy.as_deref())?, &recording).await
+        }
+        Command::TestOrder { venue } => test_order(&config, venue).await,
+    }
+}
+
+/// Feed a recording through one strategy and report how many signals it produced
+async fn simulate(strategy_config: &StrategyConfig, recording: &str) -> Result<()> {
+    let strategy = HypeUsdcCrossArbitrage::from_config(strategy_config)?;
+    let executor = NullExecutor::<Action>::new();
+    let recorded = executor.recorded();
+
+    let mut engine: Engine<Event, Action> = Engine::new()
+        .with_event_channel_capacity(100_000)
+        .with_action_channel_capacity(100_000);
+    engine.add_collector(Box::new(ReplayCollector::<Event>::new(recording)));
+    engine.add_strategy(Box::new(strategy));
+    engine.add_executor(Box::new(executor));
+
+    if let Ok(mut set) = engine.run().await {
+        while set.join_next().await.is_some() {}
+    }
+
+    let actions = recorded.lock().unwrap();
+    info!("📊 [{}] {} signals at {} bps threshold", strategy_config.name, actions.len(), strategy_config.min_profit_bps);
+    Ok(())
+}
+
+async fn test_order(config: &Config, venue: TestOrder) -> Result<()> {
+    let private_key = std::env::var("PRIVATE_KEY")?;
+    match venue {
+        TestOrder::Hl { coin, size, limit_px, buy, attempts } => {
+            let executor = RetryExecutor::new(HyperliquidExecutor::new(private_key)?, attempts).with_label("Test order");
+            let action = HyperliquidOrderAction {
+                coin,
+                is_buy: buy,
+                size,
+                limit_px,
+                tif: TimeInForce::Ioc,
+                mode: OrderMode::Limit,
+                reduce_only: false,
+            };
+            info!("Test order: {} {} {} @ ${:.4}", if buy { "BUY" } else { "SELL" }, action.size, action.coin, action.limit_px);
+            // The executor logs the fill, if any
+            executor.execute(action).await?;
+            info!("✓ Order sent");
+        }
+        TestOrder::Dex { token_in, token_out, amount_in, fee, strategy } => {
+            let strategy_config = pick_strategy(config, strategy.as_deref())?;
+            let signer: PrivateKeySigner = private_key.parse()?;
+            let provider = connect(config, EthereumWallet::from(signer)).await?.primary;
+            let executor = UniV3Executor::new(provider, &private_key, strategy_config.router_address.parse()?)?;
+            let action = UniV3SwapAction {
+                token_in: token_in.parse()?,
+                token_out: token_out.parse()?,
+                fee: fee.unwrap_or(strategy_config.fee),
+                amount_in: amount_in.parse::<U256>()?,
+                amount_out_min: U256::ZERO,
+            };
+            info!("Test swap: {} of {} → {} (fee {})", action.amount_in, action.token_in, action.token_out, action.fee);
+            executor.execute(action).await?;
+            info!("✓ Swap sent");
+        }
+    }
+    Ok(())
+}
+
+/// RPC providers: the WebSocket one when it connects, else HTTP
+struct Providers<P> {
+    primary: Arc<P>,
+    /// Set when `rpc_url_http` is configured
+    http: Option<Arc<P>>,
+    ws_connected: bool,
+}
+
+/// Connect over WebSocket, falling back to `rpc_url_http` when the socket can't be opened
+async fn connect(config: &Config, wallet: EthereumWallet) -> Result<Providers<impl Provider + 'static>> {
+    let http = match &config.rpc_url_http {
+        Some(url) => Some(Arc::new(ProviderBuilder::new().wallet(wallet.clone()).connect_http(url.parse()?))),
+        None => None,
+    };
+    let ws_error = if config.rpc_url_ws.is_empty() {
+        anyhow::anyhow!("rpc_url_ws not set")
+    } else {
+        match ProviderBuilder::new().wallet(wallet).connect_ws(WsConnect::new(&config.rpc_url_ws)).await {
+            Ok(provider) => return Ok(Providers { primary: Arc::new(provider), http, ws_connected: true }),
+            Err(e) => anyhow::Error::from(e),
+        }
+    };
+    let Some(primary) = http.clone() else {
+        return Err(ws_error);
+    };
+    warn!("WebSocket RPC unavailable ({}) - polling over HTTP", ws_error);
+    Ok(Providers { primary, http, ws_connected: false })
+}
+
+/// Live engine: collectors, strategies and executors for every enabled strategy
+async fn run(config: Config, config_path: &str) -> Result<()> {
     // Get private key from env
     let private_key = std::env::var("PRIVATE_KEY")?;
     let signer: PrivateKeySigner = private_key.parse()?;
     let wallet = EthereumWallet::from(signer);
     
     // Connect to network
-    let ws = WsConnect::new(&config.rpc_url_ws);
-    let provider = Arc::new(
-        ProviderBuilder::new()
-            .wallet(wallet)
-            .connect_ws(ws)
-            .await?
-    );
+    let providers = connect(&config, wallet).await?;
+    let provider = providers.primary.clone();
     
     // Create engine
     let mut engine: Engine<Event, Action> = Engine::default();
     
+    // Optional Telegram and webhook alerts on fills and leg failures
+    let mut notifiers: Vec<Arc<dyn Notifier>> = Vec::new();
+    if let Some(telegram) = TelegramNotifier::from_env() {
+        info!("✓ Telegram alerts enabled");
+        notifiers.push(Arc::new(telegram));
+    }
+    if let Some(url) = &config.alert_webhook_url {
+        info!("✓ Webhook alerts enabled");
+        notifiers.push(Arc::new(WebhookNotifier::new(url.clone())));
+    }
+    let notifier: Option<Arc<dyn Notifier>> = match notifiers.len() {
+        0 => None,
+        1 => notifiers.pop(),
+        _ => Some(Arc::new(MultiNotifier(notifiers))),
+    };
+    
+    // Optional event recording for later replay
+    if let Some(path) = &config.record_path {
+        engine = engine.with_event_recorder(EventRecorder::spawn(path).await?);
+    }
+    
     // Process each enabled strategy
     let enabled_strategies: Vec<_> = config.strategies.iter()
         .filter(|s| s.enabled)
@@ -70,50 +289,79 @@ async fn main() -> Result<()> {
         anyhow::bail!("No enabled strategies found in config");
     }
     
-    let num_strategies = enabled_strategies.len();
-    info!("🚀 Starting {} enabled strategies", num_strategies);
+    // Optional what-if endpoint, evaluated against config only (no live data)
+    if let Some(addr) = config.evaluate_addr.clone() {
+        let strategies: Vec<_> = enabled_strategies.iter().map(|s| (*s).clone()).collect();
+        tokio::spawn(async move {
+            if let Err(e) = evaluate::serve(&addr, strategies).await {
+                error!("What-if endpoint failed: {}", e);
+            }
+        });
+    }
     
-    for strategy_config in enabled_strategies {
-        info!("  • {}", strategy_config.name);
-        
-        // Parse addresses
-        let pool_address: Address = strategy_config.pool_address.parse()?;
-        let router_address: Address = strategy_config.router_address.parse()?;
-        
-        // Add DEX collector (UniswapV3)
-        let univ3_collector = Box::new(UniV3Collector::new(
-            provider.clone(),
-            pool_address,
+    // Optional liveness/readiness probes
+    let health = Arc::new(HealthState::new(Duration::from_secs(config.health_stale_secs)));
+    if let Some(addr) = config.health_addr.clone() {
+        engine = engine.with_health(health.clone());
+        let health = health.clone();
+        tokio::spawn(async move {
+            if let Err(e) = health::serve(&addr, health).await {
+                error!("Health endpoint failed: {}", e);
+            }
+        });
+    }
+    
+    // Optional breaker shared by every strategy's executor
+    let circuit_breaker = config.circuit_breaker_failures.map(|failures| {
+        let breaker = Arc::new(CircuitBreaker::new(
+            failures,
+            Duration::from_secs(config.circuit_breaker_cooldown_secs),
         ));
-        engine.add_collector(Box::new(CollectorMap::new(
-            univ3_collector,
-            |pool_state| Event::PoolUpdate(pool_state),
-        )));
-        
-        // Add CEX collector (Hyperliquid)
-        let hl_collector = Box::new(HyperliquidCollector::new(
-            strategy_config.hyperliquid_coin.clone()
+        let open = breaker.clone();
+        health.register_halt_check("circuit_breaker", move || open.is_open());
+        breaker
+    });
+    
+    // Optional native token USD price for gas costs
+    let native_price = config.native_price_coingecko_id.clone().map(|coin_id| {
+        let oracle = Arc::new(NativePriceOracle::new(
+            Duration::from_secs(config.native_price_ttl_secs),
+            config.native_price_default_usd,
         ));
+        CoinGeckoPoller::new(coin_id)
+            .with_poll_interval(Duration::from_secs(config.native_price_poll_secs))
+            .spawn(oracle.clone());
+        oracle
+    });
+    
+    // SIGHUP re-reads the config file and retunes running strategies in place
+    if std::path::Path::new(config_path).exists() {
+        let reload_collector = Box::new(ConfigReloadCollector::new(config_path.to_string()));
         engine.add_collector(Box::new(CollectorMap::new(
-            hl_collector,
-            |bbo| Event::HyperliquidBbo(bbo),
+            reload_collector,
+            |reload| Event::ConfigReload(reload),
         )));
-        
-        // Add strategy
-        let strategy = Box::new(HypeUsdcCrossArbitrage::from_config(strategy_config)?);
-        engine.add_strategy(strategy);
-        
-        // Create per-strategy execution manager (1 execution at a time per strategy)
-        let exec_manager = Arc::new(ExecutionManager::new(1));
-        
-        // Add executors
-        let arb_executor = ArbitrageExecutor::new(
-            UniV3Executor::new(provider.clone(), &private_key, router_address)?,
-            HyperliquidExecutor::new(private_key.clone())?,
-            exec_manager,
-            config.cooldown_secs,
-        );
-        engine.add_executor(Box::new(arb_executor));
+    }
+    
+    let num_strategies = enabled_strategies.len();
+    info!("🚀 Starting {} enabled strategies", num_strategies);
+    // Orders each strategy may leave resting on HL, cancelled on shutdown
+    let mut resting_orders = Vec::new();
+    let tokens = Arc::new(TokenRegistry::new(provider.clone()));
+    
+    for strategy_config in enabled_strategies {
+        info!("  • {}", strategy_config.name);
+        let (bundle, resting) = StrategyBuilder::new(&config, strategy_config, provider.clone(), private_key.clone())
+            .with_notifier(notifier.clone())
+            .with_circuit_breaker(circuit_breaker.clone())
+            .with_native_price(native_price.clone())
+            .with_health(health.clone())
+            .with_http_fallback(providers.http.clone())
+            .with_pool_polling(!providers.ws_connected)
+            .with_token_registry(tokens.clone())
+            .build()?;
+        resting_orders.push((strategy_config.name.clone(), resting));
+        engine.add_strategy_bundle(bundle);
     }
     
     info!("🤖 RustyArb live | Min profit: {}bps | Strategies: {}",
@@ -121,10 +369,52 @@ async fn main() -> Result<()> {
         num_strategies
     );
     
-    // Run engine
+    // Run engine until its tasks end or Ctrl-C
     if let Ok(mut set) = engine.run().await {
-        while set.join_next().await.is_some() {}
+        tokio::select! {
+            _ = async { while set.join_next().await.is_some() {} } => {}
+            _ = tokio::signal::ctrl_c() => info!("🛑 Shutdown requested"),
+        }
+    }
+
+    for (name, orders) in &resting_orders {
+        if orders.is_empty() {
+            continue;
+        }
+        match orders.cancel_all().await {
+            Ok(cancelled) => info!("[{}] Cancelled {} resting HL orders", name, cancelled),
+            Err(e) => error!("[{}] Failed to cancel resting HL orders: {}", name, e),
+        }
     }
     
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(rpc_url_ws: &str, rpc_url_http: Option<&str>) -> Config {
+        let mut config: Config = toml::from_str("max_concurrent = 1\ncooldown_secs = 15\nstrategies = []").unwrap();
+        config.rpc_url_ws = rpc_url_ws.to_string();
+        config.rpc_url_http = rpc_url_http.map(str::to_string);
+        config
+    }
+
+    fn wallet() -> EthereumWallet {
+        // Well-known dev key; nothing is sent
+        let signer: PrivateKeySigner = "0x59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d".parse().unwrap();
+        EthereumWallet::from(signer)
+    }
+
+    #[tokio::test]
+    async fn http_is_used_when_the_websocket_is_unset_or_unreachable() {
+        for ws in ["", "ws://127.0.0.1:1"] {
+            let providers = connect(&config(ws, Some("http://127.0.0.1:8545")), wallet()).await.unwrap();
+            assert!(!providers.ws_connected, "{}", ws);
+            assert!(providers.http.is_some());
+        }
+        let err = connect(&config("", None), wallet()).await.err().unwrap();
+        assert!(err.to_string().contains("rpc_url_ws not set"), "{}", err);
+    }
+}
@@ -0,0 +1,277 @@
+//! Price and profit math shared by the arbitrage strategies: quote a pool, net the edge
+//! and pick a direction, independent of the venue on the other side
+
+use alloy::primitives::U256;
+
+use crate::types::Direction;
+
+/// Pool price as token1 per token0 in human units, from `sqrtPriceX96` and the
+/// token0/token1 decimals. Full-width conversion: sqrtPriceX96 can exceed u128 at extreme ticks.
+pub fn pool_price(sqrt_price: U256, token0_decimals: u8, token1_decimals: u8) -> Option<f64> {
+    let sqrt_price = sqrt_price.to_string().parse::<f64>().ok()?;
+    let q96 = 2_f64.powi(96);
+    let base_price = (sqrt_price / q96).powi(2);
+    let decimal_adjustment = 10_f64.powi(token0_decimals as i32 - token1_decimals as i32);
+    let price = base_price * decimal_adjustment;
+
+    if price.is_finite() && price > 0.0 { Some(price) } else { None }
+}
+
+/// Tick bounds valid in any Uniswap V3 pool
+pub const MIN_TICK: i32 = -887272;
+pub const MAX_TICK: i32 = 887272;
+
+/// Highest tick whose price is not above `price` (token1 per token0, human units): the
+/// inverse of [pool_price] at tick granularity. None outside the valid tick range.
+pub fn price_to_tick(price: f64, token0_decimals: u8, token1_decimals: u8) -> Option<i32> {
+    if !price.is_finite() || price <= 0.0 {
+        return None;
+    }
+    let raw_price = price * 10_f64.powi(token1_decimals as i32 - token0_decimals as i32);
+    let tick = (raw_price.ln() / 1.0001_f64.ln()).floor();
+    (MIN_TICK as f64..=MAX_TICK as f64).contains(&tick).then_some(tick as i32)
+}
+
+/// Exact on-chain integer amount for a human amount with `decimals` places. Goes through
+/// the shortest decimal form of the f64 instead of multiplying by 10^decimals, which picks
+/// up binary rounding error (0.1234 * 1e18 is not 123400000000000000). Extra places are
+/// truncated; non-finite and negative amounts give zero.
+pub fn to_raw(amount: f64, decimals: u8) -> U256 {
+    if !amount.is_finite() || amount <= 0.0 {
+        return U256::ZERO;
+    }
+    // f64 Display never uses exponent notation
+    let text = amount.to_string();
+    let (whole, frac) = text.split_once('.').unwrap_or((&text, ""));
+    let mut digits = whole.to_string();
+    digits.extend(frac.chars().chain(std::iter::repeat('0')).take(decimals as usize));
+    U256::from_str_radix(&digits, 10).unwrap_or(U256::ZERO)
+}
+
+/// Round to `decimals` places, e.g. a size to the precision a venue accepts
+pub fn round_to_decimals(amount: f64, decimals: u8) -> f64 {
+    let scale = 10_f64.powi(decimals as i32);
+    (amount * scale).round() / scale
+}
+
+/// Price move caused by swapping `amount_in` raw units into a pool, in bps, assuming the
+/// swap stays within the current tick (constant in-range `liquidity`). Selling token0
+/// (`zero_for_one`) lowers sqrtP to L·sqrtP / (L + Δx·sqrtP); selling token1 raises it by
+/// Δy / L. None when liquidity is unknown (zero).
+pub fn price_impact_bps(sqrt_price: U256, liquidity: u128, amount_in: f64, zero_for_one: bool) -> Option<f64> {
+    if liquidity == 0 || amount_in <= 0.0 {
+        return None;
+    }
+    // Raw sqrt(token1/token0), without the Q96 scaling
+    let sqrt_p = sqrt_price.to_string().parse::<f64>().ok()? / 2_f64.powi(96);
+    let liquidity = liquidity as f64;
+    let sqrt_ratio = if zero_for_one {
+        1.0 + amount_in * sqrt_p / liquidity
+    } else {
+        1.0 + amount_in / (liquidity * sqrt_p)
+    };
+    let impact_bps = (sqrt_ratio.powi(2) - 1.0) * 10000.0;
+    impact_bps.is_finite().then_some(impact_bps)
+}
+
+/// Raw output of swapping `amount_in` raw units after the pool fee, under the same
+/// single-tick-range assumption as [price_impact_bps]: Δy = L·(sqrtP − sqrtP') selling
+/// token0, Δx = L·(1/sqrtP − 1/sqrtP') selling token1. Overstates output once the swap
+/// would cross into thinner ticks.
+pub fn single_range_amount_out(sqrt_price: U256, liquidity: u128, fee: u32, amount_in: f64, zero_for_one: bool) -> Option<f64> {
+    if liquidity == 0 || amount_in <= 0.0 {
+        return None;
+    }
+    let sqrt_p = sqrt_price.to_string().parse::<f64>().ok()? / 2_f64.powi(96);
+    let liquidity = liquidity as f64;
+    let amount_in = amount_in * (1.0 - pool_fee_fraction(fee));
+    let amount_out = if zero_for_one {
+        let next = liquidity * sqrt_p / (liquidity + amount_in * sqrt_p);
+        liquidity * (sqrt_p - next)
+    } else {
+        let next = sqrt_p + amount_in / liquidity;
+        liquidity * (1.0 / sqrt_p - 1.0 / next)
+    };
+    (amount_out.is_finite() && amount_out > 0.0).then_some(amount_out)
+}
+
+/// Uniswap V3 fee tier (hundredths of a bip) as a fraction
+pub fn pool_fee_fraction(fee: u32) -> f64 {
+    fee as f64 / 1_000_000.0
+}
+
+/// Quote a pool mid as bid/ask with the pool fee split across the two sides
+pub fn dex_spread(mid_price: f64, fee: u32) -> (f64, f64) {
+    let fee_fraction = pool_fee_fraction(fee);
+    (mid_price * (1.0 - fee_fraction / 2.0), mid_price * (1.0 + fee_fraction / 2.0))
+}
+
+/// Pool bid/ask as token1 per token0 in human units: the `sqrtPriceX96` mid with the pool
+/// fee split across the two sides. `dec_a`/`dec_b` are the token0/token1 decimals.
+pub fn dex_bid_ask(sqrt_price: U256, fee: u32, dec_a: u8, dec_b: u8) -> Option<(f64, f64)> {
+    let mid_price = pool_price(sqrt_price, dec_a, dec_b)?;
+    Some(dex_spread(mid_price, fee))
+}
+
+/// Widen a venue's top of book by its trading fee; a negative fee (rebate) narrows it
+pub fn apply_fee_bps(bid: f64, ask: f64, fee_bps: f64) -> (f64, f64) {
+    let fee = fee_bps / 10000.0;
+    (bid * (1.0 - fee), ask * (1.0 + fee))
+}
+
+/// Trade notional for an edge `excess_bps` above the profit threshold: `min_usd` at the
+/// threshold, rising linearly to `max_usd` once the excess reaches `ramp_bps`
+pub fn scaled_notional(excess_bps: f64, min_usd: f64, max_usd: f64, ramp_bps: f64) -> f64 {
+    if ramp_bps <= 0.0 {
+        return max_usd;
+    }
+    let fraction = (excess_bps / ramp_bps).clamp(0.0, 1.0);
+    min_usd + (max_usd - min_usd) * fraction
+}
+
+/// Gas for one trade as bps of its notional
+pub fn gas_cost_bps(gas_fee_usd: f64, notional_usd: f64) -> f64 {
+    gas_fee_usd / notional_usd * 10000.0
+}
+
+/// Net profit in bps of buying at `buy_price` and selling at `sell_price` (fees already
+/// in the prices), after paying gas on `notional_usd`
+pub fn net_profit_bps(buy_price: f64, sell_price: f64, gas_fee_usd: f64, notional_usd: f64) -> f64 {
+    let gross_profit_bps = (sell_price - buy_price) / buy_price * 10000.0;
+    gross_profit_bps - gas_cost_bps(gas_fee_usd, notional_usd)
+}
+
+/// Exponential moving average over roughly `window` samples; the first sample seeds it
+pub fn ema(previous: Option<f64>, value: f64, window: u32) -> f64 {
+    let alpha = 2.0 / (window as f64 + 1.0);
+    match previous {
+        Some(previous) => previous + alpha * (value - previous),
+        None => value,
+    }
+}
+
+/// How far apart two mids are, in bps of the lower one
+pub fn divergence_bps(mid_a: f64, mid_b: f64) -> f64 {
+    (mid_a - mid_b).abs() / mid_a.min(mid_b) * 10000.0
+}
+
+/// Direction worth trading from quotes on both venues (fees already in the prices):
+/// buying on the DEX at `dex_ask` to sell at `cex_bid`, or buying at `cex_ask` to sell at
+/// `dex_bid`. None unless an edge clears `min_profit_bps`; the wider one wins when both do.
+/// Costs outside the prices, like gas, belong in the threshold.
+pub fn choose_direction(dex_bid: f64, dex_ask: f64, cex_bid: f64, cex_ask: f64, min_profit_bps: f64) -> Option<Direction> {
+    let buy_dex_bps = net_profit_bps(dex_ask, cex_bid, 0.0, 1.0);
+    let buy_cex_bps = net_profit_bps(cex_ask, dex_bid, 0.0, 1.0);
+    if buy_dex_bps.max(buy_cex_bps) <= min_profit_bps {
+        None
+    } else if buy_dex_bps >= buy_cex_bps {
+        Some(Direction::BuyDexSellCex)
+    } else {
+        Some(Direction::BuyCexSellDex)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pool_price_uses_the_full_sqrt_price() {
+        // Above 2^128: the low 128 bits alone are 12345678901234567890123456789
+        let sqrt_price = U256::from_str_radix("340282366933284142364609175321891668245", 10).unwrap();
+        // Reference from Python: (Decimal(sqrt_price) / 2**96) ** 2
+        let reference: f64 = "18446744075048072804.577".parse().unwrap();
+        let price = pool_price(sqrt_price, 18, 18).unwrap();
+        assert!((price / reference - 1.0).abs() < 1e-12, "{}", price);
+        // Decimals shift the raw price: 6-decimal token0 against 18-decimal token1
+        let scaled = pool_price(sqrt_price, 6, 18).unwrap();
+        assert!((scaled / (reference * 1e-12) - 1.0).abs() < 1e-12, "{}", scaled);
+    }
+
+    #[test]
+    fn to_raw_is_exact() {
+        assert_eq!(to_raw(0.1234, 18), U256::from(123_400_000_000_000_000u64));
+        assert_eq!(to_raw(1000.0, 6), U256::from(1_000_000_000u64));
+        assert_eq!(to_raw(12345.678901, 6), U256::from(12_345_678_901u64));
+        assert_eq!(to_raw(1.5, 0), U256::from(1u8));
+        assert_eq!(to_raw(-1.0, 6), U256::ZERO);
+        assert_eq!(to_raw(f64::NAN, 6), U256::ZERO);
+    }
+
+    #[test]
+    fn ema_seeds_then_moves_by_alpha() {
+        assert_eq!(ema(None, 7.0, 9), 7.0);
+        // Window 9 is alpha 0.2
+        assert!((ema(Some(0.0), 10.0, 9) - 2.0).abs() < 1e-12);
+        assert!((ema(Some(2.0), 10.0, 9) - 3.6).abs() < 1e-12);
+        // Window 1 follows the latest value
+        assert_eq!(ema(Some(0.0), 10.0, 1), 10.0);
+    }
+
+    #[test]
+    fn scaled_notional_ramps_between_bounds() {
+        assert_eq!(scaled_notional(-5.0, 100.0, 2000.0, 20.0), 100.0);
+        assert_eq!(scaled_notional(0.0, 100.0, 2000.0, 20.0), 100.0);
+        assert_eq!(scaled_notional(5.0, 100.0, 2000.0, 20.0), 575.0);
+        assert_eq!(scaled_notional(20.0, 100.0, 2000.0, 20.0), 2000.0);
+        assert_eq!(scaled_notional(80.0, 100.0, 2000.0, 20.0), 2000.0);
+        // No ramp: jump straight to the maximum
+        assert_eq!(scaled_notional(0.0, 100.0, 2000.0, 0.0), 2000.0);
+    }
+
+    #[test]
+    fn dex_bid_ask_brackets_the_pool_mid() {
+        // sqrt(2.5e10) * 2^96: 0.025 WHYPE (18 decimals) per USDC (6 decimals), $40 HYPE
+        let sqrt_price = U256::from(12_527_072_418_752_395_559_269_591_125_327_872u128);
+        let (bid, ask) = dex_bid_ask(sqrt_price, 3000, 6, 18).unwrap();
+        assert!((bid - 0.025 * 0.9985).abs() < 1e-12, "{}", bid);
+        assert!((ask - 0.025 * 1.0015).abs() < 1e-12, "{}", ask);
+        assert!(dex_bid_ask(U256::ZERO, 3000, 6, 18).is_none());
+    }
+
+    #[test]
+    fn dex_bid_ask_handles_sqrt_prices_beyond_u128() {
+        // 2^140 / 2^96 = 2^44 raw sqrt: raw price 2^88, which u128 math would truncate
+        let sqrt_price = U256::from(1u8) << 140;
+        let (bid, ask) = dex_bid_ask(sqrt_price, 100, 18, 18).unwrap();
+        let mid = 2_f64.powi(88);
+        assert!((bid / mid - 0.99995).abs() < 1e-12, "{}", bid);
+        assert!((ask / mid - 1.00005).abs() < 1e-12, "{}", ask);
+    }
+
+    #[test]
+    fn net_profit_nets_gas_over_the_trade_size() {
+        assert!((net_profit_bps(100.0, 101.0, 0.0, 1000.0) - 100.0).abs() < 1e-9);
+        assert!((net_profit_bps(100.0, 101.0, 2.0, 1000.0) - 80.0).abs() < 1e-9);
+        assert!(net_profit_bps(101.0, 100.0, 0.0, 1000.0) < 0.0);
+    }
+
+    #[test]
+    fn direction_follows_the_crossed_side() {
+        // CEX bid 30 bps over the DEX ask
+        assert_eq!(choose_direction(39.9, 40.0, 40.12, 40.14, 10.0), Some(Direction::BuyDexSellCex));
+        // DEX bid 30 bps over the CEX ask
+        assert_eq!(choose_direction(40.12, 40.14, 39.9, 40.0, 10.0), Some(Direction::BuyCexSellDex));
+        // Overlapping books, nothing to take
+        assert_eq!(choose_direction(39.99, 40.01, 39.98, 40.02, 10.0), None);
+    }
+
+    #[test]
+    fn direction_needs_an_edge_above_the_threshold() {
+        // CEX bid 9.75 bps over the DEX ask
+        assert_eq!(choose_direction(39.9, 40.0, 40.039, 40.06, 10.0), None);
+        assert_eq!(choose_direction(39.9, 40.0, 40.039, 40.06, 9.5), Some(Direction::BuyDexSellCex));
+        // Crossed both ways: the wider edge wins
+        assert_eq!(choose_direction(40.2, 40.3, 40.4, 40.1, 10.0), Some(Direction::BuyCexSellDex));
+    }
+
+    #[test]
+    fn gas_weighs_less_on_larger_notional() {
+        assert_eq!(gas_cost_bps(1.0, 1000.0), 10.0);
+        assert_eq!(gas_cost_bps(1.0, 2000.0), 5.0);
+        let small = net_profit_bps(100.0, 100.3, 1.0, 1000.0);
+        let large = net_profit_bps(100.0, 100.3, 1.0, 2000.0);
+        assert!((small - 20.0).abs() < 1e-9, "{}", small);
+        assert!((large - 25.0).abs() < 1e-9, "{}", large);
+    }
+}
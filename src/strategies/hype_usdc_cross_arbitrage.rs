@@ -1,34 +1,197 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
 use anyhow::Result;
 use async_trait::async_trait;
-use tracing::info;
-use alloy::primitives::Address;
+use serde::{Deserialize, Serialize};
+use tracing::{debug, info, warn};
+use alloy::primitives::{Address, U256};
+use amms::amms::amm::AutomatedMarketMaker;
 
 use crate::collectors::{
-    hyperliquid::HyperliquidBbo,
+    balance::BalanceUpdate,
+    cex::CexBbo,
+    config_reload::ConfigReload,
+    heartbeat::Heartbeat,
+    hyperliquid::{FundingRate, HyperliquidBbo},
     uniswapv3::UniV3PoolState,
 };
-use crate::config::StrategyConfig;
-use crate::types::Strategy;
+use crate::config::{StrategyConfig, StrategyParams};
+use crate::execution::{EdgeGauge, PositionTracker};
+use crate::collectors::cex::CexVenue;
+use crate::executors::hyperliquid::{OrderMode, TimeInForce, HL_MIN_ORDER_VALUE_USD};
+use crate::strategies::common;
+use crate::types::{Direction, Strategy};
+use crate::utilities::price_oracle::NativePriceOracle;
 
-#[derive(Debug, Clone)]
+/// Most decimal places a hedge size is quoted with, whatever the token's own precision
+const MAX_HEDGE_SIZE_DECIMALS: u8 = 4;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum Event {
     PoolUpdate(UniV3PoolState),
     HyperliquidBbo(HyperliquidBbo),
+    /// Top of book from any other CEX collector (e.g. Binance)
+    CexBbo(CexBbo),
+    FundingRate(FundingRate),
+    BalanceUpdate(BalanceUpdate),
+    Heartbeat(Heartbeat),
+    ConfigReload(ConfigReload),
+}
+
+impl Event {
+    /// When the collector received this event (None for replayed or synthetic events)
+    pub fn received_at(&self) -> Option<Instant> {
+        match self {
+            Event::PoolUpdate(state) => state.received_at,
+            Event::HyperliquidBbo(bbo) => bbo.received_at,
+            Event::CexBbo(bbo) => bbo.received_at,
+            Event::FundingRate(_) | Event::BalanceUpdate(_) | Event::Heartbeat(_) | Event::ConfigReload(_) => None,
+        }
+    }
 }
 
 // Re-export for convenience
 pub use crate::executors::arbitrage::ArbitrageAction as Action;
 
+/// Prices and net spreads (both directions) behind a decision
+#[derive(Debug, Clone, Serialize)]
+pub struct Quotes {
+    pub dex_bid: f64,
+    pub dex_ask: f64,
+    pub hl_bid: f64,
+    pub hl_ask: f64,
+    /// Buy DEX → Sell HL
+    pub net_profit_1_bps: f64,
+    /// Buy HL → Sell DEX
+    pub net_profit_2_bps: f64,
+}
+
+/// Outcome of running the strategy's decision logic on one DEX state / HL BBO pair
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "decision", rename_all = "snake_case")]
+pub enum Decision {
+    Trade {
+        direction: Direction,
+        net_profit_bps: f64,
+        quotes: Quotes,
+        action: Action,
+    },
+    NoTrade {
+        reason: String,
+        quotes: Option<Quotes>,
+    },
+}
+
+impl Decision {
+    fn no_trade(reason: &str, quotes: Option<Quotes>) -> Self {
+        Decision::NoTrade { reason: reason.to_string(), quotes }
+    }
+
+    pub fn quotes(&self) -> Option<&Quotes> {
+        match self {
+            Decision::Trade { quotes, .. } => Some(quotes),
+            Decision::NoTrade { quotes, .. } => quotes.as_ref(),
+        }
+    }
+}
+
+/// What to do when both directions clear `min_profit_bps` at once. Buying and selling
+/// the same pair profitably both ways means crossed quotes, almost always stale data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BothProfitable {
+    /// Treat it as a data anomaly and don't trade
+    #[default]
+    Skip,
+    /// Trade the more profitable direction
+    TakeBest,
+}
+
+/// Band around `min_profit_bps` that keeps a spread hovering at the threshold from
+/// firing repeatedly. Entry needs `min + enter_buffer_bps`; after a trade that direction
+/// re-arms once the spread drops below `min - exit_buffer_bps` or `cooldown` (if any) elapses.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Hysteresis {
+    pub enter_buffer_bps: f64,
+    pub exit_buffer_bps: f64,
+    pub cooldown: Option<Duration>,
+}
+
+/// Arbitrage between a Uniswap V3 HYPE/USDC pool and a CEX hedge. The hedge venue is
+/// Hyperliquid or, with `cex_venue = "binance"`, Binance: either feeds a [CexBbo] priced
+/// at that venue's fee, so the same gating serves both.
 #[derive(Debug, Clone)]
 pub struct HypeUsdcCrossArbitrage {
-    hyperliquid_bbo: Option<HyperliquidBbo>,
-    hyperswap_state: Option<UniV3PoolState>,
+    // Config name, used to pick this strategy's parameters out of a reload
+    name: String,
+    // Period of the engine-driven status line; None disables it
+    tick_interval: Option<Duration>,
+    // Warn once if a feed still hasn't produced data this long after startup
+    startup_grace: Option<Duration>,
+    started_at: Instant,
+    feeds_ready: bool,
+    missing_warned: bool,
+    // Hedge venue top of book, normalized from whichever CEX feed is configured
+    cex_bbo: Option<CexBbo>,
+    // Spreads from the last evaluation, reported on heartbeats
+    last_quotes: Option<Quotes>,
+    // Receive time of the event being processed, stamped onto actions for latency logs
+    last_event_at: Option<Instant>,
+    // Latest state per watched pool (same pair, possibly different fee tiers)
+    pool_states: HashMap<Address, UniV3PoolState>,
+    // Pools this strategy trades; empty accepts updates from any pool
+    pools: Vec<Address>,
     // Fee and order configuration
     order_size_usd: f64,
-    hl_maker_fee_bps: f64,  // e.g., 2.0 for 0.02% fee, -2.0 for 0.02% rebate
+    // Dynamic sizing: (min, max) notional, scaled by edge over `min_profit_bps` across
+    // `size_ramp_bps`; the edge at `order_size_usd` picks the size, which is then re-quoted
+    notional_bounds: Option<(f64, f64)>,
+    size_ramp_bps: f64,
+    cex_fee_bps: f64,  // Hedge venue fee, e.g., 2.0 for 0.02% fee, -2.0 for 0.02% rebate
     dex_gas_fee_usd: f64,
+    // Gas per swap in native units, valued at the oracle price when one is available
+    native_gas: Option<(f64, Arc<NativePriceOracle>)>,
     min_profit_bps: f64,
     slippage_bps: f64,
+    // Quote the DEX from a simulated swap of the trade size instead of mid ± fee
+    simulate_price_impact: bool,
+    // Treat DEX/HL mids further apart than this as bad data, not an opportunity
+    max_divergence_bps: Option<f64>,
+    // Skip trades whose DEX swap would move the pool price further than this
+    max_price_impact_bps: Option<f64>,
+    // Quotes outside this band are bad data (bad tick, wrong decimals), never a spread
+    sanity_min_price: Option<f64>,
+    sanity_max_price: Option<f64>,
+    on_both_profitable: BothProfitable,
+    hl_tif: TimeInForce,
+    hl_order_mode: OrderMode,
+    // HL market the hedge is placed on ("HYPE/USDC", "@107", or a perp name)
+    hl_coin: String,
+    // Smallest hedge the venue accepts; smaller signals are dropped before the DEX leg
+    min_hedge_value_usd: f64,
+    // Funding-aware mode: expected hours the perp leg is held (None ignores funding)
+    funding_holding_hours: Option<f64>,
+    funding_rate: Option<FundingRate>,
+    // Latest net edge, published for executors slicing orders over time
+    edge_gauge: Option<Arc<EdgeGauge>>,
+    // Stop trading once executed notional in the tracker's window reaches the cap
+    notional_cap: Option<(f64, Arc<PositionTracker>)>,
+    notional_paused: bool,
+    // Directions that traded and wait for the spread to reset before re-entering
+    hysteresis: Option<Hysteresis>,
+    disarmed: HashMap<Direction, Instant>,
+    // Suppress re-emitting the same (direction, size) within this window
+    dedup_window: Option<Duration>,
+    last_emitted: HashMap<(Direction, u64), Instant>,
+    // EMA window for gating on the edge; raw edges are still logged and quoted
+    smoothing_window: Option<u32>,
+    smoothed_bps: Option<(f64, f64)>,
+    // Stop opening trades while the wallet can't pay gas or fund the DEX leg
+    min_gas_balance: Option<U256>,
+    balances: Option<BalanceUpdate>,
+    balance_paused: bool,
     // Token addresses for DEX swaps (used when execution is enabled)
     #[allow(dead_code)]
     usdc_address: Address,
@@ -45,15 +208,66 @@ impl HypeUsdcCrossArbitrage {
             .map_err(|_| anyhow::anyhow!("Invalid token_a address"))?;
         let hype_address = config.token_b_address.parse()
             .map_err(|_| anyhow::anyhow!("Invalid token_b address"))?;
+        let pools = std::iter::once(&config.pool_address)
+            .chain(&config.pool_addresses)
+            .map(|pool| pool.parse().map_err(|_| anyhow::anyhow!("Invalid pool address {}", pool)))
+            .collect::<Result<Vec<Address>>>()?;
+        let hysteresis_configured = config.enter_buffer_bps != 0.0
+            || config.exit_buffer_bps != 0.0
+            || config.reentry_cooldown_secs.is_some();
+        let hysteresis = hysteresis_configured.then(|| Hysteresis {
+            enter_buffer_bps: config.enter_buffer_bps,
+            exit_buffer_bps: config.exit_buffer_bps,
+            cooldown: config.reentry_cooldown_secs.map(Duration::from_secs),
+        });
 
         Ok(Self {
-            hyperliquid_bbo: None,
-            hyperswap_state: None,
+            name: config.name.clone(),
+            tick_interval: None,
+            startup_grace: None,
+            started_at: Instant::now(),
+            feeds_ready: false,
+            missing_warned: false,
+            cex_bbo: None,
+            last_quotes: None,
+            last_event_at: None,
+            pool_states: HashMap::new(),
+            pools,
             order_size_usd: config.order_size_usd,
-            hl_maker_fee_bps: config.hl_maker_fee_bps,
+            notional_bounds: config.min_notional_usd.zip(config.max_notional_usd),
+            size_ramp_bps: config.size_ramp_bps,
+            cex_fee_bps: config.hedge_fee_bps(),
             dex_gas_fee_usd: config.dex_gas_fee_usd,
+            native_gas: None,
             min_profit_bps: config.min_profit_bps,
             slippage_bps: config.slippage_bps,
+            simulate_price_impact: config.simulate_price_impact,
+            max_divergence_bps: config.max_divergence_bps,
+            max_price_impact_bps: config.max_price_impact_bps,
+            sanity_min_price: config.sanity_min_price,
+            sanity_max_price: config.sanity_max_price,
+            on_both_profitable: config.on_both_profitable,
+            hl_tif: config.hl_tif,
+            hl_order_mode: config.hl_order_mode,
+            hl_coin: config.hedge_coin().to_string(),
+            min_hedge_value_usd: match config.cex_venue {
+                CexVenue::Hyperliquid => HL_MIN_ORDER_VALUE_USD,
+                CexVenue::Binance => 0.0,
+            },
+            funding_holding_hours: config.funding_aware.then_some(config.funding_holding_hours),
+            funding_rate: None,
+            edge_gauge: None,
+            notional_cap: None,
+            notional_paused: false,
+            hysteresis,
+            disarmed: HashMap::new(),
+            dedup_window: config.dedup_window_ms.map(Duration::from_millis),
+            last_emitted: HashMap::new(),
+            smoothing_window: (config.smoothing_window > 0).then_some(config.smoothing_window),
+            smoothed_bps: None,
+            min_gas_balance: config.min_gas_balance.map(native_to_wei),
+            balances: None,
+            balance_paused: false,
             usdc_address,
             hype_address,
             dex_fee: config.fee,
@@ -71,100 +285,526 @@ impl HypeUsdcCrossArbitrage {
         dex_fee: u32,
     ) -> Self {
         Self {
-            hyperliquid_bbo: None,
-            hyperswap_state: None,
+            name: "default".to_string(),
+            tick_interval: None,
+            startup_grace: None,
+            started_at: Instant::now(),
+            feeds_ready: false,
+            missing_warned: false,
+            cex_bbo: None,
+            last_quotes: None,
+            last_event_at: None,
+            pool_states: HashMap::new(),
+            pools: Vec::new(),
             order_size_usd,
-            hl_maker_fee_bps,
+            notional_bounds: None,
+            size_ramp_bps: 0.0,
+            cex_fee_bps: hl_maker_fee_bps,
             dex_gas_fee_usd,
+            native_gas: None,
             min_profit_bps,
             slippage_bps: 50.0,  // Default for examples
+            simulate_price_impact: false,
+            max_divergence_bps: None,
+            max_price_impact_bps: None,
+            sanity_min_price: None,
+            sanity_max_price: None,
+            on_both_profitable: BothProfitable::default(),
+            hl_tif: TimeInForce::Ioc,
+            hl_order_mode: OrderMode::Limit,
+            hl_coin: "HYPE/USDC".to_string(),
+            min_hedge_value_usd: HL_MIN_ORDER_VALUE_USD,
+            funding_holding_hours: None,
+            funding_rate: None,
+            edge_gauge: None,
+            notional_cap: None,
+            notional_paused: false,
+            hysteresis: None,
+            disarmed: HashMap::new(),
+            dedup_window: None,
+            last_emitted: HashMap::new(),
+            smoothing_window: None,
+            smoothed_bps: None,
+            min_gas_balance: None,
+            balances: None,
+            balance_paused: false,
             usdc_address,
             hype_address,
             dex_fee,
         }
     }
 
-    fn calculate_dex_bid_ask(&self, state: &UniV3PoolState) -> Option<(f64, f64)> {
-        let sqrt_price_bytes = state.sqrt_price.to_be_bytes::<32>();
-        let sqrt_price = u128::from_be_bytes([
-            sqrt_price_bytes[16], sqrt_price_bytes[17], sqrt_price_bytes[18], sqrt_price_bytes[19],
-            sqrt_price_bytes[20], sqrt_price_bytes[21], sqrt_price_bytes[22], sqrt_price_bytes[23],
-            sqrt_price_bytes[24], sqrt_price_bytes[25], sqrt_price_bytes[26], sqrt_price_bytes[27],
-            sqrt_price_bytes[28], sqrt_price_bytes[29], sqrt_price_bytes[30], sqrt_price_bytes[31],
-        ]) as f64;
-        
-        let q96 = 2_f64.powi(96);
-        let base_price = (sqrt_price / q96).powi(2);
-        let decimal_adjustment = 10_f64.powi(state.token_a_decimals as i32 - state.token_b_decimals as i32);
-        let mid_price = base_price * decimal_adjustment;
-        
-        let fee_fraction = state.fee as f64 / 1_000_000.0;
-        let bid = mid_price * (1.0 - fee_fraction / 2.0);
-        let ask = mid_price * (1.0 + fee_fraction / 2.0);
-        
-        Some((bid, ask))
+    /// Log latest spreads and feed status every `interval`, even without market events
+    pub fn with_status_interval(mut self, interval: Duration) -> Self {
+        self.tick_interval = Some(interval);
+        self
+    }
+
+    /// Warn, naming the silent source, if either leg has no data `grace` after startup
+    pub fn with_startup_grace(mut self, grace: Duration) -> Self {
+        self.startup_grace = Some(grace);
+        self
+    }
+
+    /// Apply reloaded thresholds and sizing; takes effect from the next evaluation
+    pub fn update_params(&mut self, params: &StrategyParams) {
+        info!(
+            "🔄 {}: min_profit {} → {} bps, slippage {} → {} bps, size ${} → ${}",
+            self.name, self.min_profit_bps, params.min_profit_bps, self.slippage_bps, params.slippage_bps,
+            self.order_size_usd, params.order_size_usd
+        );
+        self.min_profit_bps = params.min_profit_bps;
+        self.slippage_bps = params.slippage_bps;
+        self.order_size_usd = params.order_size_usd;
+        self.cex_fee_bps = params.hedge_fee_bps;
+        self.dex_gas_fee_usd = params.dex_gas_fee_usd;
+        self.max_divergence_bps = params.max_divergence_bps;
+    }
+
+    /// Fold expected perp funding over `holding_hours` into the profitability check
+    pub fn with_funding(mut self, holding_hours: f64) -> Self {
+        self.funding_holding_hours = Some(holding_hours);
+        self
+    }
+
+    /// Publish each evaluation's net edge to `gauge`
+    pub fn with_edge_gauge(mut self, gauge: Arc<EdgeGauge>) -> Self {
+        self.edge_gauge = Some(gauge);
+        self
+    }
+
+    /// Value gas as `gas_native` of the native token at the oracle's USD price, falling
+    /// back to `dex_gas_fee_usd` while the oracle has no price
+    pub fn with_native_gas(mut self, gas_native: f64, oracle: Arc<NativePriceOracle>) -> Self {
+        self.native_gas = Some((gas_native, oracle));
+        self
+    }
+
+    fn gas_fee_usd(&self) -> f64 {
+        self.native_gas.as_ref()
+            .and_then(|(gas_native, oracle)| Some(gas_native * oracle.price_usd()?))
+            .unwrap_or(self.dex_gas_fee_usd)
+    }
+
+    /// USD price of the gas token for PnL: the oracle's if fresh, else HYPE on HL
+    fn native_px_usd(&self, hl_price: f64) -> f64 {
+        self.native_gas.as_ref()
+            .and_then(|(_, oracle)| oracle.price_usd())
+            .unwrap_or(hl_price)
+    }
+
+    /// Price the DEX leg from swaps simulated at the order size, including price impact
+    pub fn with_price_impact(mut self) -> Self {
+        self.simulate_price_impact = true;
+        self
     }
 
-    fn get_hyperliquid_prices(&self, bbo: &HyperliquidBbo) -> Option<(f64, f64)> {
-        if bbo.levels.len() < 2 {
+    /// Reject trades whose DEX swap would move the pool more than `max_impact_bps`,
+    /// estimated from in-range liquidity
+    pub fn with_max_price_impact(mut self, max_impact_bps: f64) -> Self {
+        self.max_price_impact_bps = Some(max_impact_bps);
+        self
+    }
+
+    /// Size trades between `min_usd` and `max_usd` by how far the edge clears `min_profit_bps`
+    pub fn with_dynamic_sizing(mut self, min_usd: f64, max_usd: f64, ramp_bps: f64) -> Self {
+        self.notional_bounds = Some((min_usd, max_usd));
+        self.size_ramp_bps = ramp_bps;
+        self
+    }
+
+    /// Notional to trade at this edge: `order_size_usd` unless dynamic sizing is on
+    fn trade_notional(&self, net_profit_bps: f64) -> f64 {
+        match self.notional_bounds {
+            Some((min, max)) => common::scaled_notional(net_profit_bps - self.min_profit_bps, min, max, self.size_ramp_bps),
+            None => self.order_size_usd,
+        }
+    }
+
+    pub fn with_both_profitable(mut self, policy: BothProfitable) -> Self {
+        self.on_both_profitable = policy;
+        self
+    }
+
+    /// Ignore quotes outside `[min_price, max_price]` (USDC per HYPE)
+    pub fn with_price_band(mut self, min_price: Option<f64>, max_price: Option<f64>) -> Self {
+        self.sanity_min_price = min_price;
+        self.sanity_max_price = max_price;
+        self
+    }
+
+    /// False, with a warning, when `price` falls outside the sanity band
+    fn price_in_band(&self, source: &str, price: f64) -> bool {
+        let too_low = self.sanity_min_price.is_some_and(|min| price < min);
+        let too_high = self.sanity_max_price.is_some_and(|max| price > max);
+        if too_low || too_high {
+            warn!(
+                "{}: {} price {} outside sanity band [{}, {}] - ignoring quote",
+                self.name, source, price,
+                self.sanity_min_price.map_or("-".to_string(), |p| p.to_string()),
+                self.sanity_max_price.map_or("-".to_string(), |p| p.to_string())
+            );
+            return false;
+        }
+        true
+    }
+
+    /// Estimated pool price impact of the DEX leg; None without liquidity data
+    fn price_impact_bps(&self, state: &UniV3PoolState, direction: Direction, mid_price: f64, notional_usd: f64) -> Option<f64> {
+        let hype_is_token0 = self.hype_is_token0(state);
+        let (hype_decimals, usdc_decimals) = self.token_decimals(state);
+        // Buying on the DEX spends USDC; selling spends the HYPE notional_usd buys
+        let (amount_in, zero_for_one) = match direction {
+            Direction::BuyDexSellCex => (notional_usd * 10_f64.powi(usdc_decimals as i32), !hype_is_token0),
+            Direction::BuyCexSellDex => (notional_usd / mid_price * 10_f64.powi(hype_decimals as i32), hype_is_token0),
+        };
+        common::price_impact_bps(state.sqrt_price, state.liquidity, amount_in, zero_for_one)
+    }
+
+    /// Reject signals while DEX and HL mids differ by more than `max_divergence_bps`
+    pub fn with_max_divergence(mut self, max_divergence_bps: f64) -> Self {
+        self.max_divergence_bps = Some(max_divergence_bps);
+        self
+    }
+
+    /// Drop actions identical in direction and size to one emitted less than `window` ago
+    pub fn with_dedup_window(mut self, window: Duration) -> Self {
+        self.dedup_window = Some(window);
+        self
+    }
+
+    /// Dedup key: direction plus hedge size at its quoted precision
+    fn dedup_key(action: &Action) -> (Direction, u64) {
+        let size = (action.hl_order.size * 10_f64.powi(MAX_HEDGE_SIZE_DECIMALS as i32)).round() as u64;
+        (action.direction, size)
+    }
+
+    fn is_duplicate(&self, action: &Action) -> bool {
+        let Some(window) = self.dedup_window else {
+            return false;
+        };
+        self.last_emitted
+            .get(&Self::dedup_key(action))
+            .is_some_and(|emitted_at| emitted_at.elapsed() < window)
+    }
+
+    /// Only trade once an EMA of the direction's edge over about `window` evaluations
+    /// clears the entry threshold, filtering single-tick spikes
+    pub fn with_smoothing(mut self, window: u32) -> Self {
+        self.smoothing_window = (window > 0).then_some(window);
+        self
+    }
+
+    /// Fold the latest edges into the per-direction EMA
+    fn smooth(&mut self, quotes: &Quotes) {
+        let Some(window) = self.smoothing_window else {
+            return;
+        };
+        let previous = self.smoothed_bps;
+        self.smoothed_bps = Some((
+            common::ema(previous.map(|(bps, _)| bps), quotes.net_profit_1_bps, window),
+            common::ema(previous.map(|(_, bps)| bps), quotes.net_profit_2_bps, window),
+        ));
+    }
+
+    /// Edge to gate `direction` on: the EMA when smoothing, else the raw value
+    fn gating_bps(&self, direction: Direction, net_profit_bps: f64) -> f64 {
+        match self.smoothed_bps {
+            Some((buy_dex_bps, _)) if direction.buys_dex() => buy_dex_bps,
+            Some((_, buy_cex_bps)) => buy_cex_bps,
+            None => net_profit_bps,
+        }
+    }
+
+    pub fn with_hysteresis(mut self, hysteresis: Hysteresis) -> Self {
+        self.hysteresis = Some(hysteresis);
+        self
+    }
+
+    /// Re-arm directions whose spread fell back through the exit band or whose
+    /// cooldown ran out
+    fn rearm(&mut self, quotes: &Quotes) {
+        let Some(hysteresis) = self.hysteresis else {
+            return;
+        };
+        let exit_bps = self.min_profit_bps - hysteresis.exit_buffer_bps;
+        self.disarmed.retain(|direction, traded_at| {
+            let net_bps = if direction.buys_dex() { quotes.net_profit_1_bps } else { quotes.net_profit_2_bps };
+            let cooled = hysteresis.cooldown.is_some_and(|cooldown| traded_at.elapsed() >= cooldown);
+            net_bps >= exit_bps && !cooled
+        });
+    }
+
+    fn entry_threshold_bps(&self) -> f64 {
+        self.min_profit_bps + self.hysteresis.map_or(0.0, |h| h.enter_buffer_bps)
+    }
+
+    /// Pause once `tracker` reports at least `max_notional_usd` executed in its window
+    pub fn with_notional_cap(mut self, max_notional_usd: f64, tracker: Arc<PositionTracker>) -> Self {
+        self.notional_cap = Some((max_notional_usd, tracker));
+        self
+    }
+
+    /// Whether the notional cap currently blocks trading; logs on pause/resume only
+    fn notional_cap_reached(&mut self) -> bool {
+        let Some((max_notional_usd, tracker)) = &self.notional_cap else {
+            return false;
+        };
+        let traded = tracker.notional_in_window();
+        let reached = traded >= *max_notional_usd;
+        if reached && !self.notional_paused {
+            warn!("⏸️  Notional cap reached (${:.0} >= ${:.0}/h) - pausing", traded, max_notional_usd);
+        } else if !reached && self.notional_paused {
+            info!("▶️  Notional back under cap (${:.0} < ${:.0}/h) - resuming", traded, max_notional_usd);
+        }
+        self.notional_paused = reached;
+        reached
+    }
+
+    /// Pause while the native balance is below `min_gas_wei`. Balances come from
+    /// `Event::BalanceUpdate`; until the first one arrives trading is not gated.
+    pub fn with_min_gas_balance(mut self, min_gas_wei: U256) -> Self {
+        self.min_gas_balance = Some(min_gas_wei);
+        self
+    }
+
+    /// Whether the last balance snapshot is too low to open `action`; logs on pause/resume only
+    fn balance_insufficient(&mut self, action: &Action) -> bool {
+        let Some(balances) = &self.balances else {
+            return false;
+        };
+        let low_gas = self.min_gas_balance.is_some_and(|min| balances.native < min);
+        let low_input = balances.token(action.dex_swap.token_in)
+            .is_some_and(|balance| balance < action.dex_swap.amount_in);
+        let insufficient = low_gas || low_input;
+        if insufficient && !self.balance_paused {
+            if low_gas {
+                warn!("⏸️  Native balance {} below min gas balance - pausing", balances.native);
+            } else {
+                warn!("⏸️  Balance of {} too low for {} - pausing", action.dex_swap.token_in, action.direction);
+            }
+        } else if !insufficient && self.balance_paused {
+            info!("▶️  Balances sufficient - resuming");
+        }
+        self.balance_paused = insufficient;
+        insufficient
+    }
+
+    /// Use this funding rate until the collector reports a newer one
+    pub fn set_funding_rate(&mut self, funding: FundingRate) {
+        self.funding_rate = Some(funding);
+    }
+
+    /// Watched pool quoting the best DEX price for a direction: lowest ask to buy HYPE
+    /// on the DEX, highest bid to sell it there
+    pub fn best_pool_for(&self, direction: Direction) -> Option<&UniV3PoolState> {
+        self.pool_states.values()
+            .filter_map(|state| Some((state, self.calculate_dex_bid_ask(state, self.order_size_usd)?)))
+            .max_by(|(_, (bid_a, ask_a)), (_, (bid_b, ask_b))| {
+                match direction {
+                    Direction::BuyDexSellCex => ask_b.total_cmp(ask_a),
+                    Direction::BuyCexSellDex => bid_a.total_cmp(bid_b),
+                }
+            })
+            .map(|(state, _)| state)
+    }
+
+    /// Uniswap orders pool tokens by address. Use the pool's reported token0 when
+    /// available, otherwise derive it from the configured addresses.
+    fn hype_is_token0(&self, state: &UniV3PoolState) -> bool {
+        if state.token0 != Address::ZERO {
+            state.token0 == self.hype_address
+        } else {
+            self.hype_address < self.usdc_address
+        }
+    }
+
+    /// (HYPE, USDC) decimals as reported by the pool
+    fn token_decimals(&self, state: &UniV3PoolState) -> (u8, u8) {
+        if self.hype_is_token0(state) {
+            (state.token_a_decimals, state.token_b_decimals)
+        } else {
+            (state.token_b_decimals, state.token_a_decimals)
+        }
+    }
+
+    /// Average execution prices (USDC per HYPE) of `notional_usd` swaps in each direction,
+    /// fees and price impact included. None without live tick state or if simulation fails.
+    fn simulate_dex_bid_ask(&self, state: &UniV3PoolState, mid_price: f64, notional_usd: f64) -> Option<(f64, f64)> {
+        let pool = state.pool.as_ref()?;
+        let (hype_decimals, usdc_decimals) = self.token_decimals(state);
+        let hype_scale = 10_f64.powi(hype_decimals as i32);
+        let usdc_scale = 10_f64.powi(usdc_decimals as i32);
+
+        // Ask: spend notional_usd of USDC for HYPE
+        let usdc_in = common::to_raw(notional_usd, usdc_decimals);
+        let hype_out = pool.simulate_swap(self.usdc_address, self.hype_address, usdc_in).ok()?;
+        let hype_out = hype_out.to_string().parse::<f64>().ok()? / hype_scale;
+
+        // Bid: sell the HYPE that notional_usd buys at mid
+        let hype_amount = notional_usd / mid_price;
+        let hype_in = common::to_raw(hype_amount, hype_decimals);
+        let usdc_out = pool.simulate_swap(self.hype_address, self.usdc_address, hype_in).ok()?;
+        let usdc_out = usdc_out.to_string().parse::<f64>().ok()? / usdc_scale;
+
+        if hype_out <= 0.0 || usdc_out <= 0.0 {
             return None;
         }
+        Some((usdc_out / hype_amount, notional_usd / hype_out))
+    }
 
-        let raw_bid = bbo.levels[0].as_ref()?.px.parse::<f64>().ok()?;
-        let raw_ask = bbo.levels[1].as_ref()?.px.parse::<f64>().ok()?;
+    /// [simulate_dex_bid_ask](Self::simulate_dex_bid_ask) from in-range liquidity alone, for
+    /// states without tick data (replays, polled or what-if states)
+    fn estimate_dex_bid_ask(&self, state: &UniV3PoolState, mid_price: f64, notional_usd: f64) -> Option<(f64, f64)> {
+        let hype_is_token0 = self.hype_is_token0(state);
+        let (hype_decimals, usdc_decimals) = self.token_decimals(state);
+        let hype_scale = 10_f64.powi(hype_decimals as i32);
+        let usdc_scale = 10_f64.powi(usdc_decimals as i32);
 
-        // Apply maker fee to spread (like we do for DEX)
-        // Convert bps to decimal: positive fee = cost, negative fee = rebate
-        let hl_maker_fee = self.hl_maker_fee_bps / 10000.0;
-        let bid = raw_bid * (1.0 - hl_maker_fee);
-        let ask = raw_ask * (1.0 + hl_maker_fee);
+        // Ask: spend notional_usd of USDC for HYPE
+        let usdc_in = notional_usd * usdc_scale;
+        let hype_out = common::single_range_amount_out(state.sqrt_price, state.liquidity, state.fee, usdc_in, !hype_is_token0)?
+            / hype_scale;
 
-        Some((bid, ask))
+        // Bid: sell the HYPE that notional_usd buys at mid
+        let hype_amount = notional_usd / mid_price;
+        let hype_in = hype_amount * hype_scale;
+        let usdc_out = common::single_range_amount_out(state.sqrt_price, state.liquidity, state.fee, hype_in, hype_is_token0)?
+            / usdc_scale;
+
+        Some((usdc_out / hype_amount, notional_usd / hype_out))
     }
 
-    /// Calculate net profit in basis points after all fees
-    fn calculate_net_profit_bps(&self, buy_price: f64, sell_price: f64) -> f64 {
-        // Gross profit percentage (fees already in spread)
-        let gross_profit_pct = (sell_price - buy_price) / buy_price;
-        
-        // DEX gas fee as percentage of trade
-        let gas_fee_pct = self.dex_gas_fee_usd / self.order_size_usd;
+    /// DEX bid/ask for a `notional_usd` swap; mid ± fee unless price impact is simulated
+    fn calculate_dex_bid_ask(&self, state: &UniV3PoolState, notional_usd: f64) -> Option<(f64, f64)> {
+        // Pool price is token1 per token0; we quote USDC per HYPE
+        let pool_price = common::pool_price(state.sqrt_price, state.token_a_decimals, state.token_b_decimals)?;
+        let mid_price = if self.hype_is_token0(state) { pool_price } else { 1.0 / pool_price };
+        if !self.price_in_band("DEX", mid_price) {
+            return None;
+        }
         
-        // Net profit percentage after gas fee
-        let net_profit_pct = gross_profit_pct - gas_fee_pct;
+        if self.simulate_price_impact {
+            let sized = self.simulate_dex_bid_ask(state, mid_price, notional_usd)
+                .or_else(|| self.estimate_dex_bid_ask(state, mid_price, notional_usd));
+            if let Some(sized) = sized {
+                return Some(sized);
+            }
+        }
         
-        // Convert to basis points
-        net_profit_pct * 10000.0
+        // Quoted as token1 per token0; inverting to USDC per HYPE swaps the sides
+        let (bid, ask) = common::dex_bid_ask(state.sqrt_price, state.fee, state.token_a_decimals, state.token_b_decimals)?;
+        Some(if self.hype_is_token0(state) { (bid, ask) } else { (1.0 / ask, 1.0 / bid) })
     }
 
-    fn generate_action(&self, buy_dex: bool, dex_price: f64, hl_price: f64) -> Action {
+    fn get_cex_prices(&self, bbo: &CexBbo) -> Option<(f64, f64)> {
+        let (raw_bid, raw_ask) = (bbo.bid, bbo.ask);
+        if !(raw_bid > 0.0 && raw_ask > 0.0) {
+            return None;
+        }
+        if !self.price_in_band(bbo.venue.as_str(), (raw_bid + raw_ask) / 2.0) {
+            return None;
+        }
+
+        // Apply the venue fee to the spread (like we do for DEX)
+        Some(common::apply_fee_bps(raw_bid, raw_ask, self.cex_fee_bps))
+    }
+
+    /// Calculate net profit in basis points after all fees, with gas spread over `notional_usd`
+    fn calculate_net_profit_bps(&self, buy_price: f64, sell_price: f64, notional_usd: f64) -> f64 {
+        common::net_profit_bps(buy_price, sell_price, self.gas_fee_usd(), notional_usd)
+    }
+
+    /// DEX price and net edge of one direction at `notional_usd`
+    fn quote_direction(&self, state: &UniV3PoolState, hl_bid: f64, hl_ask: f64, direction: Direction, notional_usd: f64) -> Option<(f64, f64)> {
+        let (dex_bid, dex_ask) = self.calculate_dex_bid_ask(state, notional_usd)?;
+        let (hl_bid, hl_ask) = self.carry_adjusted(hl_bid, hl_ask);
+        Some(match direction {
+            Direction::BuyDexSellCex => (dex_ask, self.calculate_net_profit_bps(dex_ask, hl_bid, notional_usd)),
+            Direction::BuyCexSellDex => (dex_bid, self.calculate_net_profit_bps(hl_ask, dex_bid, notional_usd)),
+        })
+    }
+
+    /// (notional, DEX price, net edge) to trade. Dynamic sizing picks a notional from the
+    /// edge quoted at `order_size_usd` and re-quotes it there, since impact and gas per bp
+    /// change with size; if the edge does not hold at that size, trade the quoted size.
+    fn size_trade(&self, state: &UniV3PoolState, hl_bid: f64, hl_ask: f64, direction: Direction, dex_price: f64, net_profit_bps: f64) -> (f64, f64, f64) {
+        let quoted = (self.order_size_usd, dex_price, net_profit_bps);
+        let notional_usd = self.trade_notional(net_profit_bps);
+        if notional_usd == self.order_size_usd {
+            return quoted;
+        }
+        match self.quote_direction(state, hl_bid, hl_ask, direction, notional_usd) {
+            Some((dex_price, net_profit_bps)) if net_profit_bps > self.min_profit_bps => (notional_usd, dex_price, net_profit_bps),
+            requoted => {
+                debug!(
+                    "{}: edge {:.2} bps at ${:.0} is {:.2} bps at ${:.0} - keeping the quoted size",
+                    self.name, net_profit_bps, self.order_size_usd,
+                    requoted.map_or(f64::NAN, |(_, bps)| bps), notional_usd
+                );
+                quoted
+            }
+        }
+    }
+
+    /// HL quotes with the expected funding for holding the HL leg folded in: shorts
+    /// receive positive funding, as if they sold higher, and longs pay it, as if they
+    /// bought higher. Unchanged unless funding-aware mode has a rate to work with.
+    fn carry_adjusted(&self, hl_bid: f64, hl_ask: f64) -> (f64, f64) {
+        let (Some(hours), Some(funding)) = (self.funding_holding_hours, &self.funding_rate) else {
+            return (hl_bid, hl_ask);
+        };
+        // Rates are per hourly settlement
+        let carry = 1.0 + funding.rate * hours;
+        (hl_bid * carry, hl_ask * carry)
+    }
+
+    fn generate_action(&self, direction: Direction, dex_state: &UniV3PoolState, dex_price: f64, hl_price: f64, net_profit_bps: f64, notional_usd: f64) -> Action {
         use alloy::primitives::U256;
         use crate::executors::{univ3::UniV3SwapAction, hyperliquid::HyperliquidOrderAction};
         
-        let hype_amount_raw = self.order_size_usd / dex_price;
-        let hype_amount = (hype_amount_raw * 10000.0).round() / 10000.0;
-        let usdc_raw = (self.order_size_usd * 1_000_000.0) as u64;
-        let hype_raw = U256::from((hype_amount * 1e18) as u128);
+        let dex_fee = dex_state.fee;
+        let (hype_decimals, usdc_decimals) = self.token_decimals(dex_state);
+        // Hedge venues size far coarser than the token; the executors round further
+        let hype_amount = common::round_to_decimals(
+            notional_usd / dex_price,
+            hype_decimals.min(MAX_HEDGE_SIZE_DECIMALS),
+        );
+        let usdc_raw = common::to_raw(notional_usd, usdc_decimals);
+        let hype_raw = common::to_raw(hype_amount, hype_decimals);
+        let gross_profit_usd = net_profit_bps / 10000.0 * notional_usd + self.gas_fee_usd();
         
         // Get slippage from config
-        if buy_dex {
+        if direction.buys_dex() {
             let hl_sell_price = hl_price * (1.0 - self.slippage_bps / 10000.0);
             
             Action {
                 dex_swap: UniV3SwapAction {
                     token_in: self.usdc_address,
                     token_out: self.hype_address,
-                    fee: self.dex_fee,
-                    amount_in: U256::from(usdc_raw),
+                    fee: dex_fee,
+                    amount_in: usdc_raw,
                     amount_out_min: U256::ZERO,
                 },
                 hl_order: HyperliquidOrderAction {
-                    coin: "HYPE/USDC".to_string(),
+                    coin: self.hl_coin.clone(),
                     is_buy: false,
                     size: hype_amount,
                     limit_px: hl_sell_price,
+                    tif: self.hl_tif,
+                    mode: self.hl_order_mode,
+                    reduce_only: false,
                 },
-                direction: "Buy DEX".to_string(),
+                direction: Direction::BuyDexSellCex,
+                expected_dex_out: hype_raw,
+                decision_hl_px: hl_price,
+                net_profit_bps,
+                expected_gross_profit_usd: gross_profit_usd,
+                // HyperEVM gas is paid in HYPE
+                native_px_usd: self.native_px_usd(hl_price),
+                hype_decimals,
+                slippage_bps: self.slippage_bps,
+                observed_at: self.last_event_at,
             }
         } else {
             let hl_buy_price = hl_price * (1.0 + self.slippage_bps / 10000.0);
@@ -173,77 +813,870 @@ impl HypeUsdcCrossArbitrage {
                 dex_swap: UniV3SwapAction {
                     token_in: self.hype_address,
                     token_out: self.usdc_address,
-                    fee: self.dex_fee,
+                    fee: dex_fee,
                     amount_in: hype_raw,
                     amount_out_min: U256::ZERO,
                 },
                 hl_order: HyperliquidOrderAction {
-                    coin: "HYPE/USDC".to_string(),
+                    coin: self.hl_coin.clone(),
                     is_buy: true,
                     size: hype_amount,
                     limit_px: hl_buy_price,
+                    tif: self.hl_tif,
+                    mode: self.hl_order_mode,
+                    reduce_only: false,
                 },
-                direction: "Buy HL".to_string(),
+                direction: Direction::BuyCexSellDex,
+                expected_dex_out: common::to_raw(hype_amount * dex_price, usdc_decimals),
+                decision_hl_px: hl_price,
+                net_profit_bps,
+                expected_gross_profit_usd: gross_profit_usd,
+                // HyperEVM gas is paid in HYPE
+                native_px_usd: self.native_px_usd(hl_price),
+                hype_decimals,
+                slippage_bps: self.slippage_bps,
+                observed_at: self.last_event_at,
             }
         }
     }
     
-    fn check_and_generate_actions(&mut self) -> Vec<Action> {
-        let (hl_bbo, dex_state) = match (&self.hyperliquid_bbo, &self.hyperswap_state) {
-            (Some(b), Some(d)) => (b, d),
-            _ => return vec![],
-        };
+    /// Trade decision, unless the hedge is below the venue minimum: the HL leg would be
+    /// rejected after the DEX swap had already committed capital
+    fn trade(&self, direction: Direction, net_profit_bps: f64, action: Action, quotes: Quotes) -> Decision {
+        let hedge_value = action.hl_order.size * action.hl_order.limit_px;
+        if hedge_value < self.min_hedge_value_usd {
+            return Decision::no_trade(
+                &format!("hedge value ${:.2} below ${} venue minimum", hedge_value, self.min_hedge_value_usd),
+                Some(quotes),
+            );
+        }
+        Decision::Trade { direction, net_profit_bps, quotes, action }
+    }
 
-        let (dex_bid, dex_ask) = match self.calculate_dex_bid_ask(dex_state) {
+    /// Run the decision logic on a DEX state / CEX BBO pair without touching live state
+    pub fn evaluate(&self, dex_state: &UniV3PoolState, hl_bbo: &CexBbo) -> Decision {
+        let (dex_bid, dex_ask) = match self.calculate_dex_bid_ask(dex_state, self.order_size_usd) {
             Some(p) => p,
-            None => return vec![],
+            None => return Decision::no_trade("invalid DEX state", None),
         };
 
-        let (hl_bid, hl_ask) = match self.get_hyperliquid_prices(hl_bbo) {
+        let (hl_bid, hl_ask) = match self.get_cex_prices(hl_bbo) {
             Some(p) => p,
-            None => return vec![],
+            None => return Decision::no_trade(&format!("no usable {} quote", hl_bbo.venue.as_str()), None),
         };
 
-        let net_profit_1_bps = self.calculate_net_profit_bps(dex_ask, hl_bid);
-        let net_profit_2_bps = self.calculate_net_profit_bps(hl_ask, dex_bid);
+        if let Some(max_bps) = self.max_divergence_bps {
+            let dex_mid = (dex_bid + dex_ask) / 2.0;
+            let hl_mid = (hl_bid + hl_ask) / 2.0;
+            let divergence_bps = common::divergence_bps(dex_mid, hl_mid);
+            if divergence_bps > max_bps {
+                warn!(
+                    dex_mid,
+                    hl_mid,
+                    divergence_bps,
+                    "⚠️  Rejecting signal: DEX {:.4} vs HL {:.4} diverge {:.0} bps (> {} bps)",
+                    dex_mid, hl_mid, divergence_bps, max_bps
+                );
+                return Decision::no_trade(
+                    &format!("price divergence {:.0} bps above {} bps limit", divergence_bps, max_bps),
+                    None,
+                );
+            }
+        }
 
-        // Log spreads without slippage
-        info!("DEX {:.3}/{:.3} | HL {:.3}/{:.3} | Net: {:+.2}%/{:+.2}%",
-            dex_bid, dex_ask, hl_bid, hl_ask, net_profit_1_bps / 100.0, net_profit_2_bps / 100.0);
+        // Edges include funding; the hedge is still priced off the quoted book
+        let (carry_bid, carry_ask) = self.carry_adjusted(hl_bid, hl_ask);
+        let quotes = Quotes {
+            dex_bid,
+            dex_ask,
+            hl_bid,
+            hl_ask,
+            net_profit_1_bps: self.calculate_net_profit_bps(dex_ask, carry_bid, self.order_size_usd),
+            net_profit_2_bps: self.calculate_net_profit_bps(carry_ask, dex_bid, self.order_size_usd),
+        };
+
+        let both_profitable = quotes.net_profit_1_bps > self.min_profit_bps && quotes.net_profit_2_bps > self.min_profit_bps;
+        let direction = match self.on_both_profitable {
+            BothProfitable::Skip if both_profitable => {
+                warn!(
+                    net_profit_1_bps = quotes.net_profit_1_bps,
+                    net_profit_2_bps = quotes.net_profit_2_bps,
+                    "⚠️  Rejecting signal: both directions profitable ({:+.2} / {:+.2} bps), quotes look crossed",
+                    quotes.net_profit_1_bps, quotes.net_profit_2_bps
+                );
+                return Decision::no_trade("both directions profitable (crossed quotes)", Some(quotes));
+            }
+            BothProfitable::TakeBest if both_profitable => Some(if quotes.net_profit_1_bps >= quotes.net_profit_2_bps {
+                Direction::BuyDexSellCex
+            } else {
+                Direction::BuyCexSellDex
+            }),
+            // Gas is the one cost not in the prices
+            _ => common::choose_direction(
+                dex_bid,
+                dex_ask,
+                carry_bid,
+                carry_ask,
+                self.min_profit_bps + common::gas_cost_bps(self.gas_fee_usd(), self.order_size_usd),
+            ),
+        };
+        let Some(direction) = direction else {
+            return Decision::no_trade(
+                &format!("best spread {:.2} bps below {} bps threshold",
+                    quotes.net_profit_1_bps.max(quotes.net_profit_2_bps), self.min_profit_bps),
+                Some(quotes),
+            );
+        };
+        let (dex_price, hl_price, quoted_bps) = match direction {
+            Direction::BuyDexSellCex => (dex_ask, hl_bid, quotes.net_profit_1_bps),
+            Direction::BuyCexSellDex => (dex_bid, hl_ask, quotes.net_profit_2_bps),
+        };
+        let (notional_usd, dex_price, net_profit_bps) = self.size_trade(dex_state, hl_bid, hl_ask, direction, dex_price, quoted_bps);
 
-        if net_profit_1_bps > self.min_profit_bps {
-            info!("🎯 EXEC: Buy DEX → Sell HL ({:.2} bps > {} bps threshold)", 
-                net_profit_1_bps, self.min_profit_bps);
-            return vec![self.generate_action(true, dex_ask, hl_bid)];
+        if let Some(max_bps) = self.max_price_impact_bps {
+            let dex_mid = (dex_bid + dex_ask) / 2.0;
+            if let Some(impact_bps) = self.price_impact_bps(dex_state, direction, dex_mid, notional_usd) {
+                if impact_bps > max_bps {
+                    return Decision::no_trade(
+                        &format!("price impact {:.1} bps above {} bps limit", impact_bps, max_bps),
+                        Some(quotes),
+                    );
+                }
+            }
         }
-        if net_profit_2_bps > self.min_profit_bps {
-            info!("🎯 EXEC: Buy HL → Sell DEX ({:.2} bps > {} bps threshold)", 
-                net_profit_2_bps, self.min_profit_bps);
-            return vec![self.generate_action(false, dex_bid, hl_ask)];
+
+        let action = self.generate_action(direction, dex_state, dex_price, hl_price, net_profit_bps, notional_usd);
+        self.trade(direction, net_profit_bps, action, quotes)
+    }
+
+    /// Periodic liveness line: which legs have data and the latest spreads
+    fn log_heartbeat(&self) {
+        let has_dex = !self.pool_states.is_empty();
+        let has_cex = self.cex_bbo.is_some();
+        match &self.last_quotes {
+            Some(q) => info!(
+                has_dex,
+                has_cex,
+                net_profit_1_bps = q.net_profit_1_bps,
+                net_profit_2_bps = q.net_profit_2_bps,
+                "💓 No trade | Net: {:+.2}%/{:+.2}% | DEX {} HL {}",
+                q.net_profit_1_bps / 100.0, q.net_profit_2_bps / 100.0,
+                if has_dex { "✓" } else { "✗" }, if has_cex { "✓" } else { "✗" }
+            ),
+            None => info!(
+                has_dex,
+                has_cex,
+                "💓 Waiting for data | DEX {} HL {}",
+                if has_dex { "✓" } else { "✗" }, if has_cex { "✓" } else { "✗" }
+            ),
         }
+    }
 
-        vec![]
+    /// Sources that haven't produced a first update yet
+    fn missing_feeds(&self) -> Vec<&'static str> {
+        let mut missing = Vec::new();
+        if self.pool_states.is_empty() {
+            missing.push("DEX state");
+        }
+        if self.cex_bbo.is_none() {
+            missing.push("HL BBO");
+        }
+        missing
+    }
+
+    /// Until both legs have reported once, warn when the startup grace period runs out
+    fn check_startup_feeds(&mut self) {
+        if self.feeds_ready {
+            return;
+        }
+        let missing = self.missing_feeds();
+        if missing.is_empty() {
+            self.feeds_ready = true;
+            info!("{}: all feeds live after {:.1}s", self.name, self.started_at.elapsed().as_secs_f64());
+            return;
+        }
+        let Some(grace) = self.startup_grace else {
+            return;
+        };
+        if !self.missing_warned && self.started_at.elapsed() >= grace {
+            self.missing_warned = true;
+            warn!(
+                "{}: no {} after {}s - check that collector is connected",
+                self.name, missing.join(" or "), grace.as_secs()
+            );
+        }
+    }
+
+    fn check_and_generate_actions(&mut self) -> Vec<Action> {
+        let Some(hl_bbo) = &self.cex_bbo else {
+            return vec![];
+        };
+
+        // Each direction routes through its best-priced pool; prefer the stronger trade
+        let mut decisions: Vec<Decision> = [self.best_pool_for(Direction::BuyDexSellCex), self.best_pool_for(Direction::BuyCexSellDex)]
+            .into_iter()
+            .flatten()
+            .map(|dex_state| self.evaluate(dex_state, hl_bbo))
+            .collect();
+        decisions.sort_by(|a, b| match (a, b) {
+            (Decision::Trade { net_profit_bps: x, .. }, Decision::Trade { net_profit_bps: y, .. }) => y.total_cmp(x),
+            (Decision::Trade { .. }, Decision::NoTrade { .. }) => std::cmp::Ordering::Less,
+            (Decision::NoTrade { .. }, Decision::Trade { .. }) => std::cmp::Ordering::Greater,
+            _ => std::cmp::Ordering::Equal,
+        });
+        let Some(decision) = decisions.into_iter().next() else {
+            return vec![];
+        };
+
+        // Log spreads without slippage
+        if let Some(q) = decision.quotes() {
+            if let Some(gauge) = &self.edge_gauge {
+                gauge.update(q.net_profit_1_bps, q.net_profit_2_bps);
+            }
+            info!(
+                dex_bid = q.dex_bid,
+                dex_ask = q.dex_ask,
+                hl_bid = q.hl_bid,
+                hl_ask = q.hl_ask,
+                net_profit_1_bps = q.net_profit_1_bps,
+                net_profit_2_bps = q.net_profit_2_bps,
+                "DEX {:.3}/{:.3} | HL {:.3}/{:.3} | Net: {:+.2}%/{:+.2}%",
+                q.dex_bid, q.dex_ask, q.hl_bid, q.hl_ask, q.net_profit_1_bps / 100.0, q.net_profit_2_bps / 100.0
+            );
+        }
+
+        if let Some(q) = decision.quotes().cloned() {
+            self.smooth(&q);
+            self.rearm(&q);
+            self.last_quotes = Some(q);
+        }
+
+        match decision {
+            Decision::Trade { net_profit_bps, .. } if net_profit_bps <= self.entry_threshold_bps() => vec![],
+            Decision::Trade { direction, net_profit_bps, .. }
+                if self.gating_bps(direction, net_profit_bps) <= self.entry_threshold_bps() => vec![],
+            Decision::Trade { ref action, .. } if self.disarmed.contains_key(&action.direction) => vec![],
+            Decision::Trade { .. } if self.notional_cap_reached() => vec![],
+            Decision::Trade { ref action, .. } if self.balance_insufficient(action) => vec![],
+            Decision::Trade { ref action, .. } if self.is_duplicate(action) => vec![],
+            Decision::Trade { direction, net_profit_bps, action, .. } => {
+                info!(
+                    direction = %action.direction,
+                    net_profit_bps,
+                    smoothed_bps = self.gating_bps(direction, net_profit_bps),
+                    size = action.hl_order.size,
+                    "🎯 EXEC: {} ({:.2} bps > {} bps threshold)",
+                    direction, net_profit_bps, self.min_profit_bps
+                );
+                if self.hysteresis.is_some() {
+                    self.disarmed.insert(action.direction, Instant::now());
+                }
+                if let Some(window) = self.dedup_window {
+                    self.last_emitted.retain(|_, emitted_at| emitted_at.elapsed() < window);
+                    self.last_emitted.insert(Self::dedup_key(&action), Instant::now());
+                }
+                vec![action]
+            }
+            Decision::NoTrade { .. } => vec![],
+        }
     }
 }
 
 #[async_trait]
 impl Strategy<Event, Action> for HypeUsdcCrossArbitrage {
     async fn sync_state(&mut self) -> Result<()> {
+        self.started_at = Instant::now();
+        info!("{}: waiting for first {}", self.name, self.missing_feeds().join(" and "));
         Ok(())
     }
 
+    fn tick_interval(&self) -> Option<Duration> {
+        self.tick_interval
+    }
+
+    async fn on_tick(&mut self) -> Vec<Action> {
+        self.check_startup_feeds();
+        self.log_heartbeat();
+        vec![]
+    }
+
+    fn interested_in(&self, event: &Event) -> bool {
+        match event {
+            Event::PoolUpdate(state) => self.pools.is_empty() || self.pools.contains(&state.address),
+            Event::HyperliquidBbo(bbo) => bbo.coin == self.hl_coin,
+            Event::CexBbo(bbo) => bbo.coin.eq_ignore_ascii_case(&self.hl_coin),
+            Event::FundingRate(funding) => funding.coin == self.hl_coin,
+            Event::BalanceUpdate(_) | Event::Heartbeat(_) | Event::ConfigReload(_) => true,
+        }
+    }
+
     async fn process_event(&mut self, event: Event) -> Vec<Action> {
+        self.last_event_at = event.received_at();
         match event {
             Event::PoolUpdate(state) => {
-                self.hyperswap_state = Some(state);
+                if let Some(last) = self.pool_states.get(&state.address).map(|s| s.block_number) {
+                    if state.block_number <= last {
+                        warn!("Dropping stale pool update from block {} (last {})", state.block_number, last);
+                        return vec![];
+                    }
+                }
+                self.pool_states.insert(state.address, state);
             }
             Event::HyperliquidBbo(bbo) => {
-                self.hyperliquid_bbo = Some(bbo);
+                // An incomplete book clears the quote rather than leaving a stale one
+                self.cex_bbo = CexBbo::from_hyperliquid(&bbo);
+            }
+            Event::CexBbo(bbo) => {
+                self.cex_bbo = Some(bbo);
+            }
+            Event::FundingRate(funding) => {
+                info!("Funding {}: {:+.4}%/h", funding.coin, funding.rate * 100.0);
+                self.set_funding_rate(funding);
+                return vec![];
+            }
+            Event::BalanceUpdate(balances) => {
+                self.balances = Some(balances);
+                return vec![];
+            }
+            Event::Heartbeat(_) => {
+                self.log_heartbeat();
+                return vec![];
+            }
+            Event::ConfigReload(reload) => {
+                if let Some(params) = reload.strategies.iter().find(|p| p.name == self.name) {
+                    self.update_params(params);
+                }
+                return vec![];
             }
         }
-        
+        self.check_startup_feeds();
+
         // Check for arbitrage opportunities and generate actions
         self.check_and_generate_actions()
     }
 }
 
+/// Convert a native token amount (18 decimals) to wei
+fn native_to_wei(amount: f64) -> U256 {
+    common::to_raw(amount, 18)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collectors::hyperliquid::BookLevel;
+
+    /// HYPE/USDC pool state at `block`, priced near $40 (token0 = USDC, 6 decimals)
+    fn pool_state(block_number: u64) -> UniV3PoolState {
+        UniV3PoolState {
+            address: Address::repeat_byte(0xaa),
+            block_number,
+            token0: Address::repeat_byte(0x11),
+            token1: Address::repeat_byte(0x55),
+            sqrt_price: U256::from(12_527_072_418_752_395_559_269_591_125_327_872u128),
+            liquidity: 1_000_000_000_000_000_000,
+            tick: 0,
+            fee: 3000,
+            token_a_decimals: 6,
+            token_b_decimals: 18,
+            pool: None,
+            received_at: None,
+        }
+    }
+
+    /// $1000 order, $1 gas, sized between $100 and $2000 over 180 bps of excess edge
+    fn sized_strategy() -> HypeUsdcCrossArbitrage {
+        HypeUsdcCrossArbitrage::new(1000.0, 0.0, 1.0, 10.0, Address::repeat_byte(0x11), Address::repeat_byte(0x55), 3000)
+            .with_dynamic_sizing(100.0, 2000.0, 180.0)
+    }
+
+    /// HL bid `gross_bps` above the $40 pool's ask
+    fn hl_bid_above_dex_ask(gross_bps: f64) -> CexBbo {
+        let (_, dex_ask) = sized_strategy().calculate_dex_bid_ask(&pool_state(10), 1000.0).unwrap();
+        let bid = dex_ask * (1.0 + gross_bps / 10000.0);
+        CexBbo { venue: CexVenue::Hyperliquid, coin: "HYPE/USDC".to_string(), bid, ask: bid * 1.001, time: 0, received_at: None }
+    }
+
+    fn traded(decision: Decision) -> (f64, f64) {
+        let Decision::Trade { net_profit_bps, action, .. } = decision else {
+            panic!("expected a trade");
+        };
+        let usdc_in = action.dex_swap.amount_in.to_string().parse::<f64>().unwrap() / 1e6;
+        (usdc_in, net_profit_bps)
+    }
+
+    #[test]
+    fn each_direction_routes_through_its_best_pool() {
+        let mut strategy = sized_strategy();
+        // Same price; the 0.05% pool quotes the tighter bid and ask
+        let wide = pool_state(10);
+        let tight = UniV3PoolState { address: Address::repeat_byte(0xbb), fee: 500, ..pool_state(10) };
+        strategy.pool_states.insert(wide.address, wide);
+        strategy.pool_states.insert(tight.address, tight.clone());
+        for direction in [Direction::BuyDexSellCex, Direction::BuyCexSellDex] {
+            assert_eq!(strategy.best_pool_for(direction).unwrap().address, tight.address);
+        }
+
+        // A richer pool is the better place to sell, not to buy
+        let rich = UniV3PoolState { address: Address::repeat_byte(0xcc), sqrt_price: tight.sqrt_price * U256::from(99) / U256::from(100), ..tight.clone() };
+        strategy.pool_states.insert(rich.address, rich.clone());
+        assert_eq!(strategy.best_pool_for(Direction::BuyDexSellCex).unwrap().address, tight.address);
+        assert_eq!(strategy.best_pool_for(Direction::BuyCexSellDex).unwrap().address, rich.address);
+    }
+
+    #[test]
+    fn scaled_trade_is_priced_at_its_own_notional() {
+        // 100 bps net at $1000 is 90 over the threshold: halfway up the ramp, $1050
+        let (usdc_in, net_profit_bps) = traded(sized_strategy().evaluate(&pool_state(10), &hl_bid_above_dex_ask(110.0)));
+        assert!((usdc_in - 1050.0).abs() < 1e-3, "{}", usdc_in);
+        // Gas is spread over the $1050 actually traded
+        let expected = 110.0 - common::gas_cost_bps(1.0, 1050.0);
+        assert!((net_profit_bps - expected).abs() < 1e-3, "{}", net_profit_bps);
+    }
+
+    #[test]
+    fn keeps_quoted_size_when_edge_fails_at_scaled_notional() {
+        // 20 bps net at $1000 scales down to ~$205, where $1 of gas eats the edge
+        let (usdc_in, net_profit_bps) = traded(sized_strategy().evaluate(&pool_state(10), &hl_bid_above_dex_ask(30.0)));
+        assert_eq!(usdc_in, 1000.0);
+        assert!((net_profit_bps - 20.0).abs() < 1e-3, "{}", net_profit_bps);
+    }
+
+    #[tokio::test]
+    async fn out_of_order_pool_update_keeps_the_newer_state() {
+        let mut strategy = sized_strategy();
+        let newest = pool_state(12);
+        let late = UniV3PoolState { sqrt_price: newest.sqrt_price * U256::from(2), ..pool_state(11) };
+        for state in [pool_state(10), newest.clone(), late] {
+            strategy.process_event(Event::PoolUpdate(state)).await;
+        }
+        let kept = &strategy.pool_states[&newest.address];
+        assert_eq!(kept.block_number, 12);
+        assert_eq!(kept.sqrt_price, newest.sqrt_price);
+    }
+
+    /// A $1000 strategy with no extra gating
+    fn plain_strategy() -> HypeUsdcCrossArbitrage {
+        HypeUsdcCrossArbitrage::new(1000.0, 0.0, 1.0, 10.0, Address::repeat_byte(0x11), Address::repeat_byte(0x55), 3000)
+    }
+
+    /// Actions emitted for one pool update per block while the HL bid sits 110 bps up
+    async fn actions_over_blocks(strategy: &mut HypeUsdcCrossArbitrage, blocks: std::ops::Range<u64>) -> usize {
+        strategy.process_event(Event::CexBbo(hl_bid_above_dex_ask(110.0))).await;
+        let mut actions = 0;
+        for block in blocks {
+            actions += strategy.process_event(Event::PoolUpdate(pool_state(block))).await.len();
+        }
+        actions
+    }
+
+    #[tokio::test]
+    async fn dedup_window_drops_repeats_of_a_persisting_signal() {
+        assert_eq!(actions_over_blocks(&mut plain_strategy(), 10..13).await, 3);
+
+        let mut strategy = plain_strategy().with_dedup_window(Duration::from_millis(200));
+        assert_eq!(actions_over_blocks(&mut strategy, 10..13).await, 1);
+        tokio::time::sleep(Duration::from_millis(250)).await;
+        assert_eq!(actions_over_blocks(&mut strategy, 13..14).await, 1);
+    }
+
+    /// Actions emitted as the HL bid moves through `gross_bps` above the pool's ask ($1 gas
+    /// on $1000 takes 10 bps off each)
+    async fn actions_over_edges(strategy: &mut HypeUsdcCrossArbitrage, gross_bps: &[f64]) -> Vec<usize> {
+        strategy.process_event(Event::PoolUpdate(pool_state(10))).await;
+        let mut actions = Vec::new();
+        for &gross in gross_bps {
+            actions.push(strategy.process_event(Event::CexBbo(hl_bid_above_dex_ask(gross))).await.len());
+        }
+        actions
+    }
+
+    #[tokio::test]
+    async fn smoothing_ignores_spikes_but_follows_a_sustained_edge() {
+        let noisy = [5.0, 5.0, 40.0, 5.0, 5.0, 40.0, 5.0];
+        let raw = actions_over_edges(&mut plain_strategy(), &noisy).await;
+        assert_eq!(raw.iter().sum::<usize>(), 2);
+        let smoothed = actions_over_edges(&mut plain_strategy().with_smoothing(9), &noisy).await;
+        assert_eq!(smoothed.iter().sum::<usize>(), 0);
+
+        // From -5 bps net the EMA reaches 2, 7.6, then 12.08 against the 10 bps threshold
+        let sustained = actions_over_edges(&mut plain_strategy().with_smoothing(9), &[5.0, 40.0, 40.0, 40.0]).await;
+        assert_eq!(sustained, vec![0, 0, 0, 1]);
+    }
+
+    #[test]
+    fn events_round_trip_through_json() {
+        let events = [
+            Event::PoolUpdate(pool_state(10)),
+            Event::HyperliquidBbo(HyperliquidBbo {
+                coin: "HYPE/USDC".to_string(),
+                levels: vec![
+                    Some(BookLevel { px: "39.99".to_string(), sz: "12.5".to_string(), n: 3 }),
+                    None,
+                ],
+                time: 1_700_000_000_000,
+                received_at: Some(Instant::now()),
+            }),
+        ];
+        for event in events {
+            let json = serde_json::to_string(&event).unwrap();
+            let back: Event = serde_json::from_str(&json).unwrap();
+            assert_eq!(serde_json::to_string(&back).unwrap(), json);
+            assert!(back.received_at().is_none());
+        }
+    }
+
+    #[test]
+    fn order_below_the_venue_minimum_is_not_traded() {
+        // $5 at a 110 bps edge clears the threshold but not HL's $10 minimum
+        let strategy = HypeUsdcCrossArbitrage::new(5.0, 0.0, 0.0, 10.0, Address::repeat_byte(0x11), Address::repeat_byte(0x55), 3000);
+        match strategy.evaluate(&pool_state(10), &hl_bid_above_dex_ask(110.0)) {
+            Decision::NoTrade { reason, .. } => assert!(reason.contains("below $10 venue minimum"), "{}", reason),
+            Decision::Trade { .. } => panic!("a $5 hedge would be rejected after the swap"),
+        }
+    }
+
+    #[test]
+    fn cex_quote_outside_the_sanity_band_is_rejected() {
+        let strategy = plain_strategy().with_price_band(Some(30.0), Some(50.0));
+        let in_band = hl_bid_above_dex_ask(110.0);
+        assert!(strategy.get_cex_prices(&in_band).is_some());
+        assert!(matches!(strategy.evaluate(&pool_state(10), &in_band), Decision::Trade { .. }));
+
+        // A Binance quote far off the band, e.g. a bad tick
+        let bad_tick = CexBbo { venue: CexVenue::Binance, bid: 400.0, ask: 400.4, ..in_band };
+        assert!(strategy.get_cex_prices(&bad_tick).is_none());
+        match strategy.evaluate(&pool_state(10), &bad_tick) {
+            Decision::NoTrade { reason, .. } => assert_eq!(reason, "no usable Binance quote"),
+            Decision::Trade { .. } => panic!("out-of-band quote was traded"),
+        }
+    }
+
+    /// HL book crossed around the $40 pool: bid above the DEX ask, ask below the DEX bid
+    fn crossed_hl() -> CexBbo {
+        CexBbo { bid: 41.0, ask: 39.4, ..hl_bid_above_dex_ask(0.0) }
+    }
+
+    #[test]
+    fn crossed_quotes_are_skipped_by_default() {
+        match plain_strategy().evaluate(&pool_state(10), &crossed_hl()) {
+            Decision::NoTrade { reason, quotes } => {
+                assert_eq!(reason, "both directions profitable (crossed quotes)");
+                let quotes = quotes.unwrap();
+                assert!(quotes.net_profit_1_bps > 10.0 && quotes.net_profit_2_bps > 10.0);
+            }
+            Decision::Trade { .. } => panic!("crossed quotes were traded"),
+        }
+    }
+
+    #[test]
+    fn take_best_trades_the_wider_side_of_crossed_quotes() {
+        let strategy = plain_strategy().with_both_profitable(BothProfitable::TakeBest);
+        // Selling at 41 clears the DEX ask by more than buying at 39.4 clears its bid
+        match strategy.evaluate(&pool_state(10), &crossed_hl()) {
+            Decision::Trade { direction, quotes, .. } => {
+                assert_eq!(direction, Direction::BuyDexSellCex);
+                assert!(quotes.net_profit_1_bps > quotes.net_profit_2_bps);
+            }
+            Decision::NoTrade { reason, .. } => panic!("expected a trade: {}", reason),
+        }
+    }
+
+    #[tokio::test]
+    async fn hysteresis_only_fires_on_distinct_opportunities() {
+        // Net edge is gross less 10 bps of gas: enter above 15, re-arm below 5
+        let series = [30.0, 28.0, 30.0, 18.0, 12.0, 30.0, 22.0];
+        let raw = actions_over_edges(&mut plain_strategy(), &series).await;
+        assert_eq!(raw, vec![1, 1, 1, 0, 0, 1, 1]);
+
+        let hysteresis = Hysteresis { enter_buffer_bps: 5.0, exit_buffer_bps: 5.0, cooldown: None };
+        let banded = actions_over_edges(&mut plain_strategy().with_hysteresis(hysteresis), &series).await;
+        // The first crossing trades; staying above the exit band doesn't, dropping
+        // through it re-arms, and 12 bps net is below the entry buffer
+        assert_eq!(banded, vec![1, 0, 0, 0, 0, 1, 0]);
+    }
+
+    #[tokio::test]
+    async fn hysteresis_cooldown_rearms_without_the_spread_resetting() {
+        let hysteresis = Hysteresis { enter_buffer_bps: 0.0, exit_buffer_bps: 100.0, cooldown: Some(Duration::from_millis(50)) };
+        let mut strategy = plain_strategy().with_hysteresis(hysteresis);
+        assert_eq!(actions_over_edges(&mut strategy, &[30.0, 30.0]).await, vec![1, 0]);
+        tokio::time::sleep(Duration::from_millis(60)).await;
+        assert_eq!(actions_over_edges(&mut strategy, &[30.0]).await, vec![1]);
+    }
+
+    #[tokio::test]
+    async fn tenfold_price_discrepancy_emits_no_action() {
+        // A bad tick quoting HYPE at $400 against the $40 pool
+        let glitch = CexBbo { bid: 400.0, ask: 400.4, ..hl_bid_above_dex_ask(0.0) };
+        assert!(matches!(plain_strategy().evaluate(&pool_state(10), &glitch), Decision::Trade { .. }));
+
+        let mut strategy = plain_strategy().with_max_divergence(500.0);
+        strategy.process_event(Event::PoolUpdate(pool_state(10))).await;
+        assert!(strategy.process_event(Event::CexBbo(glitch.clone())).await.is_empty());
+        match strategy.evaluate(&pool_state(10), &glitch) {
+            Decision::NoTrade { reason, .. } => assert!(reason.starts_with("price divergence"), "{}", reason),
+            Decision::Trade { .. } => panic!("divergent quotes were traded"),
+        }
+    }
+
+    fn funding(rate: f64) -> FundingRate {
+        FundingRate { coin: "HYPE/USDC".to_string(), rate, next_time: 0 }
+    }
+
+    #[tokio::test]
+    async fn funding_shifts_the_profitability_threshold() {
+        // Over 8 hours, 0.01%/h of funding is worth 8 bps to the HL short
+        let mut strategy = plain_strategy().with_funding(8.0);
+        assert_eq!(actions_over_edges(&mut strategy, &[15.0]).await, vec![0]);
+        strategy.set_funding_rate(funding(0.0001));
+        assert_eq!(actions_over_edges(&mut strategy, &[15.0]).await, vec![1]);
+
+        // Paying funding on the short costs the same 8 bps
+        strategy.set_funding_rate(funding(-0.0001));
+        assert_eq!(actions_over_edges(&mut strategy, &[25.0]).await, vec![0]);
+
+        // Without funding-aware mode the rate is ignored
+        let mut unaware = plain_strategy();
+        unaware.set_funding_rate(funding(-0.0001));
+        assert_eq!(actions_over_edges(&mut unaware, &[25.0]).await, vec![1]);
+    }
+
+    #[tokio::test]
+    async fn reloaded_threshold_applies_to_the_next_event() {
+        let mut strategy = plain_strategy();
+        assert_eq!(actions_over_edges(&mut strategy, &[40.0]).await, vec![1]);
+
+        let params = StrategyParams {
+            name: "default".to_string(),
+            min_profit_bps: 50.0,
+            slippage_bps: 0.0,
+            order_size_usd: 1000.0,
+            hedge_fee_bps: 0.0,
+            dex_gas_fee_usd: 1.0,
+            max_divergence_bps: None,
+        };
+        let reload = ConfigReload { strategies: vec![params.clone()] };
+        assert!(strategy.process_event(Event::ConfigReload(reload)).await.is_empty());
+        assert_eq!(strategy.min_profit_bps, 50.0);
+        // 30 bps net no longer clears the raised threshold
+        assert_eq!(actions_over_edges(&mut strategy, &[40.0]).await, vec![0]);
+
+        // Parameters for another strategy are left alone
+        let mut other = plain_strategy();
+        let reload = ConfigReload { strategies: vec![StrategyParams { name: "other".to_string(), ..params }] };
+        other.process_event(Event::ConfigReload(reload)).await;
+        assert_eq!(actions_over_edges(&mut other, &[40.0]).await, vec![1]);
+    }
+
+    #[tokio::test]
+    async fn notional_cap_pauses_until_the_window_rolls_off() {
+        let tracker = Arc::new(PositionTracker::new(Duration::from_millis(100)));
+        let mut strategy = plain_strategy().with_notional_cap(1500.0, tracker.clone());
+        tracker.record_fill(1000.0);
+        assert_eq!(actions_over_edges(&mut strategy, &[40.0]).await, vec![1]);
+
+        // Fed back from the executor: $1600 this window is over the $1500 cap
+        tracker.record_fill(600.0);
+        assert_eq!(actions_over_edges(&mut strategy, &[40.0, 40.0]).await, vec![0, 0]);
+        assert!(strategy.notional_paused);
+
+        tokio::time::sleep(Duration::from_millis(120)).await;
+        assert_eq!(actions_over_edges(&mut strategy, &[40.0]).await, vec![1]);
+        assert!(!strategy.notional_paused);
+    }
+
+    #[tokio::test]
+    async fn missing_feed_is_warned_about_once_the_grace_period_ends() {
+        let mut strategy = plain_strategy().with_startup_grace(Duration::from_millis(50));
+        strategy.sync_state().await.unwrap();
+        // Only the DEX side ever reports
+        strategy.process_event(Event::PoolUpdate(pool_state(10))).await;
+        assert_eq!(strategy.missing_feeds(), vec!["HL BBO"]);
+        strategy.on_tick().await;
+        assert!(!strategy.missing_warned);
+
+        tokio::time::sleep(Duration::from_millis(60)).await;
+        strategy.on_tick().await;
+        assert!(strategy.missing_warned);
+        assert!(!strategy.feeds_ready);
+
+        // The late feed clears the wait
+        strategy.process_event(Event::CexBbo(hl_bid_above_dex_ask(0.0))).await;
+        assert!(strategy.feeds_ready);
+    }
+
+    #[test]
+    fn thin_pool_is_blocked_by_the_price_impact_limit() {
+        let strategy = plain_strategy().with_max_price_impact(50.0);
+        let hl = hl_bid_above_dex_ask(110.0);
+
+        // $1000 of USDC moves a pool this thin by ~320 bps
+        let thin = UniV3PoolState { liquidity: 10_000_000_000_000_000, ..pool_state(10) };
+        match strategy.evaluate(&thin, &hl) {
+            Decision::NoTrade { reason, .. } => assert!(reason.starts_with("price impact"), "{}", reason),
+            Decision::Trade { .. } => panic!("trade into a thin pool went ahead"),
+        }
+
+        // ...and a deep one by a fraction of a bp
+        let deep = UniV3PoolState { liquidity: 100_000_000_000_000_000_000, ..pool_state(10) };
+        assert!(matches!(strategy.evaluate(&deep, &hl), Decision::Trade { .. }));
+        // Unknown liquidity isn't treated as thin
+        let unknown = UniV3PoolState { liquidity: 0, ..pool_state(10) };
+        assert!(matches!(strategy.evaluate(&unknown, &hl), Decision::Trade { .. }));
+    }
+
+    #[test]
+    fn either_token_ordering_quotes_the_same_pool() {
+        let strategy = plain_strategy();
+        // The $40 pool with HYPE (0x55…, 18 decimals) as token0
+        let hype_first = UniV3PoolState {
+            token0: Address::repeat_byte(0x55),
+            token1: Address::repeat_byte(0x11),
+            sqrt_price: U256::from(501_082_896_750_095_862_372_827u128),
+            token_a_decimals: 18,
+            token_b_decimals: 6,
+            ..pool_state(10)
+        };
+        let (usdc_bid, usdc_ask) = strategy.calculate_dex_bid_ask(&pool_state(10), 1000.0).unwrap();
+        let (hype_bid, hype_ask) = strategy.calculate_dex_bid_ask(&hype_first, 1000.0).unwrap();
+        assert!((usdc_ask - hype_ask).abs() / usdc_ask < 1e-9, "{} vs {}", usdc_ask, hype_ask);
+        assert!((usdc_bid - hype_bid).abs() / usdc_bid < 1e-9, "{} vs {}", usdc_bid, hype_bid);
+        assert!((usdc_ask - 40.06).abs() < 0.01, "{}", usdc_ask);
+
+        // Either way, buying on the DEX pays 1000 USDC in for HYPE out
+        let hl = hl_bid_above_dex_ask(110.0);
+        for state in [pool_state(10), hype_first] {
+            let Decision::Trade { action, .. } = strategy.evaluate(&state, &hl) else {
+                panic!("expected a trade");
+            };
+            assert_eq!(action.dex_swap.token_in, Address::repeat_byte(0x11));
+            assert_eq!(action.dex_swap.token_out, Address::repeat_byte(0x55));
+            assert_eq!(action.dex_swap.amount_in, U256::from(1_000_000_000u64));
+            assert_eq!(strategy.token_decimals(&state), (18, 6));
+        }
+    }
+
+    /// Log sink shared between the subscriber and the test
+    #[derive(Clone, Default)]
+    struct CapturedLogs(Arc<std::sync::Mutex<Vec<u8>>>);
+
+    impl std::io::Write for CapturedLogs {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[tokio::test]
+    async fn json_logs_carry_the_trade_fields() {
+        use tracing_subscriber::prelude::*;
+        use crate::utilities::logging::{fmt_layer, LogFormat};
+
+        let logs = CapturedLogs::default();
+        let sink = logs.clone();
+        let subscriber = tracing_subscriber::registry()
+            .with(fmt_layer(LogFormat::Json, move || sink.clone()));
+        // The current-thread test runtime keeps every log on this thread
+        let _guard = tracing::subscriber::set_default(subscriber);
+        assert_eq!(actions_over_edges(&mut plain_strategy(), &[40.0]).await, vec![1]);
+
+        let logs = String::from_utf8(logs.0.lock().unwrap().clone()).unwrap();
+        let exec: serde_json::Value = logs.lines()
+            .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap())
+            .find(|line| line["fields"]["message"].as_str().is_some_and(|m| m.contains("EXEC")))
+            .expect("no EXEC log line");
+        let fields = &exec["fields"];
+        assert_eq!(fields["direction"], Direction::BuyDexSellCex.to_string());
+        assert!(fields["net_profit_bps"].as_f64().unwrap() > 10.0, "{}", fields);
+        assert!(fields["smoothed_bps"].is_f64(), "{}", fields);
+        assert!(fields["size"].as_f64().unwrap() > 0.0, "{}", fields);
+    }
+
+    /// One poll of a BalanceCollector whose node reports `native` wei and `usdc` raw USDC
+    async fn polled_balances(native: u128, usdc: u128) -> BalanceUpdate {
+        use alloy::{primitives::Bytes, providers::ProviderBuilder, sol_types::SolCall, transports::mock::Asserter};
+        use tokio_stream::StreamExt;
+        use crate::collectors::balance::{BalanceCollector, IERC20Balance::balanceOfCall};
+        use crate::types::Collector;
+
+        let asserter = Asserter::new();
+        asserter.push_success(&U256::from(native));
+        asserter.push_success(&Bytes::from(balanceOfCall::abi_encode_returns(&U256::from(usdc))));
+        let provider = Arc::new(ProviderBuilder::new().connect_mocked_client(asserter));
+        let collector = BalanceCollector::new(provider, Address::repeat_byte(0x99), vec![Address::repeat_byte(0x11)]);
+        collector.get_event_stream().await.unwrap().next().await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn low_polled_balance_pauses_trading() {
+        // 0.1 native minimum; a $1000 trade spends 1e9 raw USDC
+        let mut strategy = plain_strategy().with_min_gas_balance(U256::from(100_000_000_000_000_000u128));
+        assert_eq!(actions_over_edges(&mut strategy, &[40.0]).await, vec![1]);
+
+        strategy.process_event(Event::BalanceUpdate(polled_balances(1_000_000_000_000_000, 10_000_000_000).await)).await;
+        assert_eq!(actions_over_edges(&mut strategy, &[40.0]).await, vec![0]);
+
+        // Gas topped up, but not enough USDC for the trade
+        strategy.process_event(Event::BalanceUpdate(polled_balances(1_000_000_000_000_000_000, 10).await)).await;
+        assert_eq!(actions_over_edges(&mut strategy, &[40.0]).await, vec![0]);
+
+        strategy.process_event(Event::BalanceUpdate(polled_balances(1_000_000_000_000_000_000, 10_000_000_000).await)).await;
+        assert_eq!(actions_over_edges(&mut strategy, &[40.0]).await, vec![1]);
+    }
+
+    /// `pool_state` backed by live tick state: one liquidity range spanning every tick,
+    /// so the amms simulation never crosses into a different liquidity
+    fn synthetic_pool_state(liquidity: u128) -> UniV3PoolState {
+        use amms::amms::uniswap_v3::UniswapV3Pool;
+
+        let state = pool_state(10);
+        // floor(log1.0001 of the raw price) for the $40 sqrt price
+        let tick = 239_433;
+        let mut pool = UniswapV3Pool::new(state.address);
+        pool.token_a.address = state.token0;
+        pool.token_a.decimals = state.token_a_decimals;
+        pool.token_b.address = state.token1;
+        pool.token_b.decimals = state.token_b_decimals;
+        pool.sqrt_price = state.sqrt_price;
+        pool.liquidity = liquidity;
+        pool.tick = tick;
+        pool.tick_spacing = 60;
+        pool.fee = state.fee;
+        UniV3PoolState { liquidity, tick, pool: Some(Arc::new(pool)), ..state }
+    }
+
+    #[test]
+    fn simulated_quote_prices_in_the_swaps_impact() {
+        // $1000 into 1e17 liquidity moves the price ~32 bps: ~16 bps worse on average
+        let state = synthetic_pool_state(100_000_000_000_000_000);
+        let hl = hl_bid_above_dex_ask(60.0);
+        let (_, mid_bps) = traded(plain_strategy().evaluate(&state, &hl));
+        let (_, simulated_bps) = traded(plain_strategy().with_price_impact().evaluate(&state, &hl));
+        let impact_bps = mid_bps - simulated_bps;
+        assert!((14.0..18.0).contains(&impact_bps), "mid {} vs simulated {}", mid_bps, simulated_bps);
+    }
+
+    #[test]
+    fn single_range_estimate_matches_the_simulated_swap() {
+        let strategy = plain_strategy().with_price_impact();
+        let live = synthetic_pool_state(100_000_000_000_000_000);
+        let replayed = UniV3PoolState { pool: None, ..live.clone() };
+        let mid = 1.0 / common::pool_price(live.sqrt_price, live.token_a_decimals, live.token_b_decimals).unwrap();
+
+        let (sim_bid, sim_ask) = strategy.simulate_dex_bid_ask(&live, mid, 1000.0).unwrap();
+        let (est_bid, est_ask) = strategy.estimate_dex_bid_ask(&replayed, mid, 1000.0).unwrap();
+        assert!(((est_bid / sim_bid - 1.0) * 10_000.0).abs() < 0.1, "bid {} vs {}", est_bid, sim_bid);
+        assert!(((est_ask / sim_ask - 1.0) * 10_000.0).abs() < 0.1, "ask {} vs {}", est_ask, sim_ask);
+
+        // Replayed states quote through the estimate, which is wider than mid ± fee
+        let (bid, ask) = strategy.calculate_dex_bid_ask(&replayed, 1000.0).unwrap();
+        assert_eq!((bid, ask), (est_bid, est_ask));
+        let (flat_bid, flat_ask) = plain_strategy().calculate_dex_bid_ask(&replayed, 1000.0).unwrap();
+        assert!(bid < flat_bid && ask > flat_ask);
+    }
+}
@@ -1 +1,3 @@
+pub mod common;
 pub mod hype_usdc_cross_arbitrage;
+pub mod triangular;
\ No newline at end of file
@@ -0,0 +1,262 @@
+use std::collections::HashMap;
+
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use tracing::info;
+use alloy::primitives::{Address, U256};
+
+use crate::collectors::uniswapv3::UniV3PoolState;
+use crate::executors::univ3::UniV3SwapAction;
+use crate::strategies::common;
+use crate::types::Strategy;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum Event {
+    PoolUpdate(UniV3PoolState),
+}
+
+/// Action for a full triangular cycle (three swaps submitted together via router multicall).
+/// Execute with `ExecutorMap::new(Box::new(univ3_executor), |a: TriangularAction| Some(a.swaps))`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TriangularAction {
+    pub swaps: Vec<UniV3SwapAction>,
+    pub net_profit_bps: f64,
+}
+
+// Re-export for convenience
+pub use TriangularAction as Action;
+
+/// One leg of the triangle: swap `token_in` for `token_out` on `pool_address`
+#[derive(Debug, Clone)]
+pub struct TriangleLeg {
+    pub pool_address: Address,
+    pub token_in: Address,
+    pub token_out: Address,
+    pub fee: u32,
+}
+
+#[derive(Debug, Clone)]
+pub struct TriangularArbitrage {
+    legs: Vec<TriangleLeg>,
+    pool_states: HashMap<Address, UniV3PoolState>,
+    // Notional is denominated in the start token (legs[0].token_in), assumed USD-pegged
+    notional_usd: f64,
+    dex_gas_fee_usd: f64,
+    min_profit_bps: f64,
+    slippage_bps: f64,
+}
+
+impl TriangularArbitrage {
+    /// Create strategy from three legs forming a closed loop (e.g. USDC→HYPE→WETH→USDC)
+    pub fn new(
+        legs: Vec<TriangleLeg>,
+        notional_usd: f64,
+        dex_gas_fee_usd: f64,
+        min_profit_bps: f64,
+        slippage_bps: f64,
+    ) -> Result<Self> {
+        if legs.len() != 3 {
+            anyhow::bail!("Triangular arbitrage needs exactly 3 legs, got {}", legs.len());
+        }
+        for i in 0..3 {
+            let next = &legs[(i + 1) % 3];
+            if legs[i].token_out != next.token_in {
+                anyhow::bail!("Leg {} output does not feed leg {} input", i, (i + 1) % 3);
+            }
+        }
+        if notional_usd <= 0.0 {
+            anyhow::bail!("notional_usd must be > 0");
+        }
+
+        Ok(Self {
+            legs,
+            pool_states: HashMap::new(),
+            notional_usd,
+            dex_gas_fee_usd,
+            min_profit_bps,
+            slippage_bps,
+        })
+    }
+
+    /// Pool price as token1 per token0 in human units
+    fn pool_price(state: &UniV3PoolState) -> Option<f64> {
+        common::pool_price(state.sqrt_price, state.token_a_decimals, state.token_b_decimals)
+    }
+
+    /// Output per unit input for a leg, after the pool fee. Uniswap orders tokens by
+    /// address, so token_in < token_out means we are selling token0.
+    fn leg_rate(leg: &TriangleLeg, state: &UniV3PoolState) -> Option<f64> {
+        let price = Self::pool_price(state)?;
+        let rate = if leg.token_in < leg.token_out { price } else { 1.0 / price };
+        Some(rate * (1.0 - common::pool_fee_fraction(state.fee)))
+    }
+
+    fn token_decimals(leg: &TriangleLeg, state: &UniV3PoolState, token: Address) -> u8 {
+        let other = if token == leg.token_in { leg.token_out } else { leg.token_in };
+        if token < other { state.token_a_decimals } else { state.token_b_decimals }
+    }
+
+    fn to_raw(amount: f64, decimals: u8) -> U256 {
+        common::to_raw(amount, decimals)
+    }
+
+    /// Calculate net profit in basis points of cycling the notional through all legs
+    fn calculate_net_profit_bps(&self, rates: &[f64]) -> f64 {
+        let gross_multiplier: f64 = rates.iter().product();
+        (gross_multiplier - 1.0) * 10000.0 - common::gas_cost_bps(self.dex_gas_fee_usd, self.notional_usd)
+    }
+
+    fn generate_action(&self, states: &[&UniV3PoolState], rates: &[f64], net_profit_bps: f64) -> Action {
+        let slippage = self.slippage_bps / 10000.0;
+        let mut amount_in = self.notional_usd;
+        let mut swaps = Vec::with_capacity(3);
+
+        // Each leg spends the previous leg's minimum output so the balance is always there
+        for ((leg, state), rate) in self.legs.iter().zip(states).zip(rates) {
+            let amount_out_min = amount_in * rate * (1.0 - slippage);
+            let decimals_in = Self::token_decimals(leg, state, leg.token_in);
+            let decimals_out = Self::token_decimals(leg, state, leg.token_out);
+
+            swaps.push(UniV3SwapAction {
+                token_in: leg.token_in,
+                token_out: leg.token_out,
+                fee: leg.fee,
+                amount_in: Self::to_raw(amount_in, decimals_in),
+                amount_out_min: Self::to_raw(amount_out_min, decimals_out),
+            });
+
+            amount_in = amount_out_min;
+        }
+
+        Action { swaps, net_profit_bps }
+    }
+
+    fn check_and_generate_actions(&self) -> Vec<Action> {
+        let states: Option<Vec<&UniV3PoolState>> = self.legs.iter()
+            .map(|leg| self.pool_states.get(&leg.pool_address))
+            .collect();
+        let states = match states {
+            Some(s) => s,
+            None => return vec![],
+        };
+
+        let rates: Option<Vec<f64>> = self.legs.iter()
+            .zip(&states)
+            .map(|(leg, state)| Self::leg_rate(leg, state))
+            .collect();
+        let rates = match rates {
+            Some(r) => r,
+            None => return vec![],
+        };
+
+        let net_profit_bps = self.calculate_net_profit_bps(&rates);
+
+        info!("TRI {:.6}/{:.6}/{:.6} | Net: {:+.2}%",
+            rates[0], rates[1], rates[2], net_profit_bps / 100.0);
+
+        if net_profit_bps > self.min_profit_bps {
+            info!("🎯 EXEC: Triangular cycle ({:.2} bps > {} bps threshold)",
+                net_profit_bps, self.min_profit_bps);
+            return vec![self.generate_action(&states, &rates, net_profit_bps)];
+        }
+
+        vec![]
+    }
+}
+
+#[async_trait]
+impl Strategy<Event, Action> for TriangularArbitrage {
+    async fn sync_state(&mut self) -> Result<()> {
+        Ok(())
+    }
+
+    fn interested_in(&self, event: &Event) -> bool {
+        match event {
+            Event::PoolUpdate(state) => self.legs.iter().any(|leg| leg.pool_address == state.address),
+        }
+    }
+
+    async fn process_event(&mut self, event: Event) -> Vec<Action> {
+        match event {
+            Event::PoolUpdate(state) => {
+                self.pool_states.insert(state.address, state);
+            }
+        }
+
+        self.check_and_generate_actions()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const A: Address = Address::repeat_byte(0x11);
+    const B: Address = Address::repeat_byte(0x22);
+    const C: Address = Address::repeat_byte(0x33);
+
+    /// 18/18-decimal pool at `price` token1 per token0
+    fn pool(address: u8, price: f64) -> UniV3PoolState {
+        UniV3PoolState {
+            address: Address::repeat_byte(address),
+            block_number: 1,
+            token0: Address::ZERO,
+            token1: Address::ZERO,
+            sqrt_price: U256::from((price.sqrt() * 2_f64.powi(96)) as u128),
+            liquidity: 0,
+            tick: 0,
+            fee: 500,
+            token_a_decimals: 18,
+            token_b_decimals: 18,
+            pool: None,
+            received_at: None,
+        }
+    }
+
+    fn leg(pool: u8, token_in: Address, token_out: Address) -> TriangleLeg {
+        TriangleLeg { pool_address: Address::repeat_byte(pool), token_in, token_out, fee: 500 }
+    }
+
+    /// A→B→C→A; $1000 notional, $1 gas (10 bps), 20 bps threshold
+    fn strategy() -> TriangularArbitrage {
+        TriangularArbitrage::new(vec![leg(0xa1, A, B), leg(0xa2, B, C), leg(0xa3, C, A)], 1000.0, 1.0, 20.0, 50.0).unwrap()
+    }
+
+    /// Feed all three pools; the C/A pool prices C per A at `ca_price` (6.0 is fair)
+    async fn run(strategy: &mut TriangularArbitrage, ca_price: f64) -> Vec<Action> {
+        assert!(strategy.process_event(Event::PoolUpdate(pool(0xa1, 2.0))).await.is_empty());
+        assert!(strategy.process_event(Event::PoolUpdate(pool(0xa2, 3.0))).await.is_empty());
+        strategy.process_event(Event::PoolUpdate(pool(0xa3, ca_price))).await
+    }
+
+    #[tokio::test]
+    async fn mispriced_loop_is_traded_once_all_three_pools_are_known() {
+        let mut strategy = strategy();
+        // 1% cheap A in the closing pool: ~101 bps gross less 3 x 5 bps fees and 10 bps gas
+        let actions = run(&mut strategy, 5.94).await;
+        assert_eq!(actions.len(), 1);
+        let action = &actions[0];
+        assert!((70.0..80.0).contains(&action.net_profit_bps), "{}", action.net_profit_bps);
+
+        let path: Vec<(Address, Address)> = action.swaps.iter().map(|s| (s.token_in, s.token_out)).collect();
+        assert_eq!(path, vec![(A, B), (B, C), (C, A)]);
+        assert_eq!(action.swaps[0].amount_in, common::to_raw(1000.0, 18));
+        // Each leg spends exactly what the previous one is guaranteed to return
+        for pair in action.swaps.windows(2) {
+            assert_eq!(pair[1].amount_in, pair[0].amount_out_min);
+        }
+    }
+
+    #[tokio::test]
+    async fn fairly_priced_loop_is_not_traded() {
+        let mut strategy = strategy();
+        assert!(run(&mut strategy, 6.0).await.is_empty());
+    }
+
+    #[test]
+    fn legs_must_close_the_loop() {
+        let legs = vec![leg(0xa1, A, B), leg(0xa2, B, C), leg(0xa3, B, A)];
+        assert!(TriangularArbitrage::new(legs, 1000.0, 1.0, 20.0, 50.0).is_err());
+    }
+}
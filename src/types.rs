@@ -1,9 +1,37 @@
 use anyhow::Result;
 use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::fmt;
 use std::pin::Pin;
+use std::time::Duration;
 use tokio_stream::Stream;
 use tokio_stream::StreamExt;
 
+/// Which side of a cross-venue round trip buys
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Direction {
+    /// Buy on the DEX, sell the hedge on the CEX
+    BuyDexSellCex,
+    /// Buy the hedge on the CEX, sell on the DEX
+    BuyCexSellDex,
+}
+
+impl Direction {
+    pub fn buys_dex(self) -> bool {
+        self == Direction::BuyDexSellCex
+    }
+}
+
+impl fmt::Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Direction::BuyDexSellCex => write!(f, "Buy DEX → Sell HL"),
+            Direction::BuyCexSellDex => write!(f, "Buy HL → Sell DEX"),
+        }
+    }
+}
+
 /// A stream of events emitted by a [Collector](Collector).
 pub type CollectorStream<'a, E> = Pin<Box<dyn Stream<Item = E> + Send + 'a>>;
 
@@ -12,8 +40,18 @@ pub type CollectorStream<'a, E> = Pin<Box<dyn Stream<Item = E> + Send + 'a>>;
 pub trait Collector<E>: Send + Sync {
     /// Returns the core event stream for the collector.
     async fn get_event_stream(&self) -> Result<CollectorStream<'_, E>>;
-}
 
+    /// Short name for logs and health reports.
+    fn name(&self) -> &str {
+        "collector"
+    }
+
+    /// Whether silence means the source is down. Collectors that only emit on an
+    /// external trigger (e.g. a signal) return false and are not held to freshness.
+    fn expects_steady_events(&self) -> bool {
+        true
+    }
+}
 
 /// Strategy trait, which defines the core logic for each opportunity.
 #[async_trait]
@@ -24,6 +62,22 @@ pub trait Strategy<E, A>: Send + Sync {
 
     /// Process an event, and return an action if needed.
     async fn process_event(&mut self, event: E) -> Vec<A>;
+
+    /// Whether the engine should deliver `event` to [process_event](Strategy::process_event);
+    /// strategies sharing the event channel filter out other pools and coins here.
+    fn interested_in(&self, _event: &E) -> bool {
+        true
+    }
+
+    /// How often the engine calls [on_tick](Strategy::on_tick); None never ticks.
+    fn tick_interval(&self) -> Option<Duration> {
+        None
+    }
+
+    /// Periodic housekeeping independent of incoming events.
+    async fn on_tick(&mut self) -> Vec<A> {
+        vec![]
+    }
 }
 
 /// Executor trait, responsible for executing actions returned by strategies.
@@ -58,6 +112,87 @@ where
         let stream = stream.map(f);
         Ok(Box::pin(stream))
     }
+
+    fn name(&self) -> &str {
+        self.collector.name()
+    }
+
+    fn expects_steady_events(&self) -> bool {
+        self.collector.expects_steady_events()
+    }
+}
+
+/// CollectorFilterMap is a wrapper around a [Collector](Collector) that maps outgoing
+/// events to a different type and drops those for which the closure returns `None`.
+pub struct CollectorFilterMap<E, F> {
+    collector: Box<dyn Collector<E>>,
+    f: F,
+}
+
+impl<E, F> CollectorFilterMap<E, F> {
+    pub fn new(collector: Box<dyn Collector<E>>, f: F) -> Self {
+        Self { collector, f }
+    }
+}
+
+#[async_trait]
+impl<E1, E2, F> Collector<E2> for CollectorFilterMap<E1, F>
+where
+    E1: Send + Sync + 'static,
+    E2: Send + Sync + 'static,
+    F: Fn(E1) -> Option<E2> + Send + Sync + Clone + 'static,
+{
+    async fn get_event_stream(&self) -> Result<CollectorStream<'_, E2>> {
+        let stream = self.collector.get_event_stream().await?;
+        let f = self.f.clone();
+        let stream = stream.filter_map(f);
+        Ok(Box::pin(stream))
+    }
+
+    fn name(&self) -> &str {
+        self.collector.name()
+    }
+
+    fn expects_steady_events(&self) -> bool {
+        self.collector.expects_steady_events()
+    }
+}
+
+/// CollectorMerge interleaves the events of two [Collectors](Collector) of the same
+/// type into one stream, in arrival order. It ends once both inner streams end.
+pub struct CollectorMerge<E> {
+    a: Box<dyn Collector<E>>,
+    b: Box<dyn Collector<E>>,
+    /// Both inner names, e.g. "binance+okx"
+    name: String,
+}
+
+impl<E> CollectorMerge<E> {
+    pub fn new(a: Box<dyn Collector<E>>, b: Box<dyn Collector<E>>) -> Self {
+        let name = format!("{}+{}", a.name(), b.name());
+        Self { a, b, name }
+    }
+}
+
+#[async_trait]
+impl<E> Collector<E> for CollectorMerge<E>
+where
+    E: Send + Sync + 'static,
+{
+    async fn get_event_stream(&self) -> Result<CollectorStream<'_, E>> {
+        let a = self.a.get_event_stream().await?;
+        let b = self.b.get_event_stream().await?;
+        Ok(Box::pin(a.merge(b)))
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Steady if either side is: one steady source is enough for silence to mean trouble
+    fn expects_steady_events(&self) -> bool {
+        self.a.expects_steady_events() || self.b.expects_steady_events()
+    }
 }
 
 /// ExecutorMap is a wrapper around an [Executor](Executor) that maps incoming
@@ -98,4 +233,70 @@ pub enum Events {
 /// Convenience enum containing all the actions that can be executed by executors.
 pub enum Actions {
     // FlashbotsBundle(FlashbotsBundle),
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Emits a fixed list of events, then ends
+    struct VecCollector {
+        name: &'static str,
+        events: Vec<u32>,
+        steady: bool,
+    }
+
+    #[async_trait]
+    impl Collector<u32> for VecCollector {
+        async fn get_event_stream(&self) -> Result<CollectorStream<'_, u32>> {
+            Ok(Box::pin(tokio_stream::iter(self.events.clone())))
+        }
+
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        fn expects_steady_events(&self) -> bool {
+            self.steady
+        }
+    }
+
+    fn collector(name: &'static str, events: Vec<u32>, steady: bool) -> Box<dyn Collector<u32>> {
+        Box::new(VecCollector { name, events, steady })
+    }
+
+    #[tokio::test]
+    async fn filter_map_drops_none_and_keeps_the_inner_identity() {
+        let evens = CollectorFilterMap::new(
+            collector("ticks", (1..=6).collect(), false),
+            |n: u32| (n % 2 == 0).then(|| n.to_string()),
+        );
+        let events: Vec<String> = evens.get_event_stream().await.unwrap().collect().await;
+        assert_eq!(events, ["2", "4", "6"]);
+        assert_eq!(evens.name(), "ticks");
+        assert!(!evens.expects_steady_events());
+    }
+
+    #[tokio::test]
+    async fn merge_interleaves_both_streams_until_both_end() {
+        let merged = CollectorMerge::new(collector("a", vec![1, 2, 3], false), collector("b", vec![10, 20], true));
+        let mut events: Vec<u32> = merged.get_event_stream().await.unwrap().collect().await;
+        events.sort();
+        assert_eq!(events, [1, 2, 3, 10, 20]);
+        assert_eq!(merged.name(), "a+b");
+        assert!(merged.expects_steady_events());
+
+        let quiet = CollectorMerge::new(collector("a", vec![], false), collector("b", vec![], false));
+        assert!(!quiet.expects_steady_events());
+    }
+
+    #[test]
+    fn direction_displays_and_round_trips() {
+        assert_eq!(Direction::BuyDexSellCex.to_string(), "Buy DEX → Sell HL");
+        assert_eq!(Direction::BuyCexSellDex.to_string(), "Buy HL → Sell DEX");
+        for direction in [Direction::BuyDexSellCex, Direction::BuyCexSellDex] {
+            let json = serde_json::to_string(&direction).unwrap();
+            assert_eq!(serde_json::from_str::<Direction>(&json).unwrap(), direction);
+        }
+        assert_eq!(serde_json::to_string(&Direction::BuyCexSellDex).unwrap(), "\"buy_cex_sell_dex\"");
+    }
+}
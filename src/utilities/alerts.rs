@@ -0,0 +1,172 @@
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use tracing::error;
+
+/// Destination for operator alerts. `send` is fire-and-forget: implementations
+/// spawn their own I/O so alerting never delays trading.
+pub trait Notifier: Send + Sync {
+    fn send(&self, msg: String);
+}
+
+/// Fans each alert out to several notifiers
+pub struct MultiNotifier(pub Vec<Arc<dyn Notifier>>);
+
+impl Notifier for MultiNotifier {
+    fn send(&self, msg: String) {
+        for notifier in &self.0 {
+            notifier.send(msg.clone());
+        }
+    }
+}
+
+/// POSTs each alert as JSON (`{"source", "text", "time"}`, time in ms since epoch) to a
+/// URL, e.g. a Slack/Discord incoming webhook or an alerting gateway
+pub struct WebhookNotifier {
+    client: reqwest::Client,
+    url: String,
+}
+
+impl WebhookNotifier {
+    pub fn new(url: String) -> Self {
+        Self { client: reqwest::Client::new(), url }
+    }
+}
+
+impl Notifier for WebhookNotifier {
+    fn send(&self, msg: String) {
+        let client = self.client.clone();
+        let url = self.url.clone();
+        let time = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or(0);
+        let body = serde_json::json!({ "source": "rustyarb", "text": msg, "time": time });
+
+        tokio::spawn(async move {
+            let result = client.post(&url).json(&body).send().await
+                .and_then(|resp| resp.error_for_status());
+            if let Err(e) = result {
+                error!("Webhook alert failed: {}", e);
+            }
+        });
+    }
+}
+
+const TELEGRAM_API_URL: &str = "https://api.telegram.org";
+
+/// Sends alerts to a Telegram chat through the Bot API
+pub struct TelegramNotifier {
+    client: reqwest::Client,
+    api_url: String,
+    bot_token: String,
+    chat_id: String,
+}
+
+impl TelegramNotifier {
+    pub fn new(bot_token: String, chat_id: String) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            api_url: TELEGRAM_API_URL.to_string(),
+            bot_token,
+            chat_id,
+        }
+    }
+
+    /// Build from `TELEGRAM_BOT_TOKEN` and `TELEGRAM_CHAT_ID`, if both are set
+    pub fn from_env() -> Option<Self> {
+        let bot_token = std::env::var("TELEGRAM_BOT_TOKEN").ok().filter(|s| !s.is_empty())?;
+        let chat_id = std::env::var("TELEGRAM_CHAT_ID").ok().filter(|s| !s.is_empty())?;
+        Some(Self::new(bot_token, chat_id))
+    }
+
+    /// Override the Bot API base URL (e.g. a local mock server)
+    pub fn with_api_url(mut self, api_url: String) -> Self {
+        self.api_url = api_url;
+        self
+    }
+}
+
+impl Notifier for TelegramNotifier {
+    fn send(&self, msg: String) {
+        let client = self.client.clone();
+        let url = format!("{}/bot{}/sendMessage", self.api_url, self.bot_token);
+        let body = serde_json::json!({ "chat_id": self.chat_id, "text": msg });
+
+        tokio::spawn(async move {
+            let result = client.post(&url).json(&body).send().await
+                .and_then(|resp| resp.error_for_status());
+            if let Err(e) = result {
+                error!("Telegram alert failed: {}", e);
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::{http::Uri, Json, Router};
+    use serde_json::Value;
+    use tokio::sync::mpsc;
+
+    use super::*;
+
+    /// Local server that forwards the path and JSON body of every POST it receives
+    async fn mock_server() -> (String, mpsc::UnboundedReceiver<(String, Value)>) {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let app = Router::new().fallback(move |uri: Uri, Json(body): Json<Value>| {
+            let tx = tx.clone();
+            async move {
+                tx.send((uri.path().to_string(), body)).unwrap();
+                Json(serde_json::json!({ "ok": true }))
+            }
+        });
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+        (format!("http://{}", addr), rx)
+    }
+
+    async fn next_request(rx: &mut mpsc::UnboundedReceiver<(String, Value)>) -> (String, Value) {
+        tokio::time::timeout(std::time::Duration::from_secs(5), rx.recv())
+            .await
+            .expect("no alert arrived")
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn telegram_posts_to_the_bot_send_message_endpoint() {
+        let (url, mut rx) = mock_server().await;
+        let notifier = TelegramNotifier::new("123-abc".to_string(), "-10042".to_string()).with_api_url(url);
+
+        notifier.send("🚨 Circuit breaker tripped".to_string());
+        let (path, body) = next_request(&mut rx).await;
+        assert_eq!(path, "/bot123-abc/sendMessage");
+        assert_eq!(body["chat_id"], "-10042");
+        assert_eq!(body["text"], "🚨 Circuit breaker tripped");
+    }
+
+    #[tokio::test]
+    async fn webhook_posts_the_alert_as_json() {
+        let (url, mut rx) = mock_server().await;
+        let before = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_millis() as u64;
+        WebhookNotifier::new(format!("{}/hooks/arb", url)).send("✅ BuyDexSellCex filled".to_string());
+
+        let (path, body) = next_request(&mut rx).await;
+        assert_eq!(path, "/hooks/arb");
+        assert_eq!(body["source"], "rustyarb");
+        assert_eq!(body["text"], "✅ BuyDexSellCex filled");
+        assert!(body["time"].as_u64().unwrap() >= before);
+    }
+
+    #[tokio::test]
+    async fn multi_notifier_reaches_every_webhook() {
+        let (first, mut first_rx) = mock_server().await;
+        let (second, mut second_rx) = mock_server().await;
+        let notifier = MultiNotifier(vec![Arc::new(WebhookNotifier::new(first)), Arc::new(WebhookNotifier::new(second))]);
+
+        notifier.send("⚠️ ONE-SIDED!".to_string());
+        assert_eq!(next_request(&mut first_rx).await.1["text"], "⚠️ ONE-SIDED!");
+        assert_eq!(next_request(&mut second_rx).await.1["text"], "⚠️ ONE-SIDED!");
+    }
+}
@@ -0,0 +1,174 @@
+use std::sync::Arc;
+
+use alloy::primitives::{Address, U256};
+use anyhow::Result;
+use axum::{extract::State, http::StatusCode, routing::post, Json, Router};
+use serde::Deserialize;
+use tracing::info;
+
+use crate::collectors::{
+    cex::{CexBbo, CexVenue},
+    uniswapv3::UniV3PoolState,
+};
+use crate::config::StrategyConfig;
+use crate::strategies::hype_usdc_cross_arbitrage::{Decision, HypeUsdcCrossArbitrage};
+
+/// Hypothetical market snapshot posted to `/evaluate`
+#[derive(Debug, Deserialize)]
+pub struct EvaluateRequest {
+    /// Strategy name from config; defaults to the first enabled strategy
+    pub strategy: Option<String>,
+    pub dex: DexInput,
+    pub hl: HlInput,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct DexInput {
+    /// Pool sqrtPriceX96 as a decimal or 0x-prefixed string
+    pub sqrt_price_x96: String,
+    pub fee: u32,
+    pub token_a_decimals: u8,
+    pub token_b_decimals: u8,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct HlInput {
+    pub bid: f64,
+    pub ask: f64,
+}
+
+impl DexInput {
+    fn to_pool_state(&self) -> Result<UniV3PoolState> {
+        let sqrt_price: U256 = self.sqrt_price_x96.parse()
+            .map_err(|_| anyhow::anyhow!("Invalid sqrt_price_x96 '{}'", self.sqrt_price_x96))?;
+
+        Ok(UniV3PoolState {
+            address: Address::ZERO,
+            block_number: 0,
+            token0: Address::ZERO,
+            token1: Address::ZERO,
+            sqrt_price,
+            liquidity: 0,
+            tick: 0,
+            fee: self.fee,
+            token_a_decimals: self.token_a_decimals,
+            token_b_decimals: self.token_b_decimals,
+            pool: None,
+            received_at: None,
+        })
+    }
+}
+
+impl HlInput {
+    fn to_bbo(&self, venue: CexVenue, coin: &str) -> CexBbo {
+        CexBbo {
+            venue,
+            coin: coin.to_string(),
+            bid: self.bid,
+            ask: self.ask,
+            time: 0,
+            received_at: None,
+        }
+    }
+}
+
+/// Build the router serving `POST /evaluate` for the given strategy configs
+pub fn router(strategies: Vec<StrategyConfig>) -> Router {
+    Router::new()
+        .route("/evaluate", post(evaluate))
+        .with_state(Arc::new(strategies))
+}
+
+/// Serve the what-if endpoint until the listener fails
+pub async fn serve(addr: &str, strategies: Vec<StrategyConfig>) -> Result<()> {
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    info!("🔎 What-if endpoint on http://{}/evaluate", addr);
+    axum::serve(listener, router(strategies)).await?;
+    Ok(())
+}
+
+async fn evaluate(
+    State(strategies): State<Arc<Vec<StrategyConfig>>>,
+    Json(req): Json<EvaluateRequest>,
+) -> Result<Json<Decision>, (StatusCode, String)> {
+    let config = match &req.strategy {
+        Some(name) => strategies.iter().find(|s| &s.name == name),
+        None => strategies.first(),
+    }
+    .ok_or((StatusCode::NOT_FOUND, "Unknown strategy".to_string()))?;
+
+    let strategy = HypeUsdcCrossArbitrage::from_config(config)
+        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    let dex_state = req.dex.to_pool_state()
+        .map_err(|e| (StatusCode::BAD_REQUEST, e.to_string()))?;
+    let hl_bbo = req.hl.to_bbo(config.cex_venue, config.hedge_coin());
+
+    Ok(Json(strategy.evaluate(&dex_state, &hl_bbo)))
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+
+    use super::*;
+
+    fn config() -> StrategyConfig {
+        toml::from_str(r#"
+            name = "hype-usdc"
+            enabled = true
+            pool_address = "0xe712d505572b3f84c1b4deb99e1beab9dd0e23c9"
+            router_address = "0x6D99e7f6747AF2cDbB5164b6DD50e40D4fDe1e77"
+            fee = 3000
+            token_a_address = "0xb88339cb7199b77e23db6e890353e22632ba630f"
+            token_b_address = "0x5555555555555555555555555555555555555555"
+            hyperliquid_coin = "HYPE/USDC"
+            order_size_usd = 1000.0
+            hl_maker_fee_bps = 0.0
+            dex_gas_fee_usd = 1.0
+            min_profit_bps = 10.0
+            slippage_bps = 0.0
+        "#).unwrap()
+    }
+
+    async fn serve_locally() -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, router(vec![config()])).await.unwrap() });
+        format!("http://{}/evaluate", addr)
+    }
+
+    #[tokio::test]
+    async fn evaluate_returns_the_strategys_own_decision() {
+        // HYPE (token0, 18 decimals) at $40 in USDC (6 decimals), HL bid 2% above it
+        let request = json!({
+            "dex": { "sqrt_price_x96": "501082896750095862372827", "fee": 3000, "token_a_decimals": 18, "token_b_decimals": 6 },
+            "hl": { "bid": 40.8, "ask": 40.85 },
+        });
+        let resp = reqwest::Client::new().post(serve_locally().await).json(&request).send().await.unwrap();
+        assert_eq!(resp.status().as_u16(), 200);
+        let body: Value = resp.json().await.unwrap();
+
+        let req: EvaluateRequest = serde_json::from_value(request).unwrap();
+        let config = config();
+        let direct = HypeUsdcCrossArbitrage::from_config(&config).unwrap()
+            .evaluate(&req.dex.to_pool_state().unwrap(), &req.hl.to_bbo(config.cex_venue, config.hedge_coin()));
+        assert!(matches!(direct, Decision::Trade { .. }));
+        assert_eq!(body, serde_json::to_value(&direct).unwrap());
+        assert_eq!(body["decision"], "trade");
+        assert_eq!(body["direction"], "buy_dex_sell_cex");
+    }
+
+    #[tokio::test]
+    async fn unknown_strategy_or_bad_input_is_rejected() {
+        let url = serve_locally().await;
+        let client = reqwest::Client::new();
+        let dex = json!({ "sqrt_price_x96": "501082896750095862372827", "fee": 3000, "token_a_decimals": 18, "token_b_decimals": 6 });
+        let hl = json!({ "bid": 40.0, "ask": 40.1 });
+
+        let unknown = json!({ "strategy": "eth-usdc", "dex": dex, "hl": hl.clone() });
+        assert_eq!(client.post(&url).json(&unknown).send().await.unwrap().status().as_u16(), 404);
+
+        let bad_price = json!({ "dex": { "sqrt_price_x96": "not a number", "fee": 3000, "token_a_decimals": 18, "token_b_decimals": 6 }, "hl": hl });
+        assert_eq!(client.post(&url).json(&bad_price).send().await.unwrap().status().as_u16(), 400);
+    }
+}
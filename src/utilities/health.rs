@@ -0,0 +1,236 @@
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use anyhow::Result;
+use axum::{extract::State, http::StatusCode, routing::get, Json, Router};
+use serde::Serialize;
+use tracing::info;
+
+fn now_ms() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// Liveness of one collector, updated by the engine's collector loop
+#[derive(Debug)]
+pub struct CollectorProbe {
+    name: String,
+    /// Held to `stale_after` for readiness; false for trigger-driven collectors
+    steady: bool,
+    alive: AtomicBool,
+    /// 0 until the first event
+    last_event_ms: AtomicU64,
+}
+
+impl CollectorProbe {
+    /// Mark the collector alive until the returned guard drops, including on panic
+    pub fn alive_guard(self: &Arc<Self>) -> AliveGuard {
+        self.alive.store(true, Ordering::SeqCst);
+        AliveGuard(self.clone())
+    }
+
+    pub fn touch(&self) {
+        self.last_event_ms.store(now_ms(), Ordering::SeqCst);
+    }
+}
+
+pub struct AliveGuard(Arc<CollectorProbe>);
+
+impl Drop for AliveGuard {
+    fn drop(&mut self) {
+        self.0.alive.store(false, Ordering::SeqCst);
+    }
+}
+
+type HaltCheck = Box<dyn Fn() -> bool + Send + Sync>;
+
+/// Shared health state behind `/healthz` and `/readyz`
+pub struct HealthState {
+    /// A feed is stale (not ready) once this long passes without an event
+    stale_after: Duration,
+    collectors: Mutex<Vec<Arc<CollectorProbe>>>,
+    halt_checks: Mutex<Vec<(String, HaltCheck)>>,
+}
+
+#[derive(Debug, Serialize)]
+struct CollectorReport {
+    name: String,
+    alive: bool,
+    steady: bool,
+    /// Milliseconds since the last event, if any arrived
+    last_event_age_ms: Option<u64>,
+}
+
+#[derive(Debug, Serialize)]
+struct HealthReport {
+    ok: bool,
+    collectors: Vec<CollectorReport>,
+    halted: Vec<String>,
+}
+
+impl HealthState {
+    pub fn new(stale_after: Duration) -> Self {
+        Self {
+            stale_after,
+            collectors: Mutex::new(Vec::new()),
+            halt_checks: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Track a collector; the engine calls this once per collector
+    pub fn register_collector(&self, name: String, steady: bool) -> Arc<CollectorProbe> {
+        let probe = Arc::new(CollectorProbe {
+            name,
+            steady,
+            alive: AtomicBool::new(false),
+            last_event_ms: AtomicU64::new(0),
+        });
+        self.collectors.lock().unwrap().push(probe.clone());
+        probe
+    }
+
+    /// Report not-ready while `is_halted` returns true (e.g. a slippage halt)
+    pub fn register_halt_check(&self, name: &str, is_halted: impl Fn() -> bool + Send + Sync + 'static) {
+        self.halt_checks.lock().unwrap().push((name.to_string(), Box::new(is_halted)));
+    }
+
+    fn collector_reports(&self) -> Vec<CollectorReport> {
+        let now = now_ms();
+        self.collectors.lock().unwrap().iter()
+            .map(|probe| {
+                let last = probe.last_event_ms.load(Ordering::SeqCst);
+                CollectorReport {
+                    name: probe.name.clone(),
+                    alive: probe.alive.load(Ordering::SeqCst),
+                    steady: probe.steady,
+                    last_event_age_ms: (last > 0).then(|| now.saturating_sub(last)),
+                }
+            })
+            .collect()
+    }
+
+    /// Live while every collector task is running
+    fn liveness(&self) -> HealthReport {
+        let collectors = self.collector_reports();
+        HealthReport {
+            ok: collectors.iter().all(|c| c.alive),
+            collectors,
+            halted: Vec::new(),
+        }
+    }
+
+    /// Ready while every collector is live, every steady one has reported within
+    /// `stale_after`, and nothing is halted
+    fn readiness(&self) -> HealthReport {
+        let stale_ms = self.stale_after.as_millis() as u64;
+        let collectors = self.collector_reports();
+        let halted: Vec<String> = self.halt_checks.lock().unwrap().iter()
+            .filter(|(_, is_halted)| is_halted())
+            .map(|(name, _)| name.clone())
+            .collect();
+        let fresh = collectors.iter()
+            .all(|c| c.alive && (!c.steady || c.last_event_age_ms.is_some_and(|age| age <= stale_ms)));
+        HealthReport {
+            ok: fresh && halted.is_empty(),
+            collectors,
+            halted,
+        }
+    }
+}
+
+/// Build the router serving `GET /healthz` and `GET /readyz` (also as `/health` and `/ready`)
+pub fn router(state: Arc<HealthState>) -> Router {
+    Router::new()
+        .route("/healthz", get(healthz))
+        .route("/health", get(healthz))
+        .route("/readyz", get(readyz))
+        .route("/ready", get(readyz))
+        .with_state(state)
+}
+
+/// Serve the probes until the listener fails
+pub async fn serve(addr: &str, state: Arc<HealthState>) -> Result<()> {
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    info!("🩺 Health probes on http://{}/healthz and /readyz", addr);
+    axum::serve(listener, router(state)).await?;
+    Ok(())
+}
+
+fn respond(report: HealthReport) -> (StatusCode, Json<HealthReport>) {
+    let status = if report.ok { StatusCode::OK } else { StatusCode::SERVICE_UNAVAILABLE };
+    (status, Json(report))
+}
+
+async fn healthz(State(state): State<Arc<HealthState>>) -> (StatusCode, Json<HealthReport>) {
+    respond(state.liveness())
+}
+
+async fn readyz(State(state): State<Arc<HealthState>>) -> (StatusCode, Json<HealthReport>) {
+    respond(state.readiness())
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::Value;
+
+    use super::*;
+
+    async fn serve_locally(state: Arc<HealthState>) -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, router(state)).await.unwrap() });
+        format!("http://{}", addr)
+    }
+
+    async fn probe(url: &str) -> (u16, Value) {
+        let resp = reqwest::get(url).await.unwrap();
+        let status = resp.status().as_u16();
+        (status, resp.json().await.unwrap())
+    }
+
+    #[tokio::test]
+    async fn ready_only_once_every_feed_has_reported() {
+        let state = Arc::new(HealthState::new(Duration::from_secs(30)));
+        let pool = state.register_collector("uniswapv3".to_string(), true);
+        let _alive = pool.alive_guard();
+        let halted = Arc::new(AtomicBool::new(false));
+        let flag = halted.clone();
+        state.register_halt_check("circuit_breaker", move || flag.load(Ordering::SeqCst));
+        let url = serve_locally(state).await;
+
+        // Running, but no event yet
+        assert_eq!(probe(&format!("{}/healthz", url)).await.0, 200);
+        let (status, body) = probe(&format!("{}/readyz", url)).await;
+        assert_eq!(status, 503);
+        assert_eq!(body["ok"], false);
+        assert_eq!(body["collectors"][0]["name"], "uniswapv3");
+        assert!(body["collectors"][0]["last_event_age_ms"].is_null());
+
+        pool.touch();
+        let (status, body) = probe(&format!("{}/ready", url)).await;
+        assert_eq!(status, 200);
+        assert!(body["collectors"][0]["last_event_age_ms"].as_u64().unwrap() < 30_000);
+
+        halted.store(true, Ordering::SeqCst);
+        let (status, body) = probe(&format!("{}/readyz", url)).await;
+        assert_eq!(status, 503);
+        assert_eq!(body["halted"][0], "circuit_breaker");
+    }
+
+    #[tokio::test]
+    async fn dead_collector_fails_liveness() {
+        let state = Arc::new(HealthState::new(Duration::from_secs(30)));
+        let probe_handle = state.register_collector("hyperliquid".to_string(), true);
+        let url = serve_locally(state).await;
+
+        let alive = probe_handle.alive_guard();
+        assert_eq!(probe(&format!("{}/health", url)).await.0, 200);
+        drop(alive);
+        let (status, body) = probe(&format!("{}/healthz", url)).await;
+        assert_eq!(status, 503);
+        assert_eq!(body["collectors"][0]["alive"], false);
+    }
+}
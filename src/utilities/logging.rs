@@ -0,0 +1,45 @@
+use serde::Deserialize;
+use tracing::{Level, Subscriber};
+use tracing_subscriber::{filter, fmt::MakeWriter, prelude::*, registry::LookupSpan, Layer};
+
+/// Log output format: human-readable lines or one JSON object per event
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    #[default]
+    Pretty,
+    Json,
+}
+
+impl LogFormat {
+    /// `LOG_FORMAT=json|pretty` overrides the configured format
+    pub fn from_env_or(default: LogFormat) -> LogFormat {
+        match std::env::var("LOG_FORMAT").ok().as_deref().map(str::to_ascii_lowercase).as_deref() {
+            Some("json") => LogFormat::Json,
+            Some("pretty") => LogFormat::Pretty,
+            _ => default,
+        }
+    }
+}
+
+/// Install the global subscriber for the bot's own targets at INFO
+pub fn init_tracing(format: LogFormat) {
+    let filter = filter::Targets::new()
+        .with_target("rustyarb", Level::INFO);
+    tracing_subscriber::registry()
+        .with(fmt_layer(format, std::io::stdout))
+        .with(filter)
+        .init();
+}
+
+/// Formatting layer for `format`, writing through `writer`
+pub(crate) fn fmt_layer<S, W>(format: LogFormat, writer: W) -> Box<dyn Layer<S> + Send + Sync>
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    W: for<'w> MakeWriter<'w> + Send + Sync + 'static,
+{
+    match format {
+        LogFormat::Json => tracing_subscriber::fmt::layer().json().with_writer(writer).boxed(),
+        LogFormat::Pretty => tracing_subscriber::fmt::layer().with_writer(writer).boxed(),
+    }
+}
@@ -0,0 +1,8 @@
+pub mod alerts;
+pub mod evaluate;
+pub mod health;
+pub mod logging;
+pub mod price_oracle;
+pub mod reconcile;
+pub mod retry;
+pub mod tokens;
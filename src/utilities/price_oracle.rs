@@ -0,0 +1,92 @@
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use tracing::warn;
+
+const COINGECKO_API_URL: &str = "https://api.coingecko.com/api/v3";
+
+/// Latest USD price of the chain's native gas token, shared between the poller and
+/// its readers. Prices older than `ttl` are not trusted; readers get the fallback instead.
+#[derive(Debug)]
+pub struct NativePriceOracle {
+    latest: Mutex<Option<(f64, Instant)>>,
+    ttl: Duration,
+    fallback_usd: Option<f64>,
+}
+
+impl NativePriceOracle {
+    pub fn new(ttl: Duration, fallback_usd: Option<f64>) -> Self {
+        Self { latest: Mutex::new(None), ttl, fallback_usd }
+    }
+
+    pub fn update(&self, price_usd: f64) {
+        *self.latest.lock().unwrap() = Some((price_usd, Instant::now()));
+    }
+
+    /// Fresh price, else the configured fallback
+    pub fn price_usd(&self) -> Option<f64> {
+        let latest = *self.latest.lock().unwrap();
+        latest
+            .filter(|(_, fetched_at)| fetched_at.elapsed() < self.ttl)
+            .map(|(price, _)| price)
+            .or(self.fallback_usd)
+    }
+}
+
+/// Polls CoinGecko's simple price endpoint for one coin id (e.g. "hyperliquid")
+pub struct CoinGeckoPoller {
+    client: reqwest::Client,
+    api_url: String,
+    coin_id: String,
+    poll_interval: Duration,
+}
+
+impl CoinGeckoPoller {
+    pub fn new(coin_id: String) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            api_url: COINGECKO_API_URL.to_string(),
+            coin_id,
+            poll_interval: Duration::from_secs(60),
+        }
+    }
+
+    pub fn with_poll_interval(mut self, poll_interval: Duration) -> Self {
+        self.poll_interval = poll_interval;
+        self
+    }
+
+    /// Override the API base URL (e.g. a pro endpoint or local mock server)
+    pub fn with_api_url(mut self, api_url: String) -> Self {
+        self.api_url = api_url;
+        self
+    }
+
+    async fn fetch(&self) -> Result<f64> {
+        let url = format!("{}/simple/price", self.api_url);
+        let body: serde_json::Value = self.client.get(&url)
+            .query(&[("ids", self.coin_id.as_str()), ("vs_currencies", "usd")])
+            .send().await?
+            .error_for_status()?
+            .json().await?;
+
+        body[&self.coin_id]["usd"].as_f64()
+            .filter(|price| price.is_finite() && *price > 0.0)
+            .ok_or_else(|| anyhow::anyhow!("No USD price for '{}' in response", self.coin_id))
+    }
+
+    /// Keep `oracle` updated on a background task; failures leave the last price to age out
+    pub fn spawn(self, oracle: Arc<NativePriceOracle>) {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(self.poll_interval);
+            loop {
+                interval.tick().await;
+                match self.fetch().await {
+                    Ok(price) => oracle.update(price),
+                    Err(e) => warn!("Native price fetch failed: {}", e),
+                }
+            }
+        });
+    }
+}
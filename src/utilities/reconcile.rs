@@ -0,0 +1,289 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use alloy::{
+    primitives::{Address, U256},
+    providers::Provider,
+};
+use anyhow::Result;
+use hyperliquid_rust_sdk::{BaseUrl, InfoClient};
+use tracing::{error, info, warn};
+
+use crate::collectors::balance::IERC20Balance;
+use crate::execution::ExecutionManager;
+use crate::utilities::alerts::Notifier;
+use crate::utilities::tokens::TokenRegistry;
+
+/// Holdings of the traded token on each venue, in whole tokens
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Exposure {
+    /// ERC20 balance, plus the native balance when the DEX leg trades native HYPE
+    pub wallet: f64,
+    pub hl_spot: f64,
+    /// Signed perp position (short is negative)
+    pub hl_perp: f64,
+}
+
+impl Exposure {
+    /// Combined holdings; a fully hedged round trip leaves this unchanged
+    pub fn net(&self) -> f64 {
+        self.wallet + self.hl_spot + self.hl_perp
+    }
+}
+
+/// Net exposure minus the expected level, in whole tokens
+pub fn drift(expected: f64, actual: &Exposure) -> f64 {
+    actual.net() - expected
+}
+
+/// Latest reconciliation result, shared with the executor and health checks
+#[derive(Debug, Default)]
+pub struct InventoryDrift {
+    latest: Mutex<Option<f64>>,
+    halted: AtomicBool,
+    /// Native HYPE burned on swap gas since startup
+    gas_spent: Mutex<f64>,
+}
+
+impl InventoryDrift {
+    /// Drift at the last successful reconciliation
+    pub fn latest(&self) -> Option<f64> {
+        *self.latest.lock().unwrap()
+    }
+
+    /// Set once drift reached the halt threshold; cleared only by a restart
+    pub fn is_halted(&self) -> bool {
+        self.halted.load(Ordering::SeqCst)
+    }
+
+    /// Record gas paid for a swap, in native HYPE, so it is not counted as drift
+    pub fn record_gas(&self, native: f64) {
+        *self.gas_spent.lock().unwrap() += native;
+    }
+
+    pub fn gas_spent(&self) -> f64 {
+        *self.gas_spent.lock().unwrap()
+    }
+}
+
+/// Periodically compares actual holdings across the wallet and HL against the level at
+/// startup. Round trips are delta neutral, so lasting drift means a missed, partial or
+/// unwound leg (or a bug) left the book one-sided.
+pub struct Reconciler<P> {
+    provider: Arc<P>,
+    wallet: Address,
+    hl_user: Address,
+    token: Address,
+    include_native: bool,
+    /// HL name of the token, for both spot balances and the perp position
+    coin: String,
+    interval: Duration,
+    alert_drift: f64,
+    halt_drift: Option<f64>,
+    halt_samples: u32,
+    exec_manager: Option<Arc<ExecutionManager>>,
+    notifier: Option<Arc<dyn Notifier>>,
+    tokens: Arc<TokenRegistry<P>>,
+    drift: Arc<InventoryDrift>,
+}
+
+fn to_tokens(raw: U256, decimals: u8) -> f64 {
+    raw.to_string().parse::<f64>().unwrap_or(0.0) / 10_f64.powi(decimals as i32)
+}
+
+impl<P: Provider + 'static> Reconciler<P> {
+    pub fn new(
+        provider: Arc<P>,
+        wallet: Address,
+        hl_user: Address,
+        token: Address,
+        coin: String,
+    ) -> Self {
+        Self {
+            tokens: Arc::new(TokenRegistry::new(provider.clone())),
+            provider,
+            wallet,
+            hl_user,
+            token,
+            include_native: false,
+            coin,
+            interval: Duration::from_secs(300),
+            alert_drift: 0.0,
+            halt_drift: None,
+            halt_samples: 1,
+            exec_manager: None,
+            notifier: None,
+            drift: Arc::new(InventoryDrift::default()),
+        }
+    }
+
+    pub fn with_interval(mut self, interval: Duration) -> Self {
+        self.interval = interval;
+        self
+    }
+
+    /// Count the native balance too (auto-wrap trades native HYPE). Swap gas recorded
+    /// through `InventoryDrift::record_gas` is added back; other gas spend shows as drift.
+    pub fn with_native(mut self) -> Self {
+        self.include_native = true;
+        self
+    }
+
+    /// Alert when drift exceeds `alert_drift` tokens and halt trading at `halt_drift`
+    pub fn with_thresholds(mut self, alert_drift: f64, halt_drift: Option<f64>) -> Self {
+        self.alert_drift = alert_drift;
+        self.halt_drift = halt_drift;
+        self
+    }
+
+    /// Halt only after this many consecutive samples at or beyond `halt_drift`
+    pub fn with_halt_samples(mut self, samples: u32) -> Self {
+        self.halt_samples = samples.max(1);
+        self
+    }
+
+    /// Skip samples while a trade is in flight; its legs settle at different times
+    pub fn with_execution_manager(mut self, exec_manager: Arc<ExecutionManager>) -> Self {
+        self.exec_manager = Some(exec_manager);
+        self
+    }
+
+    pub fn with_notifier(mut self, notifier: Arc<dyn Notifier>) -> Self {
+        self.notifier = Some(notifier);
+        self
+    }
+
+    /// Resolve the token's decimals through a shared registry
+    pub fn with_token_registry(mut self, tokens: Arc<TokenRegistry<P>>) -> Self {
+        self.tokens = tokens;
+        self
+    }
+
+    /// Shared handle to the latest drift and halt state
+    pub fn drift(&self) -> Arc<InventoryDrift> {
+        self.drift.clone()
+    }
+
+    async fn fetch(&self, info_client: &InfoClient, token_decimals: u8) -> Result<Exposure> {
+        let erc20 = IERC20Balance::new(self.token, &*self.provider).balanceOf(self.wallet).call().await?;
+        let mut wallet = to_tokens(erc20, token_decimals);
+        if self.include_native {
+            wallet += to_tokens(self.provider.get_balance(self.wallet).await?, 18) + self.drift.gas_spent();
+        }
+
+        let spot = info_client.user_token_balances(self.hl_user).await
+            .map_err(|e| anyhow::anyhow!("HL spot balances: {:?}", e))?;
+        let hl_spot = spot.balances.iter()
+            .filter(|b| b.coin == self.coin)
+            .filter_map(|b| b.total.parse::<f64>().ok())
+            .sum();
+
+        let perp = info_client.user_state(self.hl_user).await
+            .map_err(|e| anyhow::anyhow!("HL user state: {:?}", e))?;
+        let hl_perp = perp.asset_positions.iter()
+            .filter(|p| p.position.coin == self.coin)
+            .filter_map(|p| p.position.szi.parse::<f64>().ok())
+            .sum();
+
+        Ok(Exposure { wallet, hl_spot, hl_perp })
+    }
+
+    fn alert(&self, msg: String) {
+        if let Some(notifier) = &self.notifier {
+            notifier.send(msg);
+        }
+    }
+
+    /// Reconcile on a background task; the first successful fetch sets the expected level
+    pub fn spawn(self) {
+        tokio::spawn(async move {
+            let info_client = match InfoClient::new(None, Some(BaseUrl::Mainnet)).await {
+                Ok(client) => client,
+                Err(e) => {
+                    error!("Reconcile: no HL info client, reconciliation disabled: {:?}", e);
+                    return;
+                }
+            };
+            let token_decimals = match self.tokens.decimals(self.token).await {
+                Ok(decimals) => decimals,
+                Err(e) => {
+                    error!("Reconcile: {} - reconciliation disabled", e);
+                    return;
+                }
+            };
+            let mut expected = None;
+            let mut breaches = 0;
+            let mut interval = tokio::time::interval(self.interval);
+            loop {
+                interval.tick().await;
+                if self.exec_manager.as_ref().is_some_and(|manager| manager.is_busy()) {
+                    info!("Reconcile: trade in flight, skipping sample");
+                    continue;
+                }
+                let exposure = match self.fetch(&info_client, token_decimals).await {
+                    Ok(exposure) => exposure,
+                    Err(e) => {
+                        warn!("Reconcile: balance fetch failed: {}", e);
+                        continue;
+                    }
+                };
+                let Some(baseline) = expected else {
+                    info!("Reconcile: baseline {} {:.4} (wallet {:.4}, HL spot {:.4}, HL perp {:+.4})",
+                        self.coin, exposure.net(), exposure.wallet, exposure.hl_spot, exposure.hl_perp);
+                    expected = Some(exposure.net());
+                    *self.drift.latest.lock().unwrap() = Some(0.0);
+                    continue;
+                };
+
+                let drift = drift(baseline, &exposure);
+                *self.drift.latest.lock().unwrap() = Some(drift);
+                if self.halt_drift.is_some_and(|halt| drift.abs() >= halt) {
+                    breaches += 1;
+                } else {
+                    breaches = 0;
+                }
+                if breaches >= self.halt_samples && !self.drift.is_halted() {
+                    self.drift.halted.store(true, Ordering::SeqCst);
+                    error!(drift, "🚨 Reconcile: {} drifted {:+.4} - halting new trades", self.coin, drift);
+                    self.alert(format!("🚨 {} inventory drifted {:+.4} from baseline - trading halted", self.coin, drift));
+                } else if drift.abs() > self.alert_drift {
+                    warn!(drift, "⚠️ Reconcile: {} drifted {:+.4} (wallet {:.4}, HL spot {:.4}, HL perp {:+.4})",
+                        self.coin, drift, exposure.wallet, exposure.hl_spot, exposure.hl_perp);
+                    self.alert(format!("⚠️ {} inventory drifted {:+.4} from baseline", self.coin, drift));
+                } else {
+                    info!(drift, "Reconcile: {} drift {:+.4}", self.coin, drift);
+                }
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hedged_round_trip_leaves_no_drift() {
+        let before = Exposure { wallet: 100.0, hl_spot: 20.0, hl_perp: -50.0 };
+        // Bought 10 on the DEX, sold 10 perp
+        let after = Exposure { wallet: 110.0, hl_spot: 20.0, hl_perp: -60.0 };
+        assert_eq!(drift(before.net(), &after), 0.0);
+
+        // Hedge missed: the DEX leg alone shows up
+        let unhedged = Exposure { wallet: 110.0, ..before };
+        assert_eq!(drift(before.net(), &unhedged), 10.0);
+        let oversold = Exposure { hl_perp: -62.5, ..after };
+        assert_eq!(drift(before.net(), &oversold), -2.5);
+    }
+
+    #[test]
+    fn recorded_gas_accumulates() {
+        let drift = InventoryDrift::default();
+        assert_eq!(drift.gas_spent(), 0.0);
+        drift.record_gas(0.25);
+        drift.record_gas(0.5);
+        assert_eq!(drift.gas_spent(), 0.75);
+        assert!(!drift.is_halted());
+    }
+}
@@ -0,0 +1,151 @@
+use std::future::Future;
+use std::time::Duration;
+
+use anyhow::Result;
+use tracing::warn;
+
+/// Exponential backoff for transient RPC/API failures
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    /// Retries after the first attempt; 0 disables retrying
+    pub max_retries: u32,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 0,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(2),
+        }
+    }
+}
+
+/// Error text fragments showing the request never reached the venue (no connection)
+/// or was refused unprocessed (rate limit). Timeouts, 5xx and nonce errors are left out:
+/// they can arrive after the tx or order was accepted, and a resend would duplicate it.
+const RETRYABLE: &[&str] = &[
+    "connection refused", "error trying to connect", "dns error", "failed to lookup address",
+    "429", "too many requests", "rate limit",
+];
+
+/// Error text fragments that will fail again no matter how often we retry
+const TERMINAL: &[&str] = &["revert", "insufficient funds", "insufficient balance", "invalid"];
+
+/// Whether an error will recur on every attempt (revert, insufficient funds, bad input)
+pub fn is_terminal(error: &anyhow::Error) -> bool {
+    let msg = format!("{:#}", error).to_lowercase();
+    TERMINAL.iter().any(|t| msg.contains(t))
+}
+
+/// Whether an error shows the request was never processed (connect failure, rate
+/// limit), so it is safe to send again
+pub fn is_retryable(error: &anyhow::Error) -> bool {
+    let msg = format!("{:#}", error).to_lowercase();
+    !is_terminal(error) && RETRYABLE.iter().any(|t| msg.contains(t))
+}
+
+/// Run `op` until it succeeds, fails terminally, or retries run out, doubling the
+/// delay between attempts up to `max_backoff`. `label` names the operation in logs.
+pub async fn with_retry<T, F, Fut>(policy: &RetryPolicy, label: &str, op: F) -> Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    with_retry_guarded(policy, label, op, || std::future::ready(true)).await
+}
+
+/// [with_retry] with the caller's own test of which errors are worth retrying
+pub async fn with_retry_if<T, F, Fut>(policy: &RetryPolicy, label: &str, op: F, retryable: fn(&anyhow::Error) -> bool) -> Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    retry_loop(policy, label, op, || std::future::ready(true), retryable).await
+}
+
+/// [with_retry], asking `safe_to_resend` before each retry whether the failed attempt
+/// could have gone through after all (e.g. its nonce was used); `false` returns the error
+pub async fn with_retry_guarded<T, F, Fut, G, GFut>(policy: &RetryPolicy, label: &str, op: F, safe_to_resend: G) -> Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T>>,
+    G: FnMut() -> GFut,
+    GFut: Future<Output = bool>,
+{
+    retry_loop(policy, label, op, safe_to_resend, is_retryable).await
+}
+
+async fn retry_loop<T, F, Fut, G, GFut>(
+    policy: &RetryPolicy,
+    label: &str,
+    mut op: F,
+    mut safe_to_resend: G,
+    retryable: fn(&anyhow::Error) -> bool,
+) -> Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T>>,
+    G: FnMut() -> GFut,
+    GFut: Future<Output = bool>,
+{
+    let mut backoff = policy.initial_backoff;
+    let mut attempt = 0;
+    loop {
+        match op().await {
+            Ok(value) => return Ok(value),
+            Err(e) if attempt < policy.max_retries && retryable(&e) => {
+                if !safe_to_resend().await {
+                    warn!("{} failed ({}) and may have gone through - not retrying", label, e);
+                    return Err(e);
+                }
+                attempt += 1;
+                warn!("{} failed ({}), retry {}/{} in {}ms", label, e, attempt, policy.max_retries, backoff.as_millis());
+                tokio::time::sleep(backoff).await;
+                backoff = (backoff * 2).min(policy.max_backoff);
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::Cell;
+
+    use super::*;
+
+    const POLICY: RetryPolicy = RetryPolicy {
+        max_retries: 3,
+        initial_backoff: Duration::ZERO,
+        max_backoff: Duration::ZERO,
+    };
+
+    #[test]
+    fn only_unprocessed_requests_are_retryable() {
+        assert!(is_retryable(&anyhow::anyhow!("error trying to connect: Connection refused")));
+        assert!(is_retryable(&anyhow::anyhow!("HTTP 429 Too Many Requests")));
+        // May have been accepted before the error surfaced
+        assert!(!is_retryable(&anyhow::anyhow!("request timed out")));
+        assert!(!is_retryable(&anyhow::anyhow!("nonce too low")));
+        assert!(!is_retryable(&anyhow::anyhow!("503 Service Unavailable")));
+        assert!(!is_retryable(&anyhow::anyhow!("execution reverted after 429")));
+    }
+
+    #[tokio::test]
+    async fn guard_stops_resending() {
+        let attempts = Cell::new(0);
+        let op = || {
+            attempts.set(attempts.get() + 1);
+            async { Err::<(), _>(anyhow::anyhow!("rate limit")) }
+        };
+        assert!(with_retry_guarded(&POLICY, "test", op, || std::future::ready(false)).await.is_err());
+        assert_eq!(attempts.get(), 1);
+
+        attempts.set(0);
+        assert!(with_retry(&POLICY, "test", op).await.is_err());
+        assert_eq!(attempts.get(), 1 + POLICY.max_retries);
+    }
+}
@@ -0,0 +1,84 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+
+use alloy::{primitives::Address, providers::Provider};
+use anyhow::Result;
+
+use crate::collectors::balance::IERC20Balance;
+
+/// ERC20 metadata the bot needs to convert between raw and human amounts
+#[derive(Debug, Clone, PartialEq)]
+pub struct TokenInfo {
+    pub address: Address,
+    pub decimals: u8,
+    pub symbol: String,
+}
+
+/// Resolves token addresses to their on-chain `decimals()` and `symbol()`, caching each
+/// token after its first lookup (metadata is immutable for the tokens we trade)
+pub struct TokenRegistry<P> {
+    provider: Arc<P>,
+    cache: Mutex<HashMap<Address, TokenInfo>>,
+}
+
+impl<P: Provider> TokenRegistry<P> {
+    pub fn new(provider: Arc<P>) -> Self {
+        Self { provider, cache: Mutex::new(HashMap::new()) }
+    }
+
+    /// Metadata for `token`, fetched on first use
+    pub async fn get(&self, token: Address) -> Result<TokenInfo> {
+        if let Some(info) = self.cache.lock().unwrap().get(&token).cloned() {
+            return Ok(info);
+        }
+
+        let erc20 = IERC20Balance::new(token, &*self.provider);
+        let decimals = erc20.decimals().call().await
+            .map_err(|e| anyhow::anyhow!("decimals() of {}: {}", token, e))?;
+        // symbol() is optional in ERC20; fall back to the address
+        let symbol = erc20.symbol().call().await.unwrap_or_else(|_| token.to_string());
+        let info = TokenInfo { address: token, decimals, symbol };
+        self.cache.lock().unwrap().insert(token, info.clone());
+        Ok(info)
+    }
+
+    pub async fn decimals(&self, token: Address) -> Result<u8> {
+        Ok(self.get(token).await?.decimals)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy::{
+        primitives::Bytes,
+        providers::ProviderBuilder,
+        sol_types::SolCall,
+        transports::mock::Asserter,
+    };
+
+    use crate::collectors::balance::IERC20Balance::{decimalsCall, symbolCall};
+
+    use super::*;
+
+    fn push_metadata(asserter: &Asserter, decimals: u8, symbol: &str) {
+        asserter.push_success(&Bytes::from(decimalsCall::abi_encode_returns(&decimals)));
+        asserter.push_success(&Bytes::from(symbolCall::abi_encode_returns(&symbol.to_string())));
+    }
+
+    #[tokio::test]
+    async fn second_lookup_is_served_from_the_cache() {
+        let (whype, usdc) = (Address::repeat_byte(0x11), Address::repeat_byte(0x22));
+        let asserter = Asserter::new();
+        push_metadata(&asserter, 18, "WHYPE");
+        push_metadata(&asserter, 6, "USDC");
+        let registry = TokenRegistry::new(Arc::new(ProviderBuilder::new().connect_mocked_client(asserter)));
+
+        assert_eq!(registry.get(whype).await.unwrap(), TokenInfo { address: whype, decimals: 18, symbol: "WHYPE".into() });
+        assert_eq!(registry.get(usdc).await.unwrap(), TokenInfo { address: usdc, decimals: 6, symbol: "USDC".into() });
+
+        // Nothing else is queued, so these only succeed without touching the provider
+        assert_eq!(registry.decimals(whype).await.unwrap(), 18);
+        assert_eq!(registry.get(usdc).await.unwrap().symbol, "USDC");
+        assert!(registry.get(Address::repeat_byte(0x33)).await.is_err());
+    }
+}